@@ -1,25 +1,33 @@
-// Generated by `wit-bindgen` 0.36.0. DO NOT EDIT!
+// Generated by `wit-bindgen` 0.41.0. DO NOT EDIT!
 // Options used:
 //   * runtime_path: "wit_bindgen_rt"
 #[rustfmt::skip]
 #[allow(dead_code, clippy::all)]
 pub mod midoku {
     pub mod http {
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod types {
             #[used]
             #[doc(hidden)]
             static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
             use super::super::super::_rt;
             /// Represents an HTTP method.
-            #[repr(u8)]
-            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            #[derive(Clone)]
             pub enum Method {
                 Get,
                 Post,
                 Put,
                 Head,
                 Delete,
+                Patch,
+                Options,
+                Connect,
+                Trace,
+                /// Any other method, e.g. `PURGE`.
+                ///
+                /// The method must be a valid HTTP token, otherwise the request
+                /// fails.
+                Other(_rt::String),
             }
             impl ::core::fmt::Debug for Method {
                 fn fmt(
@@ -32,26 +40,248 @@ pub mod midoku {
                         Method::Put => f.debug_tuple("Method::Put").finish(),
                         Method::Head => f.debug_tuple("Method::Head").finish(),
                         Method::Delete => f.debug_tuple("Method::Delete").finish(),
+                        Method::Patch => f.debug_tuple("Method::Patch").finish(),
+                        Method::Options => f.debug_tuple("Method::Options").finish(),
+                        Method::Connect => f.debug_tuple("Method::Connect").finish(),
+                        Method::Trace => f.debug_tuple("Method::Trace").finish(),
+                        Method::Other(e) => {
+                            f.debug_tuple("Method::Other").field(e).finish()
+                        }
+                    }
+                }
+            }
+            /// Represents an error sending a request or reading its response.
+            #[derive(Clone)]
+            pub enum HttpError {
+                /// The URL is not valid, or its scheme is not supported.
+                InvalidUrl(_rt::String),
+                /// A header name or value is not valid, contains the name of the
+                /// header.
+                InvalidHeader(_rt::String),
+                /// The host name could not be resolved.
+                Dns(_rt::String),
+                /// The connection to the server could not be established.
+                Connect(_rt::String),
+                /// The TLS handshake failed, e.g. because of an invalid certificate.
+                Tls(_rt::String),
+                /// The request, or reading its response, timed out.
+                Timeout,
+                /// The server redirected the request too many times.
+                TooManyRedirects,
+                /// The response body is larger than the host allows.
+                BodyTooLarge,
+                /// The host does not allow the source to send requests to the URL,
+                /// e.g. because its host is not in the allowlist of the source or is
+                /// a private address.
+                Blocked(_rt::String),
+                /// Any other error, e.g. the connection was closed while reading the
+                /// response.
+                Other(_rt::String),
+            }
+            impl ::core::fmt::Debug for HttpError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        HttpError::InvalidUrl(e) => {
+                            f.debug_tuple("HttpError::InvalidUrl").field(e).finish()
+                        }
+                        HttpError::InvalidHeader(e) => {
+                            f.debug_tuple("HttpError::InvalidHeader").field(e).finish()
+                        }
+                        HttpError::Dns(e) => {
+                            f.debug_tuple("HttpError::Dns").field(e).finish()
+                        }
+                        HttpError::Connect(e) => {
+                            f.debug_tuple("HttpError::Connect").field(e).finish()
+                        }
+                        HttpError::Tls(e) => {
+                            f.debug_tuple("HttpError::Tls").field(e).finish()
+                        }
+                        HttpError::Timeout => {
+                            f.debug_tuple("HttpError::Timeout").finish()
+                        }
+                        HttpError::TooManyRedirects => {
+                            f.debug_tuple("HttpError::TooManyRedirects").finish()
+                        }
+                        HttpError::BodyTooLarge => {
+                            f.debug_tuple("HttpError::BodyTooLarge").finish()
+                        }
+                        HttpError::Blocked(e) => {
+                            f.debug_tuple("HttpError::Blocked").field(e).finish()
+                        }
+                        HttpError::Other(e) => {
+                            f.debug_tuple("HttpError::Other").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for HttpError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for HttpError {}
+            /// Represents a part of a `multipart/form-data` body.
+            #[derive(Clone)]
+            pub struct MultipartPart {
+                /// The name of the form field.
+                pub name: _rt::String,
+                /// The name of the file, if the part is a file.
+                pub filename: Option<_rt::String>,
+                /// The content type of the part, `application/octet-stream` by
+                /// default for files.
+                pub content_type: Option<_rt::String>,
+                /// The content of the part.
+                pub data: _rt::Vec<u8>,
+            }
+            impl ::core::fmt::Debug for MultipartPart {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("MultipartPart")
+                        .field("name", &self.name)
+                        .field("filename", &self.filename)
+                        .field("content-type", &self.content_type)
+                        .field("data", &self.data)
+                        .finish()
+                }
+            }
+            /// Represents the body of a request, encoded by the host.
+            ///
+            /// The host sets the `content-type` header of the request, unless the
+            /// request sets it itself. The header of a multipart body is always set
+            /// by the host, as it gives the boundary between the parts.
+            #[derive(Clone)]
+            pub enum RequestBody {
+                /// Bytes sent as is, without a `content-type` header.
+                Bytes(_rt::Vec<u8>),
+                /// Fields of a form, sent as `application/x-www-form-urlencoded`.
+                Form(_rt::Vec<(_rt::String, _rt::String)>),
+                /// A JSON document, sent as `application/json`.
+                Json(_rt::String),
+                /// Parts of a form, sent as `multipart/form-data`.
+                Multipart(_rt::Vec<MultipartPart>),
+            }
+            impl ::core::fmt::Debug for RequestBody {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        RequestBody::Bytes(e) => {
+                            f.debug_tuple("RequestBody::Bytes").field(e).finish()
+                        }
+                        RequestBody::Form(e) => {
+                            f.debug_tuple("RequestBody::Form").field(e).finish()
+                        }
+                        RequestBody::Json(e) => {
+                            f.debug_tuple("RequestBody::Json").field(e).finish()
+                        }
+                        RequestBody::Multipart(e) => {
+                            f.debug_tuple("RequestBody::Multipart").field(e).finish()
+                        }
                     }
                 }
             }
-            impl Method {
+            /// Represents how the redirects of a request are followed.
+            #[derive(Clone, Copy)]
+            pub enum RedirectPolicy {
+                /// Follow up to 10 redirects.
+                Follow,
+                /// Never follow redirects, the redirect response is returned as is.
+                None,
+                /// Follow up to the given number of redirects.
+                Limited(u32),
+            }
+            impl ::core::fmt::Debug for RedirectPolicy {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        RedirectPolicy::Follow => {
+                            f.debug_tuple("RedirectPolicy::Follow").finish()
+                        }
+                        RedirectPolicy::None => {
+                            f.debug_tuple("RedirectPolicy::None").finish()
+                        }
+                        RedirectPolicy::Limited(e) => {
+                            f.debug_tuple("RedirectPolicy::Limited").field(e).finish()
+                        }
+                    }
+                }
+            }
+            /// Represents a redirect followed by a request.
+            #[derive(Clone)]
+            pub struct Redirect {
+                /// The URL that redirected the request.
+                pub url: _rt::String,
+                /// The status code of the redirect response, e.g. 301.
+                pub status_code: u16,
+            }
+            impl ::core::fmt::Debug for Redirect {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Redirect")
+                        .field("url", &self.url)
+                        .field("status-code", &self.status_code)
+                        .finish()
+                }
+            }
+            /// Represents the body of a response, read as a stream of bytes.
+            ///
+            /// Only the bytes being read are held in memory, which avoids copying
+            /// large bodies, e.g. images, at once.
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct IncomingBody {
+                handle: _rt::Resource<IncomingBody>,
+            }
+            impl IncomingBody {
                 #[doc(hidden)]
-                pub unsafe fn _lift(val: u8) -> Method {
-                    if !cfg!(debug_assertions) {
-                        return ::core::mem::transmute(val);
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
                     }
-                    match val {
-                        0 => Method::Get,
-                        1 => Method::Post,
-                        2 => Method::Put,
-                        3 => Method::Head,
-                        4 => Method::Delete,
-                        _ => panic!("invalid enum discriminant"),
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for IncomingBody {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[resource-drop]incoming-body"]
+                            fn drop(_: u32);
+                        }
+                        unsafe { drop(_handle) };
                     }
                 }
             }
             /// Represents a response from an HTTP request.
+            ///
+            /// Bodies compressed with gzip, brotli or zstd are decompressed by the
+            /// host, and the `content-encoding` and `content-length` headers are
+            /// removed, unless the request sets its own `accept-encoding` header.
             #[derive(Debug)]
             #[repr(transparent)]
             pub struct IncomingResponse {
@@ -61,7 +291,7 @@ pub mod midoku {
                 #[doc(hidden)]
                 pub unsafe fn from_handle(handle: u32) -> Self {
                     Self {
-                        handle: _rt::Resource::from_handle(handle),
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
                     }
                 }
                 #[doc(hidden)]
@@ -80,12 +310,421 @@ pub mod midoku {
                     unreachable!();
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "midoku:http/types@0.1.0")]
-                        extern "C" {
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
                             #[link_name = "[resource-drop]incoming-response"]
                             fn drop(_: u32);
                         }
-                        drop(_handle);
+                        unsafe { drop(_handle) };
+                    }
+                }
+            }
+            impl IncomingBody {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Reads up to `max-len` bytes from the body.
+                ///
+                /// Fewer bytes are returned if fewer are available right away. An
+                /// empty list is returned once the end of the body is reached.
+                ///
+                /// Returns an error if the body could not be read from the server.
+                pub fn read(&self, max_len: u64) -> Result<_rt::Vec<u8>, HttpError> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-body.read"]
+                            fn wit_import1(_: i32, _: i64, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import1(
+                                (self).handle() as i32,
+                                _rt::as_i64(&max_len),
+                                ptr0,
+                            )
+                        };
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result29 = match l2 {
+                            0 => {
+                                let e = {
+                                    let l3 = *ptr0
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l4 = *ptr0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len5 = l4;
+                                    _rt::Vec::from_raw_parts(l3.cast(), len5, len5)
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l6 = i32::from(
+                                        *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    let v28 = match l6 {
+                                        0 => {
+                                            let e28 = {
+                                                let l7 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l8 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len9 = l8;
+                                                let bytes9 = _rt::Vec::from_raw_parts(
+                                                    l7.cast(),
+                                                    len9,
+                                                    len9,
+                                                );
+                                                _rt::string_lift(bytes9)
+                                            };
+                                            HttpError::InvalidUrl(e28)
+                                        }
+                                        1 => {
+                                            let e28 = {
+                                                let l10 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l11 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                _rt::string_lift(bytes12)
+                                            };
+                                            HttpError::InvalidHeader(e28)
+                                        }
+                                        2 => {
+                                            let e28 = {
+                                                let l13 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l14 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                _rt::string_lift(bytes15)
+                                            };
+                                            HttpError::Dns(e28)
+                                        }
+                                        3 => {
+                                            let e28 = {
+                                                let l16 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l17 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len18 = l17;
+                                                let bytes18 = _rt::Vec::from_raw_parts(
+                                                    l16.cast(),
+                                                    len18,
+                                                    len18,
+                                                );
+                                                _rt::string_lift(bytes18)
+                                            };
+                                            HttpError::Connect(e28)
+                                        }
+                                        4 => {
+                                            let e28 = {
+                                                let l19 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l20 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len21 = l20;
+                                                let bytes21 = _rt::Vec::from_raw_parts(
+                                                    l19.cast(),
+                                                    len21,
+                                                    len21,
+                                                );
+                                                _rt::string_lift(bytes21)
+                                            };
+                                            HttpError::Tls(e28)
+                                        }
+                                        5 => HttpError::Timeout,
+                                        6 => HttpError::TooManyRedirects,
+                                        7 => HttpError::BodyTooLarge,
+                                        8 => {
+                                            let e28 = {
+                                                let l22 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l23 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len24 = l23;
+                                                let bytes24 = _rt::Vec::from_raw_parts(
+                                                    l22.cast(),
+                                                    len24,
+                                                    len24,
+                                                );
+                                                _rt::string_lift(bytes24)
+                                            };
+                                            HttpError::Blocked(e28)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 9, "invalid enum discriminant");
+                                            let e28 = {
+                                                let l25 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l26 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len27 = l26;
+                                                let bytes27 = _rt::Vec::from_raw_parts(
+                                                    l25.cast(),
+                                                    len27,
+                                                    len27,
+                                                );
+                                                _rt::string_lift(bytes27)
+                                            };
+                                            HttpError::Other(e28)
+                                        }
+                                    };
+                                    v28
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result29
+                    }
+                }
+            }
+            impl IncomingBody {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Skips up to `len` bytes of the body.
+                ///
+                /// Returns the number of bytes skipped, fewer than `len` if the end
+                /// of the body is reached.
+                pub fn skip(&self, len: u64) -> Result<u64, HttpError> {
+                    unsafe {
+                        #[repr(align(8))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 16 + 2 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 16
+                                + 2 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-body.skip"]
+                            fn wit_import1(_: i32, _: i64, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: i64, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import1((self).handle() as i32, _rt::as_i64(&len), ptr0)
+                        };
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result27 = match l2 {
+                            0 => {
+                                let e = {
+                                    let l3 = *ptr0.add(8).cast::<i64>();
+                                    l3 as u64
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l4 = i32::from(*ptr0.add(8).cast::<u8>());
+                                    let v26 = match l4 {
+                                        0 => {
+                                            let e26 = {
+                                                let l5 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l6 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len7 = l6;
+                                                let bytes7 = _rt::Vec::from_raw_parts(
+                                                    l5.cast(),
+                                                    len7,
+                                                    len7,
+                                                );
+                                                _rt::string_lift(bytes7)
+                                            };
+                                            HttpError::InvalidUrl(e26)
+                                        }
+                                        1 => {
+                                            let e26 = {
+                                                let l8 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l9 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len10 = l9;
+                                                let bytes10 = _rt::Vec::from_raw_parts(
+                                                    l8.cast(),
+                                                    len10,
+                                                    len10,
+                                                );
+                                                _rt::string_lift(bytes10)
+                                            };
+                                            HttpError::InvalidHeader(e26)
+                                        }
+                                        2 => {
+                                            let e26 = {
+                                                let l11 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l12 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len13 = l12;
+                                                let bytes13 = _rt::Vec::from_raw_parts(
+                                                    l11.cast(),
+                                                    len13,
+                                                    len13,
+                                                );
+                                                _rt::string_lift(bytes13)
+                                            };
+                                            HttpError::Dns(e26)
+                                        }
+                                        3 => {
+                                            let e26 = {
+                                                let l14 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l15 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len16 = l15;
+                                                let bytes16 = _rt::Vec::from_raw_parts(
+                                                    l14.cast(),
+                                                    len16,
+                                                    len16,
+                                                );
+                                                _rt::string_lift(bytes16)
+                                            };
+                                            HttpError::Connect(e26)
+                                        }
+                                        4 => {
+                                            let e26 = {
+                                                let l17 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l18 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len19 = l18;
+                                                let bytes19 = _rt::Vec::from_raw_parts(
+                                                    l17.cast(),
+                                                    len19,
+                                                    len19,
+                                                );
+                                                _rt::string_lift(bytes19)
+                                            };
+                                            HttpError::Tls(e26)
+                                        }
+                                        5 => HttpError::Timeout,
+                                        6 => HttpError::TooManyRedirects,
+                                        7 => HttpError::BodyTooLarge,
+                                        8 => {
+                                            let e26 = {
+                                                let l20 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l21 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len22 = l21;
+                                                let bytes22 = _rt::Vec::from_raw_parts(
+                                                    l20.cast(),
+                                                    len22,
+                                                    len22,
+                                                );
+                                                _rt::string_lift(bytes22)
+                                            };
+                                            HttpError::Blocked(e26)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 9, "invalid enum discriminant");
+                                            let e26 = {
+                                                let l23 = *ptr0
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l24 = *ptr0
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len25 = l24;
+                                                let bytes25 = _rt::Vec::from_raw_parts(
+                                                    l23.cast(),
+                                                    len25,
+                                                    len25,
+                                                );
+                                                _rt::string_lift(bytes25)
+                                            };
+                                            HttpError::Other(e26)
+                                        }
+                                    };
+                                    v26
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result27
+                    }
+                }
+            }
+            impl IncomingBody {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Returns whether every byte of the body was read.
+                ///
+                /// The end of the body is only known once a read reaches it.
+                pub fn end_of_stream(&self) -> bool {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-body.end-of-stream"]
+                            fn wit_import0(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import0(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe { wit_import0((self).handle() as i32) };
+                        _rt::bool_lift(ret as u8)
                     }
                 }
             }
@@ -95,79 +734,720 @@ pub mod midoku {
                 pub fn status_code(&self) -> u16 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "midoku:http/types@0.1.0")]
-                        extern "C" {
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
                             #[link_name = "[method]incoming-response.status-code"]
-                            fn wit_import(_: i32) -> i32;
+                            fn wit_import0(_: i32) -> i32;
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32) -> i32 {
+                        unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
-                        let ret = wit_import((self).handle() as i32);
+                        let ret = unsafe { wit_import0((self).handle() as i32) };
                         ret as u16
                     }
                 }
             }
+            impl IncomingResponse {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Returns the URL the response was received from, after following
+                /// redirects.
+                pub fn url(&self) -> _rt::String {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-response.url"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len4 = l3;
+                        let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                        let result5 = _rt::string_lift(bytes4);
+                        result5
+                    }
+                }
+            }
+            impl IncomingResponse {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Returns the redirects followed by the request, in order, or an
+                /// empty list if it was not redirected.
+                pub fn redirects(&self) -> _rt::Vec<Redirect> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-response.redirects"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base8 = l2;
+                        let len8 = l3;
+                        let mut result8 = _rt::Vec::with_capacity(len8);
+                        for i in 0..len8 {
+                            let base = base8
+                                .add(i * (3 * ::core::mem::size_of::<*const u8>()));
+                            let e8 = {
+                                let l4 = *base.add(0).cast::<*mut u8>();
+                                let l5 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                let l7 = i32::from(
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u16>(),
+                                );
+                                Redirect {
+                                    url: _rt::string_lift(bytes6),
+                                    status_code: l7 as u16,
+                                }
+                            };
+                            result8.push(e8);
+                        }
+                        _rt::cabi_dealloc(
+                            base8,
+                            len8 * (3 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result9 = result8;
+                        result9
+                    }
+                }
+            }
             impl IncomingResponse {
                 #[allow(unused_unsafe, clippy::all)]
                 /// Returns the headers of the response.
+                ///
+                /// A header received several times is listed once per value, in the
+                /// order they were received. Values that are not valid UTF-8 are
+                /// decoded as Latin-1.
                 pub fn headers(&self) -> _rt::Vec<(_rt::String, _rt::String)> {
                     unsafe {
-                        #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
                         let mut ret_area = RetArea(
-                            [::core::mem::MaybeUninit::uninit(); 8],
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "midoku:http/types@0.1.0")]
-                        extern "C" {
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
                             #[link_name = "[method]incoming-response.headers"]
-                            fn wit_import(_: i32, _: *mut u8);
+                            fn wit_import1(_: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: *mut u8) {
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, ptr0);
-                        let l1 = *ptr0.add(0).cast::<*mut u8>();
-                        let l2 = *ptr0.add(4).cast::<usize>();
-                        let base9 = l1;
-                        let len9 = l2;
-                        let mut result9 = _rt::Vec::with_capacity(len9);
-                        for i in 0..len9 {
-                            let base = base9.add(i * 16);
-                            let e9 = {
-                                let l3 = *base.add(0).cast::<*mut u8>();
-                                let l4 = *base.add(4).cast::<usize>();
-                                let len5 = l4;
-                                let bytes5 = _rt::Vec::from_raw_parts(
-                                    l3.cast(),
-                                    len5,
-                                    len5,
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base10 = l2;
+                        let len10 = l3;
+                        let mut result10 = _rt::Vec::with_capacity(len10);
+                        for i in 0..len10 {
+                            let base = base10
+                                .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                            let e10 = {
+                                let l4 = *base.add(0).cast::<*mut u8>();
+                                let l5 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
                                 );
-                                let l6 = *base.add(8).cast::<*mut u8>();
-                                let l7 = *base.add(12).cast::<usize>();
-                                let len8 = l7;
-                                let bytes8 = _rt::Vec::from_raw_parts(
-                                    l6.cast(),
-                                    len8,
-                                    len8,
+                                let l7 = *base
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l8 = *base
+                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len9 = l8;
+                                let bytes9 = _rt::Vec::from_raw_parts(
+                                    l7.cast(),
+                                    len9,
+                                    len9,
                                 );
-                                (_rt::string_lift(bytes5), _rt::string_lift(bytes8))
+                                (_rt::string_lift(bytes6), _rt::string_lift(bytes9))
                             };
-                            result9.push(e9);
+                            result10.push(e10);
                         }
-                        _rt::cabi_dealloc(base9, len9 * 16, 4);
-                        result9
+                        _rt::cabi_dealloc(
+                            base10,
+                            len10 * (4 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result11 = result10;
+                        result11
+                    }
+                }
+            }
+            impl IncomingResponse {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Returns the headers of the response with their values as received.
+                pub fn raw_headers(&self) -> _rt::Vec<(_rt::String, _rt::Vec<u8>)> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-response.raw-headers"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base10 = l2;
+                        let len10 = l3;
+                        let mut result10 = _rt::Vec::with_capacity(len10);
+                        for i in 0..len10 {
+                            let base = base10
+                                .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                            let e10 = {
+                                let l4 = *base.add(0).cast::<*mut u8>();
+                                let l5 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                let l7 = *base
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l8 = *base
+                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len9 = l8;
+                                (
+                                    _rt::string_lift(bytes6),
+                                    _rt::Vec::from_raw_parts(l7.cast(), len9, len9),
+                                )
+                            };
+                            result10.push(e10);
+                        }
+                        _rt::cabi_dealloc(
+                            base10,
+                            len10 * (4 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result11 = result10;
+                        result11
                     }
                 }
             }
             impl IncomingResponse {
                 #[allow(unused_unsafe, clippy::all)]
                 /// Returns the bytes of the response.
-                pub fn bytes(&self) -> _rt::Vec<u8> {
+                ///
+                /// The whole body is read into memory. Returns the bytes not read
+                /// yet, or an empty list if the body was taken with `body`.
+                ///
+                /// Returns an error if the body could not be read.
+                pub fn bytes(&self) -> Result<_rt::Vec<u8>, HttpError> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-response.bytes"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result29 = match l2 {
+                            0 => {
+                                let e = {
+                                    let l3 = *ptr0
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l4 = *ptr0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len5 = l4;
+                                    _rt::Vec::from_raw_parts(l3.cast(), len5, len5)
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l6 = i32::from(
+                                        *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    let v28 = match l6 {
+                                        0 => {
+                                            let e28 = {
+                                                let l7 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l8 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len9 = l8;
+                                                let bytes9 = _rt::Vec::from_raw_parts(
+                                                    l7.cast(),
+                                                    len9,
+                                                    len9,
+                                                );
+                                                _rt::string_lift(bytes9)
+                                            };
+                                            HttpError::InvalidUrl(e28)
+                                        }
+                                        1 => {
+                                            let e28 = {
+                                                let l10 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l11 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                _rt::string_lift(bytes12)
+                                            };
+                                            HttpError::InvalidHeader(e28)
+                                        }
+                                        2 => {
+                                            let e28 = {
+                                                let l13 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l14 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                _rt::string_lift(bytes15)
+                                            };
+                                            HttpError::Dns(e28)
+                                        }
+                                        3 => {
+                                            let e28 = {
+                                                let l16 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l17 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len18 = l17;
+                                                let bytes18 = _rt::Vec::from_raw_parts(
+                                                    l16.cast(),
+                                                    len18,
+                                                    len18,
+                                                );
+                                                _rt::string_lift(bytes18)
+                                            };
+                                            HttpError::Connect(e28)
+                                        }
+                                        4 => {
+                                            let e28 = {
+                                                let l19 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l20 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len21 = l20;
+                                                let bytes21 = _rt::Vec::from_raw_parts(
+                                                    l19.cast(),
+                                                    len21,
+                                                    len21,
+                                                );
+                                                _rt::string_lift(bytes21)
+                                            };
+                                            HttpError::Tls(e28)
+                                        }
+                                        5 => HttpError::Timeout,
+                                        6 => HttpError::TooManyRedirects,
+                                        7 => HttpError::BodyTooLarge,
+                                        8 => {
+                                            let e28 = {
+                                                let l22 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l23 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len24 = l23;
+                                                let bytes24 = _rt::Vec::from_raw_parts(
+                                                    l22.cast(),
+                                                    len24,
+                                                    len24,
+                                                );
+                                                _rt::string_lift(bytes24)
+                                            };
+                                            HttpError::Blocked(e28)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 9, "invalid enum discriminant");
+                                            let e28 = {
+                                                let l25 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l26 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len27 = l26;
+                                                let bytes27 = _rt::Vec::from_raw_parts(
+                                                    l25.cast(),
+                                                    len27,
+                                                    len27,
+                                                );
+                                                _rt::string_lift(bytes27)
+                                            };
+                                            HttpError::Other(e28)
+                                        }
+                                    };
+                                    v28
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result29
+                    }
+                }
+            }
+            impl IncomingResponse {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Returns the body of the response decoded as text.
+                ///
+                /// The body is decoded with `encoding`, e.g. `shift_jis`, if given,
+                /// and with the charset of the `content-type` header otherwise,
+                /// defaulting to UTF-8. Invalid sequences are replaced with U+FFFD.
+                /// Like `bytes`, the whole body is read into memory.
+                ///
+                /// Returns an error if the body could not be read or the encoding is
+                /// not supported.
+                pub fn text(
+                    &self,
+                    encoding: Option<&str>,
+                ) -> Result<_rt::String, HttpError> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let (result1_0, result1_1, result1_2) = match encoding {
+                            Some(e) => {
+                                let vec0 = e;
+                                let ptr0 = vec0.as_ptr().cast::<u8>();
+                                let len0 = vec0.len();
+                                (1i32, ptr0.cast_mut(), len0)
+                            }
+                            None => (0i32, ::core::ptr::null_mut(), 0usize),
+                        };
+                        let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-response.text"]
+                            fn wit_import3(
+                                _: i32,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import3(
+                            _: i32,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import3(
+                                (self).handle() as i32,
+                                result1_0,
+                                result1_1,
+                                result1_2,
+                                ptr2,
+                            )
+                        };
+                        let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                        let result31 = match l4 {
+                            0 => {
+                                let e = {
+                                    let l5 = *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len7 = l6;
+                                    let bytes7 = _rt::Vec::from_raw_parts(
+                                        l5.cast(),
+                                        len7,
+                                        len7,
+                                    );
+                                    _rt::string_lift(bytes7)
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l8 = i32::from(
+                                        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    let v30 = match l8 {
+                                        0 => {
+                                            let e30 = {
+                                                let l9 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l10 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len11 = l10;
+                                                let bytes11 = _rt::Vec::from_raw_parts(
+                                                    l9.cast(),
+                                                    len11,
+                                                    len11,
+                                                );
+                                                _rt::string_lift(bytes11)
+                                            };
+                                            HttpError::InvalidUrl(e30)
+                                        }
+                                        1 => {
+                                            let e30 = {
+                                                let l12 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l13 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len14 = l13;
+                                                let bytes14 = _rt::Vec::from_raw_parts(
+                                                    l12.cast(),
+                                                    len14,
+                                                    len14,
+                                                );
+                                                _rt::string_lift(bytes14)
+                                            };
+                                            HttpError::InvalidHeader(e30)
+                                        }
+                                        2 => {
+                                            let e30 = {
+                                                let l15 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l16 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len17 = l16;
+                                                let bytes17 = _rt::Vec::from_raw_parts(
+                                                    l15.cast(),
+                                                    len17,
+                                                    len17,
+                                                );
+                                                _rt::string_lift(bytes17)
+                                            };
+                                            HttpError::Dns(e30)
+                                        }
+                                        3 => {
+                                            let e30 = {
+                                                let l18 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l19 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len20 = l19;
+                                                let bytes20 = _rt::Vec::from_raw_parts(
+                                                    l18.cast(),
+                                                    len20,
+                                                    len20,
+                                                );
+                                                _rt::string_lift(bytes20)
+                                            };
+                                            HttpError::Connect(e30)
+                                        }
+                                        4 => {
+                                            let e30 = {
+                                                let l21 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l22 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len23 = l22;
+                                                let bytes23 = _rt::Vec::from_raw_parts(
+                                                    l21.cast(),
+                                                    len23,
+                                                    len23,
+                                                );
+                                                _rt::string_lift(bytes23)
+                                            };
+                                            HttpError::Tls(e30)
+                                        }
+                                        5 => HttpError::Timeout,
+                                        6 => HttpError::TooManyRedirects,
+                                        7 => HttpError::BodyTooLarge,
+                                        8 => {
+                                            let e30 = {
+                                                let l24 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l25 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len26 = l25;
+                                                let bytes26 = _rt::Vec::from_raw_parts(
+                                                    l24.cast(),
+                                                    len26,
+                                                    len26,
+                                                );
+                                                _rt::string_lift(bytes26)
+                                            };
+                                            HttpError::Blocked(e30)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 9, "invalid enum discriminant");
+                                            let e30 = {
+                                                let l27 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l28 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len29 = l28;
+                                                let bytes29 = _rt::Vec::from_raw_parts(
+                                                    l27.cast(),
+                                                    len29,
+                                                    len29,
+                                                );
+                                                _rt::string_lift(bytes29)
+                                            };
+                                            HttpError::Other(e30)
+                                        }
+                                    };
+                                    v30
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result31
+                    }
+                }
+            }
+            impl IncomingResponse {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Takes the body of the response to stream it.
+                ///
+                /// Returns an error if the body was already taken.
+                pub fn body(&self) -> Result<IncomingBody, ()> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -176,171 +1456,652 @@ pub mod midoku {
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "midoku:http/types@0.1.0")]
-                        extern "C" {
-                            #[link_name = "[method]incoming-response.bytes"]
-                            fn wit_import(_: i32, _: *mut u8);
+                        #[link(wasm_import_module = "midoku:http/types@0.2.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]incoming-response.body"]
+                            fn wit_import1(_: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: *mut u8) {
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, ptr0);
-                        let l1 = *ptr0.add(0).cast::<*mut u8>();
-                        let l2 = *ptr0.add(4).cast::<usize>();
-                        let len3 = l2;
-                        _rt::Vec::from_raw_parts(l1.cast(), len3, len3)
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result4 = match l2 {
+                            0 => {
+                                let e = {
+                                    let l3 = *ptr0.add(4).cast::<i32>();
+                                    unsafe { IncomingBody::from_handle(l3 as u32) }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = ();
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result4
                     }
                 }
             }
         }
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod outgoing_handler {
             #[used]
             #[doc(hidden)]
             static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
             use super::super::super::_rt;
             pub type Method = super::super::super::midoku::http::types::Method;
+            pub type RequestBody = super::super::super::midoku::http::types::RequestBody;
+            pub type RedirectPolicy = super::super::super::midoku::http::types::RedirectPolicy;
             pub type IncomingResponse = super::super::super::midoku::http::types::IncomingResponse;
+            pub type HttpError = super::super::super::midoku::http::types::HttpError;
             #[allow(unused_unsafe, clippy::all)]
             /// Sends an HTTP request.
             ///
             /// The `url` must be a valid URL.
             ///
             /// The `headers` must be a list of tuples where the first element is the
-            /// header name and the second element is the header value.
+            /// header name and the second element is the header value. A header can
+            /// be listed several times to send several values. The `raw-headers` are
+            /// sent after them with their values as given, e.g. Latin-1 values that
+            /// are not valid UTF-8.
+            ///
+            /// The `body` is encoded by the host, see `request-body`.
             ///
-            /// The `body` must be a list of bytes.
+            /// Redirects are followed by the host as requested by `redirect`,
+            /// defaulting to `follow`. A request redirected more times than allowed
+            /// fails with `too-many-redirects`. The credentials of the request, e.g.
+            /// the `authorization` header, are not sent to another origin.
             ///
-            /// Returns the response from the server.
+            /// Returns the response from the server, or the error the request failed
+            /// with.
             pub fn handle(
-                method: Method,
+                method: &Method,
                 url: &str,
                 headers: Option<&[(_rt::String, _rt::String)]>,
-                body: Option<&[u8]>,
-            ) -> Result<IncomingResponse, ()> {
+                raw_headers: Option<&[(_rt::String, _rt::Vec<u8>)]>,
+                body: Option<&RequestBody>,
+                redirect: Option<RedirectPolicy>,
+            ) -> Result<IncomingResponse, HttpError> {
                 unsafe {
                     let mut cleanup_list = _rt::Vec::new();
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
-                    let vec0 = url;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let (result5_0, result5_1, result5_2) = match headers {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 8 + 16 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 8
+                            + 16 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    use super::super::super::midoku::http::types::Method as V2;
+                    match method {
+                        V2::Get => {
+                            *ptr0.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        V2::Post => {
+                            *ptr0.add(0).cast::<u8>() = (1i32) as u8;
+                        }
+                        V2::Put => {
+                            *ptr0.add(0).cast::<u8>() = (2i32) as u8;
+                        }
+                        V2::Head => {
+                            *ptr0.add(0).cast::<u8>() = (3i32) as u8;
+                        }
+                        V2::Delete => {
+                            *ptr0.add(0).cast::<u8>() = (4i32) as u8;
+                        }
+                        V2::Patch => {
+                            *ptr0.add(0).cast::<u8>() = (5i32) as u8;
+                        }
+                        V2::Options => {
+                            *ptr0.add(0).cast::<u8>() = (6i32) as u8;
+                        }
+                        V2::Connect => {
+                            *ptr0.add(0).cast::<u8>() = (7i32) as u8;
+                        }
+                        V2::Trace => {
+                            *ptr0.add(0).cast::<u8>() = (8i32) as u8;
+                        }
+                        V2::Other(e) => {
+                            *ptr0.add(0).cast::<u8>() = (9i32) as u8;
+                            let vec1 = e;
+                            let ptr1 = vec1.as_ptr().cast::<u8>();
+                            let len1 = vec1.len();
+                            *ptr0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len1;
+                            *ptr0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr1.cast_mut();
+                        }
+                    }
+                    let vec3 = url;
+                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                    let len3 = vec3.len();
+                    *ptr0.add(4 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+                    *ptr0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr3.cast_mut();
+                    match headers {
                         Some(e) => {
-                            let vec4 = e;
-                            let len4 = vec4.len();
-                            let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec4.len() * 16,
-                                4,
+                            *ptr0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            let vec7 = e;
+                            let len7 = vec7.len();
+                            let layout7 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec7.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
                             );
-                            let result4 = if layout4.size() != 0 {
-                                let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                            let result7 = if layout7.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
                                 if ptr.is_null() {
-                                    _rt::alloc::handle_alloc_error(layout4);
+                                    _rt::alloc::handle_alloc_error(layout7);
                                 }
                                 ptr
                             } else {
                                 ::core::ptr::null_mut()
                             };
-                            for (i, e) in vec4.into_iter().enumerate() {
-                                let base = result4.add(i * 16);
+                            for (i, e) in vec7.into_iter().enumerate() {
+                                let base = result7
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
                                 {
-                                    let (t1_0, t1_1) = e;
-                                    let vec2 = t1_0;
-                                    let ptr2 = vec2.as_ptr().cast::<u8>();
-                                    let len2 = vec2.len();
-                                    *base.add(4).cast::<usize>() = len2;
-                                    *base.add(0).cast::<*mut u8>() = ptr2.cast_mut();
-                                    let vec3 = t1_1;
-                                    let ptr3 = vec3.as_ptr().cast::<u8>();
-                                    let len3 = vec3.len();
-                                    *base.add(12).cast::<usize>() = len3;
-                                    *base.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                                    let (t4_0, t4_1) = e;
+                                    let vec5 = t4_0;
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *base.add(0).cast::<*mut u8>() = ptr5.cast_mut();
+                                    let vec6 = t4_1;
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
                                 }
                             }
-                            cleanup_list.extend_from_slice(&[(result4, layout4)]);
-                            (1i32, result4, len4)
+                            *ptr0
+                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len7;
+                            *ptr0
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result7;
+                            cleanup_list.extend_from_slice(&[(result7, layout7)]);
+                        }
+                        None => {
+                            *ptr0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
                         }
-                        None => (0i32, ::core::ptr::null_mut(), 0usize),
                     };
-                    let (result7_0, result7_1, result7_2) = match body {
+                    match raw_headers {
                         Some(e) => {
-                            let vec6 = e;
-                            let ptr6 = vec6.as_ptr().cast::<u8>();
-                            let len6 = vec6.len();
-                            (1i32, ptr6.cast_mut(), len6)
+                            *ptr0
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            let vec11 = e;
+                            let len11 = vec11.len();
+                            let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec11.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result11 = if layout11.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout11);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec11.into_iter().enumerate() {
+                                let base = result11
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let (t8_0, t8_1) = e;
+                                    let vec9 = t8_0;
+                                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                                    let len9 = vec9.len();
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len9;
+                                    *base.add(0).cast::<*mut u8>() = ptr9.cast_mut();
+                                    let vec10 = t8_1;
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                            }
+                            *ptr0
+                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len11;
+                            *ptr0
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result11;
+                            cleanup_list.extend_from_slice(&[(result11, layout11)]);
+                        }
+                        None => {
+                            *ptr0
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    match body {
+                        Some(e) => {
+                            *ptr0
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            use super::super::super::midoku::http::types::RequestBody as V24;
+                            match e {
+                                V24::Bytes(e) => {
+                                    *ptr0
+                                        .add(12 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                    let vec12 = e;
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    *ptr0
+                                        .add(14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr0
+                                        .add(13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                V24::Form(e) => {
+                                    *ptr0
+                                        .add(12 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec16 = e;
+                                    let len16 = vec16.len();
+                                    let layout16 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec16.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                    let result16 = if layout16.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout16).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout16);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec16.into_iter().enumerate() {
+                                        let base = result16
+                                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let (t13_0, t13_1) = e;
+                                            let vec14 = t13_0;
+                                            let ptr14 = vec14.as_ptr().cast::<u8>();
+                                            let len14 = vec14.len();
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len14;
+                                            *base.add(0).cast::<*mut u8>() = ptr14.cast_mut();
+                                            let vec15 = t13_1;
+                                            let ptr15 = vec15.as_ptr().cast::<u8>();
+                                            let len15 = vec15.len();
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len15;
+                                            *base
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr15.cast_mut();
+                                        }
+                                    }
+                                    *ptr0
+                                        .add(14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len16;
+                                    *ptr0
+                                        .add(13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result16;
+                                    cleanup_list.extend_from_slice(&[(result16, layout16)]);
+                                }
+                                V24::Json(e) => {
+                                    *ptr0
+                                        .add(12 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    let vec17 = e;
+                                    let ptr17 = vec17.as_ptr().cast::<u8>();
+                                    let len17 = vec17.len();
+                                    *ptr0
+                                        .add(14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len17;
+                                    *ptr0
+                                        .add(13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr17.cast_mut();
+                                }
+                                V24::Multipart(e) => {
+                                    *ptr0
+                                        .add(12 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                    let vec23 = e;
+                                    let len23 = vec23.len();
+                                    let layout23 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec23.len() * (10 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                    let result23 = if layout23.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout23).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout23);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec23.into_iter().enumerate() {
+                                        let base = result23
+                                            .add(i * (10 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let super::super::super::midoku::http::types::MultipartPart {
+                                                name: name18,
+                                                filename: filename18,
+                                                content_type: content_type18,
+                                                data: data18,
+                                            } = e;
+                                            let vec19 = name18;
+                                            let ptr19 = vec19.as_ptr().cast::<u8>();
+                                            let len19 = vec19.len();
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len19;
+                                            *base.add(0).cast::<*mut u8>() = ptr19.cast_mut();
+                                            match filename18 {
+                                                Some(e) => {
+                                                    *base
+                                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<u8>() = (1i32) as u8;
+                                                    let vec20 = e;
+                                                    let ptr20 = vec20.as_ptr().cast::<u8>();
+                                                    let len20 = vec20.len();
+                                                    *base
+                                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>() = len20;
+                                                    *base
+                                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<*mut u8>() = ptr20.cast_mut();
+                                                }
+                                                None => {
+                                                    *base
+                                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<u8>() = (0i32) as u8;
+                                                }
+                                            };
+                                            match content_type18 {
+                                                Some(e) => {
+                                                    *base
+                                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<u8>() = (1i32) as u8;
+                                                    let vec21 = e;
+                                                    let ptr21 = vec21.as_ptr().cast::<u8>();
+                                                    let len21 = vec21.len();
+                                                    *base
+                                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>() = len21;
+                                                    *base
+                                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<*mut u8>() = ptr21.cast_mut();
+                                                }
+                                                None => {
+                                                    *base
+                                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<u8>() = (0i32) as u8;
+                                                }
+                                            };
+                                            let vec22 = data18;
+                                            let ptr22 = vec22.as_ptr().cast::<u8>();
+                                            let len22 = vec22.len();
+                                            *base
+                                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len22;
+                                            *base
+                                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr22.cast_mut();
+                                        }
+                                    }
+                                    *ptr0
+                                        .add(14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len23;
+                                    *ptr0
+                                        .add(13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result23;
+                                    cleanup_list.extend_from_slice(&[(result23, layout23)]);
+                                }
+                            }
+                        }
+                        None => {
+                            *ptr0
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    match redirect {
+                        Some(e) => {
+                            *ptr0
+                                .add(15 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            use super::super::super::midoku::http::types::RedirectPolicy as V25;
+                            match e {
+                                V25::Follow => {
+                                    *ptr0
+                                        .add(4 + 15 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                V25::None => {
+                                    *ptr0
+                                        .add(4 + 15 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                V25::Limited(e) => {
+                                    *ptr0
+                                        .add(4 + 15 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    *ptr0
+                                        .add(8 + 15 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i32>() = _rt::as_i32(e);
+                                }
+                            }
+                        }
+                        None => {
+                            *ptr0
+                                .add(15 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
                         }
-                        None => (0i32, ::core::ptr::null_mut(), 0usize),
                     };
-                    let ptr8 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    let ptr26 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:http/outgoing-handler@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:http/outgoing-handler@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "handle"]
-                        fn wit_import(
-                            _: i32,
-                            _: *mut u8,
-                            _: usize,
-                            _: i32,
-                            _: *mut u8,
-                            _: usize,
-                            _: i32,
-                            _: *mut u8,
-                            _: usize,
-                            _: *mut u8,
-                        );
+                        fn wit_import27(_: *mut u8, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(
-                        _: i32,
-                        _: *mut u8,
-                        _: usize,
-                        _: i32,
-                        _: *mut u8,
-                        _: usize,
-                        _: i32,
-                        _: *mut u8,
-                        _: usize,
-                        _: *mut u8,
-                    ) {
+                    unsafe extern "C" fn wit_import27(_: *mut u8, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(
-                        method.clone() as i32,
-                        ptr0.cast_mut(),
-                        len0,
-                        result5_0,
-                        result5_1,
-                        result5_2,
-                        result7_0,
-                        result7_1,
-                        result7_2,
-                        ptr8,
-                    );
-                    let l9 = i32::from(*ptr8.add(0).cast::<u8>());
-                    for (ptr, layout) in cleanup_list {
-                        if layout.size() != 0 {
-                            _rt::alloc::dealloc(ptr.cast(), layout);
-                        }
-                    }
-                    match l9 {
+                    unsafe { wit_import27(ptr0, ptr26) };
+                    let l28 = i32::from(*ptr26.add(0).cast::<u8>());
+                    let result53 = match l28 {
                         0 => {
                             let e = {
-                                let l10 = *ptr8.add(4).cast::<i32>();
-                                super::super::super::midoku::http::types::IncomingResponse::from_handle(
-                                    l10 as u32,
-                                )
+                                let l29 = *ptr26
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<i32>();
+                                unsafe {
+                                    super::super::super::midoku::http::types::IncomingResponse::from_handle(
+                                        l29 as u32,
+                                    )
+                                }
                             };
                             Ok(e)
                         }
                         1 => {
-                            let e = ();
+                            let e = {
+                                let l30 = i32::from(
+                                    *ptr26.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                use super::super::super::midoku::http::types::HttpError as V52;
+                                let v52 = match l30 {
+                                    0 => {
+                                        let e52 = {
+                                            let l31 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l32 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len33 = l32;
+                                            let bytes33 = _rt::Vec::from_raw_parts(
+                                                l31.cast(),
+                                                len33,
+                                                len33,
+                                            );
+                                            _rt::string_lift(bytes33)
+                                        };
+                                        V52::InvalidUrl(e52)
+                                    }
+                                    1 => {
+                                        let e52 = {
+                                            let l34 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l35 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len36 = l35;
+                                            let bytes36 = _rt::Vec::from_raw_parts(
+                                                l34.cast(),
+                                                len36,
+                                                len36,
+                                            );
+                                            _rt::string_lift(bytes36)
+                                        };
+                                        V52::InvalidHeader(e52)
+                                    }
+                                    2 => {
+                                        let e52 = {
+                                            let l37 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l38 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len39 = l38;
+                                            let bytes39 = _rt::Vec::from_raw_parts(
+                                                l37.cast(),
+                                                len39,
+                                                len39,
+                                            );
+                                            _rt::string_lift(bytes39)
+                                        };
+                                        V52::Dns(e52)
+                                    }
+                                    3 => {
+                                        let e52 = {
+                                            let l40 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l41 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len42 = l41;
+                                            let bytes42 = _rt::Vec::from_raw_parts(
+                                                l40.cast(),
+                                                len42,
+                                                len42,
+                                            );
+                                            _rt::string_lift(bytes42)
+                                        };
+                                        V52::Connect(e52)
+                                    }
+                                    4 => {
+                                        let e52 = {
+                                            let l43 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l44 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len45 = l44;
+                                            let bytes45 = _rt::Vec::from_raw_parts(
+                                                l43.cast(),
+                                                len45,
+                                                len45,
+                                            );
+                                            _rt::string_lift(bytes45)
+                                        };
+                                        V52::Tls(e52)
+                                    }
+                                    5 => V52::Timeout,
+                                    6 => V52::TooManyRedirects,
+                                    7 => V52::BodyTooLarge,
+                                    8 => {
+                                        let e52 = {
+                                            let l46 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l47 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len48 = l47;
+                                            let bytes48 = _rt::Vec::from_raw_parts(
+                                                l46.cast(),
+                                                len48,
+                                                len48,
+                                            );
+                                            _rt::string_lift(bytes48)
+                                        };
+                                        V52::Blocked(e52)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 9, "invalid enum discriminant");
+                                        let e52 = {
+                                            let l49 = *ptr26
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l50 = *ptr26
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len51 = l50;
+                                            let bytes51 = _rt::Vec::from_raw_parts(
+                                                l49.cast(),
+                                                len51,
+                                                len51,
+                                            );
+                                            _rt::string_lift(bytes51)
+                                        };
+                                        V52::Other(e52)
+                                    }
+                                };
+                                v52
+                            };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
+                    };
+                    for (ptr, layout) in cleanup_list {
+                        if layout.size() != 0 {
+                            _rt::alloc::dealloc(ptr.cast(), layout);
+                        }
                     }
+                    result53
                 }
             }
         }
@@ -348,7 +2109,11 @@ pub mod midoku {
     pub mod limiter {
         /// The `rate-limiter` interface provides a rate limiting mechanism that
         /// enforces a maximum number of calls that can be made in a single period.
-        #[allow(dead_code, clippy::all)]
+        ///
+        /// Besides the default limiter, a source can set limiters keyed by host, e.g.
+        /// `cdn.example.com`, or by any name it chooses, each with its own burst size
+        /// and period.
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod rate_limiter {
             #[used]
             #[doc(hidden)]
@@ -366,28 +2131,29 @@ pub mod midoku {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "burst"]
-                        fn wit_import(_: *mut u8);
+                        fn wit_import1(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0);
-                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
-                    match l1 {
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result4 = match l2 {
                         0 => None,
                         1 => {
                             let e = {
-                                let l2 = *ptr0.add(4).cast::<i32>();
-                                l2 as u32
+                                let l3 = *ptr0.add(4).cast::<i32>();
+                                l3 as u32
                             };
                             Some(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result4
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
@@ -402,28 +2168,29 @@ pub mod midoku {
                     let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "period-ms"]
-                        fn wit_import(_: *mut u8);
+                        fn wit_import1(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0);
-                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
-                    match l1 {
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result4 = match l2 {
                         0 => None,
                         1 => {
                             let e = {
-                                let l2 = *ptr0.add(4).cast::<i32>();
-                                l2 as u32
+                                let l3 = *ptr0.add(4).cast::<i32>();
+                                l3 as u32
                             };
                             Some(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result4
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
@@ -434,16 +2201,16 @@ pub mod midoku {
             pub fn set_burst(burst: u32) -> Result<(), ()> {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "set-burst"]
-                        fn wit_import(_: i32) -> i32;
+                        fn wit_import0(_: i32) -> i32;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i32) -> i32 {
+                    unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                         unreachable!()
                     }
-                    let ret = wit_import(_rt::as_i32(&burst));
+                    let ret = unsafe { wit_import0(_rt::as_i32(&burst)) };
                     match ret {
                         0 => {
                             let e = ();
@@ -465,16 +2232,16 @@ pub mod midoku {
             pub fn set_period_ms(period_ms: u32) -> Result<(), ()> {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "set-period-ms"]
-                        fn wit_import(_: i32) -> i32;
+                        fn wit_import0(_: i32) -> i32;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i32) -> i32 {
+                    unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                         unreachable!()
                     }
-                    let ret = wit_import(_rt::as_i32(&period_ms));
+                    let ret = unsafe { wit_import0(_rt::as_i32(&period_ms)) };
                     match ret {
                         0 => {
                             let e = ();
@@ -500,41 +2267,250 @@ pub mod midoku {
             pub fn ready() -> bool {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "ready"]
-                        fn wit_import() -> i32;
+                        fn wit_import0() -> i32;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import() -> i32 {
+                    unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
-                    let ret = wit_import();
+                    let ret = unsafe { wit_import0() };
                     _rt::bool_lift(ret as u8)
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             /// `block` returns immediately if the rate limit is not exceeded, otherwise
             /// it blocks until the limiter allows the call to proceed.
-            pub fn block() {
+            pub fn block() -> () {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.1.0")]
-                    extern "C" {
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
                         #[link_name = "block"]
-                        fn wit_import();
+                        fn wit_import0();
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0() {
+                        unreachable!()
+                    }
+                    unsafe { wit_import0() };
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Sets the limiter with the given key, replacing the previous one. The
+            /// limiter allows `burst` calls every `period-ms` milliseconds.
+            ///
+            /// The key is either a host, e.g. `cdn.example.com`, which `auto-block`
+            /// matches against the host of each request, or any name chosen by the
+            /// source.
+            ///
+            /// Returns `Ok` if the limiter was set successfully, `Err` if the burst
+            /// size or the period is zero.
+            pub fn set_keyed(key: &str, burst: u32, period_ms: u32) -> Result<(), ()> {
+                unsafe {
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "set-keyed"]
+                        fn wit_import1(_: *mut u8, _: usize, _: i32, _: i32) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: i32,
+                    ) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = unsafe {
+                        wit_import1(
+                            ptr0.cast_mut(),
+                            len0,
+                            _rt::as_i32(&burst),
+                            _rt::as_i32(&period_ms),
+                        )
+                    };
+                    match ret {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = ();
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Returns the burst size and the period in milliseconds of the limiter
+            /// with the given key.
+            ///
+            /// Returns `Some((burst, period-ms))` if the limiter is set, `None`
+            /// otherwise.
+            pub fn keyed(key: &str) -> Option<(u32, u32)> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "keyed"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result6 = match l3 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1.add(4).cast::<i32>();
+                                let l5 = *ptr1.add(8).cast::<i32>();
+                                (l4 as u32, l5 as u32)
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result6
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Removes the limiter with the given key.
+            ///
+            /// Returns `true` if the limiter was set, `false` otherwise.
+            pub fn remove_keyed(key: &str) -> bool {
+                unsafe {
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "remove-keyed"]
+                        fn wit_import1(_: *mut u8, _: usize) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8, _: usize) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = unsafe { wit_import1(ptr0.cast_mut(), len0) };
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Same as `ready`, for the limiter with the given key.
+            ///
+            /// Returns `true` if the limiter is ready or not set, `false` otherwise.
+            pub fn ready_keyed(key: &str) -> bool {
+                unsafe {
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "ready-keyed"]
+                        fn wit_import1(_: *mut u8, _: usize) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8, _: usize) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = unsafe { wit_import1(ptr0.cast_mut(), len0) };
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Same as `block`, for the limiter with the given key. Returns
+            /// immediately if the limiter is not set.
+            pub fn block_keyed(key: &str) -> () {
+                unsafe {
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "block-keyed"]
+                        fn wit_import1(_: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import1(ptr0.cast_mut(), len0) };
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Returns whether requests sent with `midoku:http` block on a limiter
+            /// automatically, see `set-auto-block`.
+            pub fn auto_block() -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "auto-block"]
+                        fn wit_import0() -> i32;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import() {
+                    unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
-                    wit_import();
+                    let ret = unsafe { wit_import0() };
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Sets whether requests sent with `midoku:http` block on a limiter
+            /// automatically, before being sent.
+            ///
+            /// A request blocks on the limiter keyed by the host of its URL, e.g.
+            /// `cdn.example.com`, or on the default limiter if no limiter has this
+            /// key. Retries of a request wait on the same limiter. Disabled by
+            /// default, in which case the source blocks on limiters itself.
+            pub fn set_auto_block(enabled: bool) -> () {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "midoku:limiter/rate-limiter@0.2.0")]
+                    unsafe extern "C" {
+                        #[link_name = "set-auto-block"]
+                        fn wit_import0(_: i32);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0(_: i32) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import0(
+                            match &enabled {
+                                true => 1,
+                                false => 0,
+                            },
+                        )
+                    };
                 }
             }
         }
     }
     pub mod settings {
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod settings {
             #[used]
             #[doc(hidden)]
@@ -603,138 +2579,162 @@ pub mod midoku {
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "midoku:settings/settings@0.1.0")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "get"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result30 = match l3 {
                         0 => {
                             let e = {
-                                let l3 = i32::from(*ptr1.add(8).cast::<u8>());
-                                let v28 = match l3 {
+                                let l4 = i32::from(*ptr1.add(8).cast::<u8>());
+                                let v29 = match l4 {
                                     0 => {
-                                        let e28 = {
-                                            let l4 = i32::from(*ptr1.add(16).cast::<u8>());
-                                            _rt::bool_lift(l4 as u8)
+                                        let e29 = {
+                                            let l5 = i32::from(*ptr1.add(16).cast::<u8>());
+                                            _rt::bool_lift(l5 as u8)
                                         };
-                                        Value::Bool(e28)
+                                        Value::Bool(e29)
                                     }
                                     1 => {
-                                        let e28 = {
-                                            let l5 = i32::from(*ptr1.add(16).cast::<u8>());
-                                            let v9 = match l5 {
+                                        let e29 = {
+                                            let l6 = i32::from(*ptr1.add(16).cast::<u8>());
+                                            let v10 = match l6 {
                                                 0 => {
-                                                    let e9 = {
-                                                        let l6 = *ptr1.add(24).cast::<i64>();
-                                                        l6
+                                                    let e10 = {
+                                                        let l7 = *ptr1.add(24).cast::<i64>();
+                                                        l7
                                                     };
-                                                    Number::S64(e9)
+                                                    Number::S64(e10)
                                                 }
                                                 1 => {
-                                                    let e9 = {
-                                                        let l7 = *ptr1.add(24).cast::<i64>();
-                                                        l7 as u64
+                                                    let e10 = {
+                                                        let l8 = *ptr1.add(24).cast::<i64>();
+                                                        l8 as u64
                                                     };
-                                                    Number::U64(e9)
+                                                    Number::U64(e10)
                                                 }
                                                 n => {
                                                     debug_assert_eq!(n, 2, "invalid enum discriminant");
-                                                    let e9 = {
-                                                        let l8 = *ptr1.add(24).cast::<f64>();
-                                                        l8
+                                                    let e10 = {
+                                                        let l9 = *ptr1.add(24).cast::<f64>();
+                                                        l9
                                                     };
-                                                    Number::F64(e9)
+                                                    Number::F64(e10)
                                                 }
                                             };
-                                            v9
+                                            v10
                                         };
-                                        Value::Number(e28)
+                                        Value::Number(e29)
                                     }
                                     2 => {
-                                        let e28 = {
-                                            let l10 = *ptr1.add(16).cast::<*mut u8>();
-                                            let l11 = *ptr1.add(20).cast::<usize>();
-                                            let len12 = l11;
-                                            let bytes12 = _rt::Vec::from_raw_parts(
-                                                l10.cast(),
-                                                len12,
-                                                len12,
+                                        let e29 = {
+                                            let l11 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l12 = *ptr1
+                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len13 = l12;
+                                            let bytes13 = _rt::Vec::from_raw_parts(
+                                                l11.cast(),
+                                                len13,
+                                                len13,
                                             );
-                                            _rt::string_lift(bytes12)
+                                            _rt::string_lift(bytes13)
                                         };
-                                        Value::String(e28)
+                                        Value::String(e29)
                                     }
                                     3 => {
-                                        let e28 = {
-                                            let l13 = *ptr1.add(16).cast::<*mut u8>();
-                                            let l14 = *ptr1.add(20).cast::<usize>();
-                                            let base18 = l13;
-                                            let len18 = l14;
-                                            let mut result18 = _rt::Vec::with_capacity(len18);
-                                            for i in 0..len18 {
-                                                let base = base18.add(i * 8);
-                                                let e18 = {
-                                                    let l15 = *base.add(0).cast::<*mut u8>();
-                                                    let l16 = *base.add(4).cast::<usize>();
-                                                    let len17 = l16;
-                                                    let bytes17 = _rt::Vec::from_raw_parts(
-                                                        l15.cast(),
-                                                        len17,
-                                                        len17,
+                                        let e29 = {
+                                            let l14 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l15 = *ptr1
+                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base19 = l14;
+                                            let len19 = l15;
+                                            let mut result19 = _rt::Vec::with_capacity(len19);
+                                            for i in 0..len19 {
+                                                let base = base19
+                                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                                let e19 = {
+                                                    let l16 = *base.add(0).cast::<*mut u8>();
+                                                    let l17 = *base
+                                                        .add(::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>();
+                                                    let len18 = l17;
+                                                    let bytes18 = _rt::Vec::from_raw_parts(
+                                                        l16.cast(),
+                                                        len18,
+                                                        len18,
                                                     );
-                                                    _rt::string_lift(bytes17)
+                                                    _rt::string_lift(bytes18)
                                                 };
-                                                result18.push(e18);
+                                                result19.push(e19);
                                             }
-                                            _rt::cabi_dealloc(base18, len18 * 8, 4);
-                                            result18
+                                            _rt::cabi_dealloc(
+                                                base19,
+                                                len19 * (2 * ::core::mem::size_of::<*const u8>()),
+                                                ::core::mem::size_of::<*const u8>(),
+                                            );
+                                            result19
                                         };
-                                        Value::Array(e28)
+                                        Value::Array(e29)
                                     }
                                     n => {
                                         debug_assert_eq!(n, 4, "invalid enum discriminant");
-                                        let e28 = {
-                                            let l19 = *ptr1.add(16).cast::<*mut u8>();
-                                            let l20 = *ptr1.add(20).cast::<usize>();
-                                            let base27 = l19;
-                                            let len27 = l20;
-                                            let mut result27 = _rt::Vec::with_capacity(len27);
-                                            for i in 0..len27 {
-                                                let base = base27.add(i * 16);
-                                                let e27 = {
-                                                    let l21 = *base.add(0).cast::<*mut u8>();
-                                                    let l22 = *base.add(4).cast::<usize>();
-                                                    let len23 = l22;
-                                                    let bytes23 = _rt::Vec::from_raw_parts(
-                                                        l21.cast(),
-                                                        len23,
-                                                        len23,
+                                        let e29 = {
+                                            let l20 = *ptr1.add(16).cast::<*mut u8>();
+                                            let l21 = *ptr1
+                                                .add(16 + 1 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base28 = l20;
+                                            let len28 = l21;
+                                            let mut result28 = _rt::Vec::with_capacity(len28);
+                                            for i in 0..len28 {
+                                                let base = base28
+                                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                                let e28 = {
+                                                    let l22 = *base.add(0).cast::<*mut u8>();
+                                                    let l23 = *base
+                                                        .add(::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>();
+                                                    let len24 = l23;
+                                                    let bytes24 = _rt::Vec::from_raw_parts(
+                                                        l22.cast(),
+                                                        len24,
+                                                        len24,
                                                     );
-                                                    let l24 = *base.add(8).cast::<*mut u8>();
-                                                    let l25 = *base.add(12).cast::<usize>();
-                                                    let len26 = l25;
-                                                    let bytes26 = _rt::Vec::from_raw_parts(
-                                                        l24.cast(),
-                                                        len26,
-                                                        len26,
+                                                    let l25 = *base
+                                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<*mut u8>();
+                                                    let l26 = *base
+                                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<usize>();
+                                                    let len27 = l26;
+                                                    let bytes27 = _rt::Vec::from_raw_parts(
+                                                        l25.cast(),
+                                                        len27,
+                                                        len27,
                                                     );
-                                                    (_rt::string_lift(bytes23), _rt::string_lift(bytes26))
+                                                    (_rt::string_lift(bytes24), _rt::string_lift(bytes27))
                                                 };
-                                                result27.push(e27);
+                                                result28.push(e28);
                                             }
-                                            _rt::cabi_dealloc(base27, len27 * 16, 4);
-                                            result27
+                                            _rt::cabi_dealloc(
+                                                base28,
+                                                len28 * (4 * ::core::mem::size_of::<*const u8>()),
+                                                ::core::mem::size_of::<*const u8>(),
+                                            );
+                                            result28
                                         };
-                                        Value::Map(e28)
+                                        Value::Map(e29)
                                     }
                                 };
-                                v28
+                                v29
                             };
                             Ok(e)
                         }
@@ -743,7 +2743,8 @@ pub mod midoku {
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result30
                 }
             }
         }
@@ -754,26 +2755,465 @@ pub mod midoku {
 pub mod exports {
     pub mod midoku {
         pub mod bindings {
-            #[allow(dead_code, clippy::all)]
+            /// The Midoku API implemented by sources.
+            ///
+            /// Every function returns a `source-error` on failure, allowing the host to
+            /// tell apart, for example, a manga removed from the source from the source
+            /// being unreachable.
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod api {
                 #[used]
                 #[doc(hidden)]
                 static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
                 use super::super::super::super::_rt;
                 pub type Chapter = super::super::super::super::exports::midoku::types::chapter::Chapter;
+                pub type SourceError = super::super::super::super::exports::midoku::types::error::SourceError;
                 pub type Filter = super::super::super::super::exports::midoku::types::filter::Filter;
                 pub type Manga = super::super::super::super::exports::midoku::types::manga::Manga;
                 pub type Page = super::super::super::super::exports::midoku::types::page::Page;
+                pub type SourceInfo = super::super::super::super::exports::midoku::types::source_info::SourceInfo;
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_get_source_info_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::get_source_info();
+                    let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let super::super::super::super::exports::midoku::types::source_info::SourceInfo {
+                        name: name2,
+                        version: version2,
+                        languages: languages2,
+                        base_url: base_url2,
+                        nsfw: nsfw2,
+                        icon: icon2,
+                        allowed_hosts: allowed_hosts2,
+                    } = result0;
+                    let vec3 = (name2.into_bytes()).into_boxed_slice();
+                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                    let len3 = vec3.len();
+                    ::core::mem::forget(vec3);
+                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                    let vec4 = (version2.into_bytes()).into_boxed_slice();
+                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                    let len4 = vec4.len();
+                    ::core::mem::forget(vec4);
+                    *ptr1.add(3 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+                    *ptr1
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr4.cast_mut();
+                    let vec6 = languages2;
+                    let len6 = vec6.len();
+                    let layout6 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec6.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result6 = if layout6.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout6).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout6);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec6.into_iter().enumerate() {
+                        let base = result6
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let vec5 = (e.into_bytes()).into_boxed_slice();
+                            let ptr5 = vec5.as_ptr().cast::<u8>();
+                            let len5 = vec5.len();
+                            ::core::mem::forget(vec5);
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len5;
+                            *base.add(0).cast::<*mut u8>() = ptr5.cast_mut();
+                        }
+                    }
+                    *ptr1.add(5 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+                    *ptr1
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result6;
+                    let vec7 = (base_url2.into_bytes()).into_boxed_slice();
+                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                    let len7 = vec7.len();
+                    ::core::mem::forget(vec7);
+                    *ptr1.add(7 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+                    *ptr1
+                        .add(6 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr7.cast_mut();
+                    *ptr1.add(8 * ::core::mem::size_of::<*const u8>()).cast::<u8>() = (match nsfw2 {
+                        true => 1,
+                        false => 0,
+                    }) as u8;
+                    match icon2 {
+                        Some(e) => {
+                            *ptr1
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            let vec8 = (e).into_boxed_slice();
+                            let ptr8 = vec8.as_ptr().cast::<u8>();
+                            let len8 = vec8.len();
+                            ::core::mem::forget(vec8);
+                            *ptr1
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len8;
+                            *ptr1
+                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr8.cast_mut();
+                        }
+                        None => {
+                            *ptr1
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    match allowed_hosts2 {
+                        Some(e) => {
+                            *ptr1
+                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            let vec10 = e;
+                            let len10 = vec10.len();
+                            let layout10 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec10.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result10 = if layout10.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout10).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout10);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec10.into_iter().enumerate() {
+                                let base = result10
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let vec9 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                                    let len9 = vec9.len();
+                                    ::core::mem::forget(vec9);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len9;
+                                    *base.add(0).cast::<*mut u8>() = ptr9.cast_mut();
+                                }
+                            }
+                            *ptr1
+                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len10;
+                            *ptr1
+                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result10;
+                        }
+                        None => {
+                            *ptr1
+                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_get_source_info<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l0, l1, 1);
+                    let l2 = *arg0
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l3 = *arg0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l2, l3, 1);
+                    let l4 = *arg0
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l5 = *arg0
+                        .add(5 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base8 = l4;
+                    let len8 = l5;
+                    for i in 0..len8 {
+                        let base = base8
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l6 = *base.add(0).cast::<*mut u8>();
+                            let l7 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l6, l7, 1);
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base8,
+                        len8 * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let l9 = *arg0
+                        .add(6 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l10 = *arg0
+                        .add(7 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l9, l10, 1);
+                    let l11 = i32::from(
+                        *arg0.add(9 * ::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                    );
+                    match l11 {
+                        0 => {}
+                        _ => {
+                            let l12 = *arg0
+                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l13 = *arg0
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base14 = l12;
+                            let len14 = l13;
+                            _rt::cabi_dealloc(base14, len14 * 1, 1);
+                        }
+                    }
+                    let l15 = i32::from(
+                        *arg0.add(12 * ::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                    );
+                    match l15 {
+                        0 => {}
+                        _ => {
+                            let l16 = *arg0
+                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l17 = *arg0
+                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base20 = l16;
+                            let len20 = l17;
+                            for i in 0..len20 {
+                                let base = base20
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l18 = *base.add(0).cast::<*mut u8>();
+                                    let l19 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l18, l19, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base20,
+                                len20 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                    }
+                }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_initialize_cabi<T: Guest>() -> i32 {
+                pub unsafe fn _export_initialize_cabi<T: Guest>() -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let result0 = T::initialize();
-                    let result1 = match result0 {
-                        Ok(_) => 0i32,
-                        Err(_) => 1i32,
+                    let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result0 {
+                        Ok(_) => {
+                            *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+                            use super::super::super::super::exports::midoku::types::error::SourceError as V9;
+                            match e {
+                                V9::Network(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                    let vec2 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                                    let len2 = vec2.len();
+                                    ::core::mem::forget(vec2);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len2;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr2.cast_mut();
+                                }
+                                V9::Parse(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                V9::NotFound(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                V9::RateLimited(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                V9::RequiresLogin(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                V9::Unsupported(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                V9::Other(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                            }
+                        }
                     };
-                    result1
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_initialize<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                1 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                2 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                3 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                4 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                5 => {
+                                    let l12 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l13 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                }
+                                _ => {
+                                    let l14 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l15 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l14, l15, 1);
+                                }
+                            }
+                        }
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -787,15 +3227,20 @@ pub mod exports {
                     let len7 = arg1;
                     let mut result7 = _rt::Vec::with_capacity(len7);
                     for i in 0..len7 {
-                        let base = base7.add(i * 12);
+                        let base = base7
+                            .add(i * (3 * ::core::mem::size_of::<*const u8>()));
                         let e7 = {
                             let l0 = i32::from(*base.add(0).cast::<u8>());
                             use super::super::super::super::exports::midoku::types::filter::Filter as V6;
                             let v6 = match l0 {
                                 0 => {
                                     let e6 = {
-                                        let l1 = *base.add(4).cast::<*mut u8>();
-                                        let l2 = *base.add(8).cast::<usize>();
+                                        let l1 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l2 = *base
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
                                         let len3 = l2;
                                         let bytes3 = _rt::Vec::from_raw_parts(
                                             l1.cast(),
@@ -811,8 +3256,14 @@ pub mod exports {
                                 n => {
                                     debug_assert_eq!(n, 1, "invalid enum discriminant");
                                     let e6 = {
-                                        let l4 = *base.add(4).cast::<i32>();
-                                        let l5 = i32::from(*base.add(8).cast::<u8>());
+                                        let l4 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<i32>();
+                                        let l5 = i32::from(
+                                            *base
+                                                .add(4 + 1 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>(),
+                                        );
                                         super::super::super::super::exports::midoku::types::filter::FilterSort {
                                             option_index: l4 as u32,
                                             option_reversed: _rt::bool_lift(l5 as u8),
@@ -825,9 +3276,13 @@ pub mod exports {
                         };
                         result7.push(e7);
                     }
-                    _rt::cabi_dealloc(base7, len7 * 12, 4);
+                    _rt::cabi_dealloc(
+                        base7,
+                        len7 * (3 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
                     let result8 = T::get_manga_list(result7, arg2 as u32);
-                    let ptr9 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr9 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result8 {
                         Ok(e) => {
                             *ptr9.add(0).cast::<u8>() = (0i32) as u8;
@@ -835,8 +3290,8 @@ pub mod exports {
                             let vec21 = t10_0;
                             let len21 = vec21.len();
                             let layout21 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec21.len() * 68,
-                                4,
+                                vec21.len() * (17 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
                             );
                             let result21 = if layout21.size() != 0 {
                                 let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
@@ -848,7 +3303,8 @@ pub mod exports {
                                 ::core::ptr::null_mut()
                             };
                             for (i, e) in vec21.into_iter().enumerate() {
-                                let base = result21.add(i * 68);
+                                let base = result21
+                                    .add(i * (17 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let super::super::super::super::exports::midoku::types::manga::Manga {
                                         id: id11,
@@ -867,49 +3323,75 @@ pub mod exports {
                                     let ptr12 = vec12.as_ptr().cast::<u8>();
                                     let len12 = vec12.len();
                                     ::core::mem::forget(vec12);
-                                    *base.add(4).cast::<usize>() = len12;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
                                     *base.add(0).cast::<*mut u8>() = ptr12.cast_mut();
                                     let vec13 = (title11.into_bytes()).into_boxed_slice();
                                     let ptr13 = vec13.as_ptr().cast::<u8>();
                                     let len13 = vec13.len();
                                     ::core::mem::forget(vec13);
-                                    *base.add(12).cast::<usize>() = len13;
-                                    *base.add(8).cast::<*mut u8>() = ptr13.cast_mut();
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
                                     let vec14 = (url11.into_bytes()).into_boxed_slice();
                                     let ptr14 = vec14.as_ptr().cast::<u8>();
                                     let len14 = vec14.len();
                                     ::core::mem::forget(vec14);
-                                    *base.add(20).cast::<usize>() = len14;
-                                    *base.add(16).cast::<*mut u8>() = ptr14.cast_mut();
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
                                     let vec15 = (description11.into_bytes()).into_boxed_slice();
                                     let ptr15 = vec15.as_ptr().cast::<u8>();
                                     let len15 = vec15.len();
                                     ::core::mem::forget(vec15);
-                                    *base.add(28).cast::<usize>() = len15;
-                                    *base.add(24).cast::<*mut u8>() = ptr15.cast_mut();
+                                    *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len15;
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr15.cast_mut();
                                     let vec16 = (cover_url11.into_bytes()).into_boxed_slice();
                                     let ptr16 = vec16.as_ptr().cast::<u8>();
                                     let len16 = vec16.len();
                                     ::core::mem::forget(vec16);
-                                    *base.add(36).cast::<usize>() = len16;
-                                    *base.add(32).cast::<*mut u8>() = ptr16.cast_mut();
+                                    *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len16;
+                                    *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr16.cast_mut();
                                     let vec17 = (author_name11.into_bytes()).into_boxed_slice();
                                     let ptr17 = vec17.as_ptr().cast::<u8>();
                                     let len17 = vec17.len();
                                     ::core::mem::forget(vec17);
-                                    *base.add(44).cast::<usize>() = len17;
-                                    *base.add(40).cast::<*mut u8>() = ptr17.cast_mut();
+                                    *base
+                                        .add(11 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len17;
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr17.cast_mut();
                                     let vec18 = (artist_name11.into_bytes()).into_boxed_slice();
                                     let ptr18 = vec18.as_ptr().cast::<u8>();
                                     let len18 = vec18.len();
                                     ::core::mem::forget(vec18);
-                                    *base.add(52).cast::<usize>() = len18;
-                                    *base.add(48).cast::<*mut u8>() = ptr18.cast_mut();
+                                    *base
+                                        .add(13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len18;
+                                    *base
+                                        .add(12 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr18.cast_mut();
                                     let vec20 = categories11;
                                     let len20 = vec20.len();
                                     let layout20 = _rt::alloc::Layout::from_size_align_unchecked(
-                                        vec20.len() * 8,
-                                        4,
+                                        vec20.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
                                     );
                                     let result20 = if layout20.size() != 0 {
                                         let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
@@ -921,35 +3403,159 @@ pub mod exports {
                                         ::core::ptr::null_mut()
                                     };
                                     for (i, e) in vec20.into_iter().enumerate() {
-                                        let base = result20.add(i * 8);
+                                        let base = result20
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
                                         {
                                             let vec19 = (e.into_bytes()).into_boxed_slice();
                                             let ptr19 = vec19.as_ptr().cast::<u8>();
                                             let len19 = vec19.len();
                                             ::core::mem::forget(vec19);
-                                            *base.add(4).cast::<usize>() = len19;
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len19;
                                             *base.add(0).cast::<*mut u8>() = ptr19.cast_mut();
                                         }
                                     }
-                                    *base.add(60).cast::<usize>() = len20;
-                                    *base.add(56).cast::<*mut u8>() = result20;
-                                    *base.add(64).cast::<u8>() = (status11.clone() as i32)
-                                        as u8;
-                                    *base.add(65).cast::<u8>() = (content_rating11.clone()
-                                        as i32) as u8;
-                                    *base.add(66).cast::<u8>() = (reading_mode11.clone() as i32)
-                                        as u8;
+                                    *base
+                                        .add(15 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len20;
+                                    *base
+                                        .add(14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result20;
+                                    *base
+                                        .add(16 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (status11.clone() as i32) as u8;
+                                    *base
+                                        .add(1 + 16 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (content_rating11.clone() as i32) as u8;
+                                    *base
+                                        .add(2 + 16 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (reading_mode11.clone() as i32) as u8;
                                 }
                             }
-                            *ptr9.add(8).cast::<usize>() = len21;
-                            *ptr9.add(4).cast::<*mut u8>() = result21;
-                            *ptr9.add(12).cast::<u8>() = (match t10_1 {
+                            *ptr9
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len21;
+                            *ptr9
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result21;
+                            *ptr9
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (match t10_1 {
                                 true => 1,
                                 false => 0,
                             }) as u8;
                         }
-                        Err(_) => {
+                        Err(e) => {
                             *ptr9.add(0).cast::<u8>() = (1i32) as u8;
+                            use super::super::super::super::exports::midoku::types::error::SourceError as V29;
+                            match e {
+                                V29::Network(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                    let vec22 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr22 = vec22.as_ptr().cast::<u8>();
+                                    let len22 = vec22.len();
+                                    ::core::mem::forget(vec22);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len22;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr22.cast_mut();
+                                }
+                                V29::Parse(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec23 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr23 = vec23.as_ptr().cast::<u8>();
+                                    let len23 = vec23.len();
+                                    ::core::mem::forget(vec23);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len23;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr23.cast_mut();
+                                }
+                                V29::NotFound(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    let vec24 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr24 = vec24.as_ptr().cast::<u8>();
+                                    let len24 = vec24.len();
+                                    ::core::mem::forget(vec24);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len24;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr24.cast_mut();
+                                }
+                                V29::RateLimited(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                    let vec25 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr25 = vec25.as_ptr().cast::<u8>();
+                                    let len25 = vec25.len();
+                                    ::core::mem::forget(vec25);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len25;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr25.cast_mut();
+                                }
+                                V29::RequiresLogin(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                    let vec26 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr26 = vec26.as_ptr().cast::<u8>();
+                                    let len26 = vec26.len();
+                                    ::core::mem::forget(vec26);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len26;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr26.cast_mut();
+                                }
+                                V29::Unsupported(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                    let vec27 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr27 = vec27.as_ptr().cast::<u8>();
+                                    let len27 = vec27.len();
+                                    ::core::mem::forget(vec27);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len27;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr27.cast_mut();
+                                }
+                                V29::Other(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec28 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr28 = vec28.as_ptr().cast::<u8>();
+                                    let len28 = vec28.len();
+                                    ::core::mem::forget(vec28);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len28;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr28.cast_mut();
+                                }
+                            }
                         }
                     };
                     ptr9
@@ -960,52 +3566,167 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let base22 = l1;
                             let len22 = l2;
                             for i in 0..len22 {
-                                let base = base22.add(i * 68);
+                                let base = base22
+                                    .add(i * (17 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let l3 = *base.add(0).cast::<*mut u8>();
-                                    let l4 = *base.add(4).cast::<usize>();
+                                    let l4 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l3, l4, 1);
-                                    let l5 = *base.add(8).cast::<*mut u8>();
-                                    let l6 = *base.add(12).cast::<usize>();
+                                    let l5 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l5, l6, 1);
-                                    let l7 = *base.add(16).cast::<*mut u8>();
-                                    let l8 = *base.add(20).cast::<usize>();
+                                    let l7 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l8 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l7, l8, 1);
-                                    let l9 = *base.add(24).cast::<*mut u8>();
-                                    let l10 = *base.add(28).cast::<usize>();
+                                    let l9 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l10 = *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l9, l10, 1);
-                                    let l11 = *base.add(32).cast::<*mut u8>();
-                                    let l12 = *base.add(36).cast::<usize>();
+                                    let l11 = *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l12 = *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l11, l12, 1);
-                                    let l13 = *base.add(40).cast::<*mut u8>();
-                                    let l14 = *base.add(44).cast::<usize>();
+                                    let l13 = *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l14 = *base
+                                        .add(11 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l13, l14, 1);
-                                    let l15 = *base.add(48).cast::<*mut u8>();
-                                    let l16 = *base.add(52).cast::<usize>();
+                                    let l15 = *base
+                                        .add(12 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l15, l16, 1);
-                                    let l17 = *base.add(56).cast::<*mut u8>();
-                                    let l18 = *base.add(60).cast::<usize>();
+                                    let l17 = *base
+                                        .add(14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *base
+                                        .add(15 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base21 = l17;
                                     let len21 = l18;
                                     for i in 0..len21 {
-                                        let base = base21.add(i * 8);
+                                        let base = base21
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
                                         {
                                             let l19 = *base.add(0).cast::<*mut u8>();
-                                            let l20 = *base.add(4).cast::<usize>();
+                                            let l20 = *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
                                             _rt::cabi_dealloc(l19, l20, 1);
                                         }
                                     }
-                                    _rt::cabi_dealloc(base21, len21 * 8, 4);
+                                    _rt::cabi_dealloc(
+                                        base21,
+                                        len21 * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base22,
+                                len22 * (17 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l23 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l23 {
+                                0 => {
+                                    let l24 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l25 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l24, l25, 1);
+                                }
+                                1 => {
+                                    let l26 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l27 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l26, l27, 1);
+                                }
+                                2 => {
+                                    let l28 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l29 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l28, l29, 1);
+                                }
+                                3 => {
+                                    let l30 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l31 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l30, l31, 1);
+                                }
+                                4 => {
+                                    let l32 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l33 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l32, l33, 1);
+                                }
+                                5 => {
+                                    let l34 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l35 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l34, l35, 1);
+                                }
+                                _ => {
+                                    let l36 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l37 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l36, l37, 1);
                                 }
                             }
-                            _rt::cabi_dealloc(base22, len22 * 68, 4);
                         }
-                        _ => {}
                     }
                 }
                 #[doc(hidden)]
@@ -1018,7 +3739,7 @@ pub mod exports {
                     let len0 = arg1;
                     let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
                     let result1 = T::get_manga_details(_rt::string_lift(bytes0));
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result1 {
                         Ok(e) => {
                             *ptr2.add(0).cast::<u8>() = (0i32) as u8;
@@ -1039,49 +3760,77 @@ pub mod exports {
                             let ptr4 = vec4.as_ptr().cast::<u8>();
                             let len4 = vec4.len();
                             ::core::mem::forget(vec4);
-                            *ptr2.add(8).cast::<usize>() = len4;
-                            *ptr2.add(4).cast::<*mut u8>() = ptr4.cast_mut();
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len4;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr4.cast_mut();
                             let vec5 = (title3.into_bytes()).into_boxed_slice();
                             let ptr5 = vec5.as_ptr().cast::<u8>();
                             let len5 = vec5.len();
                             ::core::mem::forget(vec5);
-                            *ptr2.add(16).cast::<usize>() = len5;
-                            *ptr2.add(12).cast::<*mut u8>() = ptr5.cast_mut();
+                            *ptr2
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len5;
+                            *ptr2
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr5.cast_mut();
                             let vec6 = (url3.into_bytes()).into_boxed_slice();
                             let ptr6 = vec6.as_ptr().cast::<u8>();
                             let len6 = vec6.len();
                             ::core::mem::forget(vec6);
-                            *ptr2.add(24).cast::<usize>() = len6;
-                            *ptr2.add(20).cast::<*mut u8>() = ptr6.cast_mut();
+                            *ptr2
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len6;
+                            *ptr2
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr6.cast_mut();
                             let vec7 = (description3.into_bytes()).into_boxed_slice();
                             let ptr7 = vec7.as_ptr().cast::<u8>();
                             let len7 = vec7.len();
                             ::core::mem::forget(vec7);
-                            *ptr2.add(32).cast::<usize>() = len7;
-                            *ptr2.add(28).cast::<*mut u8>() = ptr7.cast_mut();
+                            *ptr2
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len7;
+                            *ptr2
+                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr7.cast_mut();
                             let vec8 = (cover_url3.into_bytes()).into_boxed_slice();
                             let ptr8 = vec8.as_ptr().cast::<u8>();
                             let len8 = vec8.len();
                             ::core::mem::forget(vec8);
-                            *ptr2.add(40).cast::<usize>() = len8;
-                            *ptr2.add(36).cast::<*mut u8>() = ptr8.cast_mut();
+                            *ptr2
+                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len8;
+                            *ptr2
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr8.cast_mut();
                             let vec9 = (author_name3.into_bytes()).into_boxed_slice();
                             let ptr9 = vec9.as_ptr().cast::<u8>();
                             let len9 = vec9.len();
                             ::core::mem::forget(vec9);
-                            *ptr2.add(48).cast::<usize>() = len9;
-                            *ptr2.add(44).cast::<*mut u8>() = ptr9.cast_mut();
+                            *ptr2
+                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len9;
+                            *ptr2
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr9.cast_mut();
                             let vec10 = (artist_name3.into_bytes()).into_boxed_slice();
                             let ptr10 = vec10.as_ptr().cast::<u8>();
                             let len10 = vec10.len();
                             ::core::mem::forget(vec10);
-                            *ptr2.add(56).cast::<usize>() = len10;
-                            *ptr2.add(52).cast::<*mut u8>() = ptr10.cast_mut();
+                            *ptr2
+                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len10;
+                            *ptr2
+                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr10.cast_mut();
                             let vec12 = categories3;
                             let len12 = vec12.len();
                             let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec12.len() * 8,
-                                4,
+                                vec12.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
                             );
                             let result12 = if layout12.size() != 0 {
                                 let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
@@ -1093,26 +3842,145 @@ pub mod exports {
                                 ::core::ptr::null_mut()
                             };
                             for (i, e) in vec12.into_iter().enumerate() {
-                                let base = result12.add(i * 8);
+                                let base = result12
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let vec11 = (e.into_bytes()).into_boxed_slice();
                                     let ptr11 = vec11.as_ptr().cast::<u8>();
                                     let len11 = vec11.len();
                                     ::core::mem::forget(vec11);
-                                    *base.add(4).cast::<usize>() = len11;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
                                     *base.add(0).cast::<*mut u8>() = ptr11.cast_mut();
                                 }
                             }
-                            *ptr2.add(64).cast::<usize>() = len12;
-                            *ptr2.add(60).cast::<*mut u8>() = result12;
-                            *ptr2.add(68).cast::<u8>() = (status3.clone() as i32) as u8;
-                            *ptr2.add(69).cast::<u8>() = (content_rating3.clone() as i32)
-                                as u8;
-                            *ptr2.add(70).cast::<u8>() = (reading_mode3.clone() as i32)
-                                as u8;
-                        }
-                        Err(_) => {
+                            *ptr2
+                                .add(16 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len12;
+                            *ptr2
+                                .add(15 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result12;
+                            *ptr2
+                                .add(17 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (status3.clone() as i32) as u8;
+                            *ptr2
+                                .add(1 + 17 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (content_rating3.clone() as i32) as u8;
+                            *ptr2
+                                .add(2 + 17 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (reading_mode3.clone() as i32) as u8;
+                        }
+                        Err(e) => {
                             *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            use super::super::super::super::exports::midoku::types::error::SourceError as V20;
+                            match e {
+                                V20::Network(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                V20::Parse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec14 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                }
+                                V20::NotFound(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    let vec15 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr15 = vec15.as_ptr().cast::<u8>();
+                                    let len15 = vec15.len();
+                                    ::core::mem::forget(vec15);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len15;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr15.cast_mut();
+                                }
+                                V20::RateLimited(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                    let vec16 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr16 = vec16.as_ptr().cast::<u8>();
+                                    let len16 = vec16.len();
+                                    ::core::mem::forget(vec16);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len16;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr16.cast_mut();
+                                }
+                                V20::RequiresLogin(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                    let vec17 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr17 = vec17.as_ptr().cast::<u8>();
+                                    let len17 = vec17.len();
+                                    ::core::mem::forget(vec17);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len17;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr17.cast_mut();
+                                }
+                                V20::Unsupported(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                    let vec18 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr18 = vec18.as_ptr().cast::<u8>();
+                                    let len18 = vec18.len();
+                                    ::core::mem::forget(vec18);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len18;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr18.cast_mut();
+                                }
+                                V20::Other(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec19 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr19 = vec19.as_ptr().cast::<u8>();
+                                    let len19 = vec19.len();
+                                    ::core::mem::forget(vec19);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len19;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr19.cast_mut();
+                                }
+                            }
                         }
                     };
                     ptr2
@@ -1123,42 +3991,150 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l1, l2, 1);
-                            let l3 = *arg0.add(12).cast::<*mut u8>();
-                            let l4 = *arg0.add(16).cast::<usize>();
+                            let l3 = *arg0
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l4 = *arg0
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l3, l4, 1);
-                            let l5 = *arg0.add(20).cast::<*mut u8>();
-                            let l6 = *arg0.add(24).cast::<usize>();
+                            let l5 = *arg0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l5, l6, 1);
-                            let l7 = *arg0.add(28).cast::<*mut u8>();
-                            let l8 = *arg0.add(32).cast::<usize>();
+                            let l7 = *arg0
+                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l8 = *arg0
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l7, l8, 1);
-                            let l9 = *arg0.add(36).cast::<*mut u8>();
-                            let l10 = *arg0.add(40).cast::<usize>();
+                            let l9 = *arg0
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l10 = *arg0
+                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l9, l10, 1);
-                            let l11 = *arg0.add(44).cast::<*mut u8>();
-                            let l12 = *arg0.add(48).cast::<usize>();
+                            let l11 = *arg0
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l12 = *arg0
+                                .add(12 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l11, l12, 1);
-                            let l13 = *arg0.add(52).cast::<*mut u8>();
-                            let l14 = *arg0.add(56).cast::<usize>();
+                            let l13 = *arg0
+                                .add(13 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l14 = *arg0
+                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l13, l14, 1);
-                            let l15 = *arg0.add(60).cast::<*mut u8>();
-                            let l16 = *arg0.add(64).cast::<usize>();
+                            let l15 = *arg0
+                                .add(15 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l16 = *arg0
+                                .add(16 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let base19 = l15;
                             let len19 = l16;
                             for i in 0..len19 {
-                                let base = base19.add(i * 8);
+                                let base = base19
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let l17 = *base.add(0).cast::<*mut u8>();
-                                    let l18 = *base.add(4).cast::<usize>();
+                                    let l18 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l17, l18, 1);
                                 }
                             }
-                            _rt::cabi_dealloc(base19, len19 * 8, 4);
+                            _rt::cabi_dealloc(
+                                base19,
+                                len19 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l20 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l20 {
+                                0 => {
+                                    let l21 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l21, l22, 1);
+                                }
+                                1 => {
+                                    let l23 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l24 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l23, l24, 1);
+                                }
+                                2 => {
+                                    let l25 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l26 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l25, l26, 1);
+                                }
+                                3 => {
+                                    let l27 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l28 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l27, l28, 1);
+                                }
+                                4 => {
+                                    let l29 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l30 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l29, l30, 1);
+                                }
+                                5 => {
+                                    let l31 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l32 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l31, l32, 1);
+                                }
+                                _ => {
+                                    let l33 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l34 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l33, l34, 1);
+                                }
+                            }
                         }
-                        _ => {}
                     }
                 }
                 #[doc(hidden)]
@@ -1171,15 +4147,15 @@ pub mod exports {
                     let len0 = arg1;
                     let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
                     let result1 = T::get_chapter_list(_rt::string_lift(bytes0));
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result1 {
                         Ok(e) => {
                             *ptr2.add(0).cast::<u8>() = (0i32) as u8;
                             let vec9 = e;
                             let len9 = vec9.len();
                             let layout9 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec9.len() * 52,
-                                4,
+                                vec9.len() * (8 + 11 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
                             );
                             let result9 = if layout9.size() != 0 {
                                 let ptr = _rt::alloc::alloc(layout9).cast::<u8>();
@@ -1191,7 +4167,8 @@ pub mod exports {
                                 ::core::ptr::null_mut()
                             };
                             for (i, e) in vec9.into_iter().enumerate() {
-                                let base = result9.add(i * 52);
+                                let base = result9
+                                    .add(i * (8 + 11 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let super::super::super::super::exports::midoku::types::chapter::Chapter {
                                         id: id3,
@@ -1207,42 +4184,178 @@ pub mod exports {
                                     let ptr4 = vec4.as_ptr().cast::<u8>();
                                     let len4 = vec4.len();
                                     ::core::mem::forget(vec4);
-                                    *base.add(4).cast::<usize>() = len4;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
                                     *base.add(0).cast::<*mut u8>() = ptr4.cast_mut();
                                     let vec5 = (title3.into_bytes()).into_boxed_slice();
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
                                     let len5 = vec5.len();
                                     ::core::mem::forget(vec5);
-                                    *base.add(12).cast::<usize>() = len5;
-                                    *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
-                                    *base.add(16).cast::<f32>() = _rt::as_f32(volume3);
-                                    *base.add(20).cast::<f32>() = _rt::as_f32(chapter3);
-                                    *base.add(24).cast::<i32>() = _rt::as_i32(date_updated3);
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<f32>() = _rt::as_f32(volume3);
+                                    *base
+                                        .add(4 + 4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<f32>() = _rt::as_f32(chapter3);
+                                    *base
+                                        .add(8 + 4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i32>() = _rt::as_i32(date_updated3);
                                     let vec6 = (scanlator3.into_bytes()).into_boxed_slice();
                                     let ptr6 = vec6.as_ptr().cast::<u8>();
                                     let len6 = vec6.len();
                                     ::core::mem::forget(vec6);
-                                    *base.add(32).cast::<usize>() = len6;
-                                    *base.add(28).cast::<*mut u8>() = ptr6.cast_mut();
+                                    *base
+                                        .add(8 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *base
+                                        .add(8 + 5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
                                     let vec7 = (url3.into_bytes()).into_boxed_slice();
                                     let ptr7 = vec7.as_ptr().cast::<u8>();
                                     let len7 = vec7.len();
                                     ::core::mem::forget(vec7);
-                                    *base.add(40).cast::<usize>() = len7;
-                                    *base.add(36).cast::<*mut u8>() = ptr7.cast_mut();
+                                    *base
+                                        .add(8 + 8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *base
+                                        .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
                                     let vec8 = (language3.into_bytes()).into_boxed_slice();
                                     let ptr8 = vec8.as_ptr().cast::<u8>();
                                     let len8 = vec8.len();
                                     ::core::mem::forget(vec8);
-                                    *base.add(48).cast::<usize>() = len8;
-                                    *base.add(44).cast::<*mut u8>() = ptr8.cast_mut();
+                                    *base
+                                        .add(8 + 10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *base
+                                        .add(8 + 9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
                                 }
                             }
-                            *ptr2.add(8).cast::<usize>() = len9;
-                            *ptr2.add(4).cast::<*mut u8>() = result9;
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len9;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result9;
                         }
-                        Err(_) => {
+                        Err(e) => {
                             *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            use super::super::super::super::exports::midoku::types::error::SourceError as V17;
+                            match e {
+                                V17::Network(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                    let vec10 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                                V17::Parse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec11 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr11.cast_mut();
+                                }
+                                V17::NotFound(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    let vec12 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                V17::RateLimited(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                V17::RequiresLogin(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                    let vec14 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                }
+                                V17::Unsupported(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                    let vec15 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr15 = vec15.as_ptr().cast::<u8>();
+                                    let len15 = vec15.len();
+                                    ::core::mem::forget(vec15);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len15;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr15.cast_mut();
+                                }
+                                V17::Other(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec16 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr16 = vec16.as_ptr().cast::<u8>();
+                                    let len16 = vec16.len();
+                                    ::core::mem::forget(vec16);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len16;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr16.cast_mut();
+                                }
+                            }
                         }
                     };
                     ptr2
@@ -1253,33 +4366,129 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let base13 = l1;
                             let len13 = l2;
                             for i in 0..len13 {
-                                let base = base13.add(i * 52);
+                                let base = base13
+                                    .add(i * (8 + 11 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let l3 = *base.add(0).cast::<*mut u8>();
-                                    let l4 = *base.add(4).cast::<usize>();
+                                    let l4 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l3, l4, 1);
-                                    let l5 = *base.add(8).cast::<*mut u8>();
-                                    let l6 = *base.add(12).cast::<usize>();
+                                    let l5 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l5, l6, 1);
-                                    let l7 = *base.add(28).cast::<*mut u8>();
-                                    let l8 = *base.add(32).cast::<usize>();
+                                    let l7 = *base
+                                        .add(8 + 5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l8 = *base
+                                        .add(8 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l7, l8, 1);
-                                    let l9 = *base.add(36).cast::<*mut u8>();
-                                    let l10 = *base.add(40).cast::<usize>();
+                                    let l9 = *base
+                                        .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l10 = *base
+                                        .add(8 + 8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l9, l10, 1);
-                                    let l11 = *base.add(44).cast::<*mut u8>();
-                                    let l12 = *base.add(48).cast::<usize>();
+                                    let l11 = *base
+                                        .add(8 + 9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l12 = *base
+                                        .add(8 + 10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l11, l12, 1);
                                 }
                             }
-                            _rt::cabi_dealloc(base13, len13 * 52, 4);
+                            _rt::cabi_dealloc(
+                                base13,
+                                len13 * (8 + 11 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l14 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l14 {
+                                0 => {
+                                    let l15 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
+                                }
+                                1 => {
+                                    let l17 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                }
+                                2 => {
+                                    let l19 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l19, l20, 1);
+                                }
+                                3 => {
+                                    let l21 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l21, l22, 1);
+                                }
+                                4 => {
+                                    let l23 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l24 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l23, l24, 1);
+                                }
+                                5 => {
+                                    let l25 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l26 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l25, l26, 1);
+                                }
+                                _ => {
+                                    let l27 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l28 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l27, l28, 1);
+                                }
+                            }
                         }
-                        _ => {}
                     }
                 }
                 #[doc(hidden)]
@@ -1299,15 +4508,15 @@ pub mod exports {
                         _rt::string_lift(bytes0),
                         _rt::string_lift(bytes1),
                     );
-                    let ptr3 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result2 {
                         Ok(e) => {
                             *ptr3.add(0).cast::<u8>() = (0i32) as u8;
                             let vec7 = e;
                             let len7 = vec7.len();
                             let layout7 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec7.len() * 20,
-                                4,
+                                vec7.len() * (5 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
                             );
                             let result7 = if layout7.size() != 0 {
                                 let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
@@ -1319,7 +4528,8 @@ pub mod exports {
                                 ::core::ptr::null_mut()
                             };
                             for (i, e) in vec7.into_iter().enumerate() {
-                                let base = result7.add(i * 20);
+                                let base = result7
+                                    .add(i * (5 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let super::super::super::super::exports::midoku::types::page::Page {
                                         index: index4,
@@ -1331,21 +4541,141 @@ pub mod exports {
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
                                     let len5 = vec5.len();
                                     ::core::mem::forget(vec5);
-                                    *base.add(8).cast::<usize>() = len5;
-                                    *base.add(4).cast::<*mut u8>() = ptr5.cast_mut();
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
                                     let vec6 = (base644).into_boxed_slice();
                                     let ptr6 = vec6.as_ptr().cast::<u8>();
                                     let len6 = vec6.len();
                                     ::core::mem::forget(vec6);
-                                    *base.add(16).cast::<usize>() = len6;
-                                    *base.add(12).cast::<*mut u8>() = ptr6.cast_mut();
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
                                 }
                             }
-                            *ptr3.add(8).cast::<usize>() = len7;
-                            *ptr3.add(4).cast::<*mut u8>() = result7;
+                            *ptr3
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len7;
+                            *ptr3
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result7;
                         }
-                        Err(_) => {
+                        Err(e) => {
                             *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+                            use super::super::super::super::exports::midoku::types::error::SourceError as V15;
+                            match e {
+                                V15::Network(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                V15::Parse(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec9 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                                    let len9 = vec9.len();
+                                    ::core::mem::forget(vec9);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len9;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr9.cast_mut();
+                                }
+                                V15::NotFound(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                    let vec10 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                                V15::RateLimited(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                    let vec11 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr11.cast_mut();
+                                }
+                                V15::RequiresLogin(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                    let vec12 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                V15::Unsupported(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                V15::Other(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec14 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                }
+                            }
                         }
                     };
                     ptr3
@@ -1356,35 +4686,126 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let base8 = l1;
                             let len8 = l2;
                             for i in 0..len8 {
-                                let base = base8.add(i * 20);
+                                let base = base8
+                                    .add(i * (5 * ::core::mem::size_of::<*const u8>()));
                                 {
-                                    let l3 = *base.add(4).cast::<*mut u8>();
-                                    let l4 = *base.add(8).cast::<usize>();
+                                    let l3 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l4 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l3, l4, 1);
-                                    let l5 = *base.add(12).cast::<*mut u8>();
-                                    let l6 = *base.add(16).cast::<usize>();
+                                    let l5 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base7 = l5;
                                     let len7 = l6;
                                     _rt::cabi_dealloc(base7, len7 * 1, 1);
                                 }
                             }
-                            _rt::cabi_dealloc(base8, len8 * 20, 4);
+                            _rt::cabi_dealloc(
+                                base8,
+                                len8 * (5 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l9 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l9 {
+                                0 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                1 => {
+                                    let l12 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l13 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                }
+                                2 => {
+                                    let l14 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l15 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l14, l15, 1);
+                                }
+                                3 => {
+                                    let l16 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l17 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l16, l17, 1);
+                                }
+                                4 => {
+                                    let l18 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l19 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l18, l19, 1);
+                                }
+                                5 => {
+                                    let l20 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l21 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l20, l21, 1);
+                                }
+                                _ => {
+                                    let l22 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l23 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l22, l23, 1);
+                                }
+                            }
                         }
-                        _ => {}
                     }
                 }
                 pub trait Guest {
+                    /// Get information about the source.
+                    ///
+                    /// This function must not send any request nor depend on `initialize`, so
+                    /// that the host can describe the source without calling it otherwise.
+                    fn get_source_info() -> SourceInfo;
                     /// Initialize the extension.
                     ///
                     /// Sources may have initialization logic that needs to be called before
                     /// calling other functions. This may include setting up rate limiters or
                     /// other configuration.
-                    fn initialize() -> Result<(), ()>;
+                    fn initialize() -> Result<(), SourceError>;
                     /// Get a list of manga from the source.
                     ///
                     /// This function should return a list of manga that can be displayed to the
@@ -1394,79 +4815,101 @@ pub mod exports {
                     fn get_manga_list(
                         filters: _rt::Vec<Filter>,
                         page: u32,
-                    ) -> Result<(_rt::Vec<Manga>, bool), ()>;
+                    ) -> Result<(_rt::Vec<Manga>, bool), SourceError>;
                     /// Get details for a specific manga.
                     ///
                     /// This function should return detailed information about a specific manga.
-                    fn get_manga_details(manga_id: _rt::String) -> Result<Manga, ()>;
+                    fn get_manga_details(
+                        manga_id: _rt::String,
+                    ) -> Result<Manga, SourceError>;
                     /// Get a list of chapters for a specific manga.
                     ///
                     /// This function should return a list of chapters for a specific manga.
                     fn get_chapter_list(
                         manga_id: _rt::String,
-                    ) -> Result<_rt::Vec<Chapter>, ()>;
+                    ) -> Result<_rt::Vec<Chapter>, SourceError>;
                     /// Get a list of pages for a specific chapter.
                     ///
                     /// This function should return a list of pages for a specific chapter.
                     fn get_page_list(
                         manga_id: _rt::String,
                         chapter_id: _rt::String,
-                    ) -> Result<_rt::Vec<Page>, ()>;
+                    ) -> Result<_rt::Vec<Page>, SourceError>;
                 }
                 #[doc(hidden)]
-                macro_rules! __export_midoku_bindings_api_0_1_0_cabi {
+                macro_rules! __export_midoku_bindings_api_0_2_0_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name =
-                        "midoku:bindings/api@0.1.0#initialize"] unsafe extern "C" fn
-                        export_initialize() -> i32 { $($path_to_types)*::
-                        _export_initialize_cabi::<$ty > () } #[export_name =
-                        "midoku:bindings/api@0.1.0#get-manga-list"] unsafe extern "C" fn
+                        const _ : () = { #[unsafe (export_name =
+                        "midoku:bindings/api@0.2.0#get-source-info")] unsafe extern "C"
+                        fn export_get_source_info() -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_get_source_info_cabi::<$ty > () } }
+                        #[unsafe (export_name =
+                        "cabi_post_midoku:bindings/api@0.2.0#get-source-info")] unsafe
+                        extern "C" fn _post_return_get_source_info(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_get_source_info::<$ty
+                        > (arg0) } } #[unsafe (export_name =
+                        "midoku:bindings/api@0.2.0#initialize")] unsafe extern "C" fn
+                        export_initialize() -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_initialize_cabi::<$ty > () } } #[unsafe (export_name =
+                        "cabi_post_midoku:bindings/api@0.2.0#initialize")] unsafe extern
+                        "C" fn _post_return_initialize(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_initialize::<$ty > (arg0) } }
+                        #[unsafe (export_name =
+                        "midoku:bindings/api@0.2.0#get-manga-list")] unsafe extern "C" fn
                         export_get_manga_list(arg0 : * mut u8, arg1 : usize, arg2 : i32,)
-                        -> * mut u8 { $($path_to_types)*::
-                        _export_get_manga_list_cabi::<$ty > (arg0, arg1, arg2) }
-                        #[export_name =
-                        "cabi_post_midoku:bindings/api@0.1.0#get-manga-list"] unsafe
+                        -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_get_manga_list_cabi::<$ty > (arg0, arg1, arg2) } }
+                        #[unsafe (export_name =
+                        "cabi_post_midoku:bindings/api@0.2.0#get-manga-list")] unsafe
                         extern "C" fn _post_return_get_manga_list(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_get_manga_list::<$ty > (arg0)
-                        } #[export_name = "midoku:bindings/api@0.1.0#get-manga-details"]
-                        unsafe extern "C" fn export_get_manga_details(arg0 : * mut u8,
-                        arg1 : usize,) -> * mut u8 { $($path_to_types)*::
-                        _export_get_manga_details_cabi::<$ty > (arg0, arg1) }
-                        #[export_name =
-                        "cabi_post_midoku:bindings/api@0.1.0#get-manga-details"] unsafe
+                        unsafe { $($path_to_types)*:: __post_return_get_manga_list::<$ty
+                        > (arg0) } } #[unsafe (export_name =
+                        "midoku:bindings/api@0.2.0#get-manga-details")] unsafe extern "C"
+                        fn export_get_manga_details(arg0 : * mut u8, arg1 : usize,) -> *
+                        mut u8 { unsafe { $($path_to_types)*::
+                        _export_get_manga_details_cabi::<$ty > (arg0, arg1) } } #[unsafe
+                        (export_name =
+                        "cabi_post_midoku:bindings/api@0.2.0#get-manga-details")] unsafe
                         extern "C" fn _post_return_get_manga_details(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_get_manga_details::<$ty >
-                        (arg0) } #[export_name =
-                        "midoku:bindings/api@0.1.0#get-chapter-list"] unsafe extern "C"
-                        fn export_get_chapter_list(arg0 : * mut u8, arg1 : usize,) -> *
-                        mut u8 { $($path_to_types)*:: _export_get_chapter_list_cabi::<$ty
-                        > (arg0, arg1) } #[export_name =
-                        "cabi_post_midoku:bindings/api@0.1.0#get-chapter-list"] unsafe
+                        unsafe { $($path_to_types)*::
+                        __post_return_get_manga_details::<$ty > (arg0) } } #[unsafe
+                        (export_name = "midoku:bindings/api@0.2.0#get-chapter-list")]
+                        unsafe extern "C" fn export_get_chapter_list(arg0 : * mut u8,
+                        arg1 : usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_get_chapter_list_cabi::<$ty > (arg0, arg1) } } #[unsafe
+                        (export_name =
+                        "cabi_post_midoku:bindings/api@0.2.0#get-chapter-list")] unsafe
                         extern "C" fn _post_return_get_chapter_list(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_get_chapter_list::<$ty >
-                        (arg0) } #[export_name =
-                        "midoku:bindings/api@0.1.0#get-page-list"] unsafe extern "C" fn
-                        export_get_page_list(arg0 : * mut u8, arg1 : usize, arg2 : * mut
-                        u8, arg3 : usize,) -> * mut u8 { $($path_to_types)*::
-                        _export_get_page_list_cabi::<$ty > (arg0, arg1, arg2, arg3) }
-                        #[export_name =
-                        "cabi_post_midoku:bindings/api@0.1.0#get-page-list"] unsafe
+                        unsafe { $($path_to_types)*::
+                        __post_return_get_chapter_list::<$ty > (arg0) } } #[unsafe
+                        (export_name = "midoku:bindings/api@0.2.0#get-page-list")] unsafe
+                        extern "C" fn export_get_page_list(arg0 : * mut u8, arg1 : usize,
+                        arg2 : * mut u8, arg3 : usize,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_get_page_list_cabi::<$ty > (arg0,
+                        arg1, arg2, arg3) } } #[unsafe (export_name =
+                        "cabi_post_midoku:bindings/api@0.2.0#get-page-list")] unsafe
                         extern "C" fn _post_return_get_page_list(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_get_page_list::<$ty > (arg0) }
-                        };
+                        unsafe { $($path_to_types)*:: __post_return_get_page_list::<$ty >
+                        (arg0) } } };
                     };
                 }
                 #[doc(hidden)]
-                pub(crate) use __export_midoku_bindings_api_0_1_0_cabi;
-                #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 72]);
+                pub(crate) use __export_midoku_bindings_api_0_2_0_cabi;
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 18 * ::core::mem::size_of::<*const u8>()],
+                );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 72],
+                    [::core::mem::MaybeUninit::uninit(); 18
+                        * ::core::mem::size_of::<*const u8>()],
                 );
             }
         }
         pub mod types {
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod chapter {
                 #[used]
                 #[doc(hidden)]
@@ -1502,15 +4945,91 @@ pub mod exports {
                     }
                 }
                 #[doc(hidden)]
-                macro_rules! __export_midoku_types_chapter_0_1_0_cabi {
+                macro_rules! __export_midoku_types_chapter_0_2_0_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = {};
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_midoku_types_chapter_0_2_0_cabi;
+            }
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+            pub mod error {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                /// Represents an error reported by a source.
+                ///
+                /// Each case carries a human readable message describing the error.
+                #[derive(Clone)]
+                pub enum SourceError {
+                    /// The source could not be reached (e.g. the site is down).
+                    Network(_rt::String),
+                    /// The response from the source could not be parsed.
+                    Parse(_rt::String),
+                    /// The requested item does not exist (e.g. the manga was removed).
+                    NotFound(_rt::String),
+                    /// The source is rate limiting the requests.
+                    RateLimited(_rt::String),
+                    /// The source requires the user to be logged in.
+                    RequiresLogin(_rt::String),
+                    /// The operation is not supported by the source.
+                    Unsupported(_rt::String),
+                    /// Any other error.
+                    Other(_rt::String),
+                }
+                impl ::core::fmt::Debug for SourceError {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            SourceError::Network(e) => {
+                                f.debug_tuple("SourceError::Network").field(e).finish()
+                            }
+                            SourceError::Parse(e) => {
+                                f.debug_tuple("SourceError::Parse").field(e).finish()
+                            }
+                            SourceError::NotFound(e) => {
+                                f.debug_tuple("SourceError::NotFound").field(e).finish()
+                            }
+                            SourceError::RateLimited(e) => {
+                                f.debug_tuple("SourceError::RateLimited").field(e).finish()
+                            }
+                            SourceError::RequiresLogin(e) => {
+                                f.debug_tuple("SourceError::RequiresLogin")
+                                    .field(e)
+                                    .finish()
+                            }
+                            SourceError::Unsupported(e) => {
+                                f.debug_tuple("SourceError::Unsupported").field(e).finish()
+                            }
+                            SourceError::Other(e) => {
+                                f.debug_tuple("SourceError::Other").field(e).finish()
+                            }
+                        }
+                    }
+                }
+                impl ::core::fmt::Display for SourceError {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        write!(f, "{:?}", self)
+                    }
+                }
+                impl std::error::Error for SourceError {}
+                #[doc(hidden)]
+                macro_rules! __export_midoku_types_error_0_2_0_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = {};
                     };
                 }
                 #[doc(hidden)]
-                pub(crate) use __export_midoku_types_chapter_0_1_0_cabi;
+                pub(crate) use __export_midoku_types_error_0_2_0_cabi;
             }
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod filter {
                 #[used]
                 #[doc(hidden)]
@@ -1572,15 +5091,15 @@ pub mod exports {
                     }
                 }
                 #[doc(hidden)]
-                macro_rules! __export_midoku_types_filter_0_1_0_cabi {
+                macro_rules! __export_midoku_types_filter_0_2_0_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = {};
                     };
                 }
                 #[doc(hidden)]
-                pub(crate) use __export_midoku_types_filter_0_1_0_cabi;
+                pub(crate) use __export_midoku_types_filter_0_2_0_cabi;
             }
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod manga {
                 #[used]
                 #[doc(hidden)]
@@ -1747,15 +5266,15 @@ pub mod exports {
                     }
                 }
                 #[doc(hidden)]
-                macro_rules! __export_midoku_types_manga_0_1_0_cabi {
+                macro_rules! __export_midoku_types_manga_0_2_0_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = {};
                     };
                 }
                 #[doc(hidden)]
-                pub(crate) use __export_midoku_types_manga_0_1_0_cabi;
+                pub(crate) use __export_midoku_types_manga_0_2_0_cabi;
             }
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod page {
                 #[used]
                 #[doc(hidden)]
@@ -1781,19 +5300,73 @@ pub mod exports {
                     }
                 }
                 #[doc(hidden)]
-                macro_rules! __export_midoku_types_page_0_1_0_cabi {
+                macro_rules! __export_midoku_types_page_0_2_0_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = {};
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_midoku_types_page_0_2_0_cabi;
+            }
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+            pub mod source_info {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                /// Describes a source, independently of any request to its website.
+                #[derive(Clone)]
+                pub struct SourceInfo {
+                    pub name: _rt::String,
+                    /// The version of the source, e.g. `1.2.0`.
+                    pub version: _rt::String,
+                    /// The languages of the content, as ISO 639-1 codes (e.g. `en`).
+                    pub languages: _rt::Vec<_rt::String>,
+                    /// The URL of the website the source gets its content from.
+                    pub base_url: _rt::String,
+                    /// If true, the source mostly provides content not safe for work.
+                    pub nsfw: bool,
+                    /// The icon of the source, as a PNG image.
+                    pub icon: Option<_rt::Vec<u8>>,
+                    /// The hosts the source sends requests to, e.g. `example.com` or
+                    /// `*.example-cdn.com` for any of its subdomains.
+                    ///
+                    /// If set, the host denies requests to any other host.
+                    pub allowed_hosts: Option<_rt::Vec<_rt::String>>,
+                }
+                impl ::core::fmt::Debug for SourceInfo {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("SourceInfo")
+                            .field("name", &self.name)
+                            .field("version", &self.version)
+                            .field("languages", &self.languages)
+                            .field("base-url", &self.base_url)
+                            .field("nsfw", &self.nsfw)
+                            .field("icon", &self.icon)
+                            .field("allowed-hosts", &self.allowed_hosts)
+                            .finish()
+                    }
+                }
+                #[doc(hidden)]
+                macro_rules! __export_midoku_types_source_info_0_2_0_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = {};
                     };
                 }
                 #[doc(hidden)]
-                pub(crate) use __export_midoku_types_page_0_1_0_cabi;
+                pub(crate) use __export_midoku_types_source_info_0_2_0_cabi;
             }
         }
     }
 }
 #[rustfmt::skip]
 mod _rt {
+    #![allow(dead_code, clippy::all)]
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
     use core::fmt;
     use core::marker;
     use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
@@ -1868,8 +5441,29 @@ mod _rt {
             }
         }
     }
-    pub use alloc_crate::vec::Vec;
-    pub use alloc_crate::string::String;
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
     pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
         if cfg!(debug_assertions) {
             String::from_utf8(bytes).unwrap()
@@ -1877,6 +5471,24 @@ mod _rt {
             String::from_utf8_unchecked(bytes)
         }
     }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            unsafe { core::hint::unreachable_unchecked() }
+        }
+    }
+    pub unsafe fn bool_lift(val: u8) -> bool {
+        if cfg!(debug_assertions) {
+            match val {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid bool discriminant"),
+            }
+        } else {
+            val != 0
+        }
+    }
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
         if size == 0 {
             return;
@@ -1885,13 +5497,6 @@ mod _rt {
         alloc::dealloc(ptr, layout);
     }
     pub use alloc_crate::alloc;
-    pub unsafe fn invalid_enum_discriminant<T>() -> T {
-        if cfg!(debug_assertions) {
-            panic!("invalid enum discriminant")
-        } else {
-            core::hint::unreachable_unchecked()
-        }
-    }
     pub fn as_i32<T: AsI32>(t: T) -> i32 {
         t.as_i32()
     }
@@ -1951,17 +5556,6 @@ mod _rt {
             self as i32
         }
     }
-    pub unsafe fn bool_lift(val: u8) -> bool {
-        if cfg!(debug_assertions) {
-            match val {
-                0 => false,
-                1 => true,
-                _ => panic!("invalid bool discriminant"),
-            }
-        } else {
-            val != 0
-        }
-    }
     #[cfg(target_arch = "wasm32")]
     pub fn run_ctors_once() {
         wit_bindgen_rt::run_ctors_once();
@@ -1985,8 +5579,8 @@ mod _rt {
     }
     extern crate alloc as alloc_crate;
 }
-/// Generates `#[no_mangle]` functions to export the specified type as the
-/// root implementation of all generated traits.
+/// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
+/// the root implementation of all generated traits.
 ///
 /// For more information see the documentation of `wit_bindgen::generate!`.
 ///
@@ -2009,72 +5603,116 @@ macro_rules! __export_endpoints_impl {
     };
     ($ty:ident with_types_in $($path_to_types_root:tt)*) => {
         $($path_to_types_root)*::
-        exports::midoku::types::chapter::__export_midoku_types_chapter_0_1_0_cabi!($ty
+        exports::midoku::types::chapter::__export_midoku_types_chapter_0_2_0_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::midoku::types::chapter);
         $($path_to_types_root)*::
-        exports::midoku::types::filter::__export_midoku_types_filter_0_1_0_cabi!($ty
+        exports::midoku::types::error::__export_midoku_types_error_0_2_0_cabi!($ty
+        with_types_in $($path_to_types_root)*:: exports::midoku::types::error);
+        $($path_to_types_root)*::
+        exports::midoku::types::filter::__export_midoku_types_filter_0_2_0_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::midoku::types::filter);
         $($path_to_types_root)*::
-        exports::midoku::types::manga::__export_midoku_types_manga_0_1_0_cabi!($ty
+        exports::midoku::types::manga::__export_midoku_types_manga_0_2_0_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::midoku::types::manga);
         $($path_to_types_root)*::
-        exports::midoku::types::page::__export_midoku_types_page_0_1_0_cabi!($ty
+        exports::midoku::types::page::__export_midoku_types_page_0_2_0_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::midoku::types::page);
         $($path_to_types_root)*::
-        exports::midoku::bindings::api::__export_midoku_bindings_api_0_1_0_cabi!($ty
+        exports::midoku::types::source_info::__export_midoku_types_source_info_0_2_0_cabi!($ty
+        with_types_in $($path_to_types_root)*:: exports::midoku::types::source_info);
+        $($path_to_types_root)*::
+        exports::midoku::bindings::api::__export_midoku_bindings_api_0_2_0_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::midoku::bindings::api);
     };
 }
 #[doc(inline)]
 pub(crate) use __export_endpoints_impl as export;
 #[cfg(target_arch = "wasm32")]
-#[link_section = "component-type:wit-bindgen:0.36.0:midoku:example-extension@0.1.0:endpoints:encoded world"]
+#[unsafe(
+    link_section = "component-type:wit-bindgen:0.41.0:midoku:example-extension@0.1.0:endpoints:encoded world"
+)]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1977] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb9\x0e\x01A\x02\x01\
-A\x18\x01B\x0d\x01m\x05\x03get\x04post\x03put\x04head\x06delete\x04\0\x06method\x03\
-\0\0\x04\0\x11incoming-response\x03\x01\x01h\x02\x01@\x01\x04self\x03\0{\x04\0%[\
-method]incoming-response.status-code\x01\x04\x01o\x02ss\x01p\x05\x01@\x01\x04sel\
-f\x03\0\x06\x04\0![method]incoming-response.headers\x01\x07\x01p}\x01@\x01\x04se\
-lf\x03\0\x08\x04\0\x1f[method]incoming-response.bytes\x01\x09\x03\0\x17midoku:ht\
-tp/types@0.1.0\x05\0\x02\x03\0\0\x06method\x02\x03\0\0\x11incoming-response\x01B\
-\x0d\x02\x03\x02\x01\x01\x04\0\x06method\x03\0\0\x02\x03\x02\x01\x02\x04\0\x11in\
-coming-response\x03\0\x02\x01o\x02ss\x01p\x04\x01k\x05\x01p}\x01k\x07\x01i\x03\x01\
-j\x01\x09\0\x01@\x04\x06method\x01\x03urls\x07headers\x06\x04body\x08\0\x0a\x04\0\
-\x06handle\x01\x0b\x03\0\"midoku:http/outgoing-handler@0.1.0\x05\x03\x01B\x0d\x01\
-ky\x01@\0\0\0\x04\0\x05burst\x01\x01\x04\0\x09period-ms\x01\x01\x01j\0\0\x01@\x01\
-\x05bursty\0\x02\x04\0\x09set-burst\x01\x03\x01@\x01\x09period-msy\0\x02\x04\0\x0d\
-set-period-ms\x01\x04\x01@\0\0\x7f\x04\0\x05ready\x01\x05\x01@\0\x01\0\x04\0\x05\
-block\x01\x06\x03\0!midoku:limiter/rate-limiter@0.1.0\x05\x04\x01B\x0a\x01q\x03\x03\
-s64\x01x\0\x03u64\x01w\0\x03f64\x01u\0\x04\0\x06number\x03\0\0\x01ps\x01o\x02ss\x01\
-p\x03\x01q\x05\x04bool\x01\x7f\0\x06number\x01\x01\0\x06string\x01s\0\x05array\x01\
-\x02\0\x03map\x01\x04\0\x04\0\x05value\x03\0\x05\x01j\x01\x06\0\x01@\x01\x03keys\
-\0\x07\x04\0\x03get\x01\x08\x03\0\x1emidoku:settings/settings@0.1.0\x05\x05\x01B\
-\x02\x01r\x08\x02ids\x05titles\x06volumev\x07chapterv\x0cdate-updatedy\x09scanla\
-tors\x03urls\x08languages\x04\0\x07chapter\x03\0\0\x04\0\x1amidoku:types/chapter\
-@0.1.0\x05\x06\x01B\x06\x01r\x01\x05querys\x04\0\x0cfilter-title\x03\0\0\x01r\x02\
-\x0coption-indexy\x0foption-reversed\x7f\x04\0\x0bfilter-sort\x03\0\x02\x01q\x02\
-\x05title\x01\x01\0\x04sort\x01\x03\0\x04\0\x06filter\x03\0\x04\x04\0\x19midoku:\
-types/filter@0.1.0\x05\x07\x01B\x09\x01m\x05\x07unknown\x07ongoing\x09completed\x06\
-hiatus\x09cancelled\x04\0\x06status\x03\0\0\x01m\x03\x04safe\x0asuggestive\x04ns\
-fw\x04\0\x0econtent-rating\x03\0\x02\x01m\x04\x0dright-to-left\x0dleft-to-right\x08\
-vertical\x06scroll\x04\0\x0creading-mode\x03\0\x04\x01ps\x01r\x0b\x02ids\x05titl\
-es\x03urls\x0bdescriptions\x09cover-urls\x0bauthor-names\x0bartist-names\x0acate\
-gories\x06\x06status\x01\x0econtent-rating\x03\x0creading-mode\x05\x04\0\x05mang\
-a\x03\0\x07\x04\0\x18midoku:types/manga@0.1.0\x05\x08\x01B\x03\x01p}\x01r\x03\x05\
-indexy\x03urls\x06base64\0\x04\0\x04page\x03\0\x01\x04\0\x17midoku:types/page@0.\
-1.0\x05\x09\x02\x03\0\x04\x07chapter\x02\x03\0\x05\x06filter\x02\x03\0\x06\x05ma\
-nga\x02\x03\0\x07\x04page\x01B\x1c\x02\x03\x02\x01\x0a\x04\0\x07chapter\x03\0\0\x02\
-\x03\x02\x01\x0b\x04\0\x06filter\x03\0\x02\x02\x03\x02\x01\x0c\x04\0\x05manga\x03\
-\0\x04\x02\x03\x02\x01\x0d\x04\0\x04page\x03\0\x06\x01j\0\0\x01@\0\0\x08\x04\0\x0a\
-initialize\x01\x09\x01p\x03\x01p\x05\x01o\x02\x0b\x7f\x01j\x01\x0c\0\x01@\x02\x07\
-filters\x0a\x04pagey\0\x0d\x04\0\x0eget-manga-list\x01\x0e\x01j\x01\x05\0\x01@\x01\
-\x08manga-ids\0\x0f\x04\0\x11get-manga-details\x01\x10\x01p\x01\x01j\x01\x11\0\x01\
-@\x01\x08manga-ids\0\x12\x04\0\x10get-chapter-list\x01\x13\x01p\x07\x01j\x01\x14\
-\0\x01@\x02\x08manga-ids\x0achapter-ids\0\x15\x04\0\x0dget-page-list\x01\x16\x04\
-\0\x19midoku:bindings/api@0.1.0\x05\x0e\x04\0(midoku:example-extension/endpoints\
-@0.1.0\x04\0\x0b\x0f\x01\0\x09endpoints\x03\0\0\0G\x09producers\x01\x0cprocessed\
--by\x02\x0dwit-component\x070.220.0\x10wit-bindgen-rust\x060.36.0";
+#[allow(clippy::octal_escapes)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 3577] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xf9\x1a\x01A\x02\x01\
+A!\x01B3\x01q\x0a\x03get\0\0\x04post\0\0\x03put\0\0\x04head\0\0\x06delete\0\0\x05\
+patch\0\0\x07options\0\0\x07connect\0\0\x05trace\0\0\x05other\x01s\0\x04\0\x06me\
+thod\x03\0\0\x01q\x0a\x0binvalid-url\x01s\0\x0einvalid-header\x01s\0\x03dns\x01s\
+\0\x07connect\x01s\0\x03tls\x01s\0\x07timeout\0\0\x12too-many-redirects\0\0\x0eb\
+ody-too-large\0\0\x07blocked\x01s\0\x05other\x01s\0\x04\0\x0ahttp-error\x03\0\x02\
+\x01ks\x01p}\x01r\x04\x04names\x08filename\x04\x0ccontent-type\x04\x04data\x05\x04\
+\0\x0emultipart-part\x03\0\x06\x01o\x02ss\x01p\x08\x01p\x07\x01q\x04\x05bytes\x01\
+\x05\0\x04form\x01\x09\0\x04json\x01s\0\x09multipart\x01\x0a\0\x04\0\x0crequest-\
+body\x03\0\x0b\x01q\x03\x06follow\0\0\x04none\0\0\x07limited\x01y\0\x04\0\x0fred\
+irect-policy\x03\0\x0d\x01r\x02\x03urls\x0bstatus-code{\x04\0\x08redirect\x03\0\x0f\
+\x04\0\x0dincoming-body\x03\x01\x04\0\x11incoming-response\x03\x01\x01h\x11\x01j\
+\x01\x05\x01\x03\x01@\x02\x04self\x13\x07max-lenw\0\x14\x04\0\x1a[method]incomin\
+g-body.read\x01\x15\x01j\x01w\x01\x03\x01@\x02\x04self\x13\x03lenw\0\x16\x04\0\x1a\
+[method]incoming-body.skip\x01\x17\x01@\x01\x04self\x13\0\x7f\x04\0#[method]inco\
+ming-body.end-of-stream\x01\x18\x01h\x12\x01@\x01\x04self\x19\0{\x04\0%[method]i\
+ncoming-response.status-code\x01\x1a\x01@\x01\x04self\x19\0s\x04\0\x1d[method]in\
+coming-response.url\x01\x1b\x01p\x10\x01@\x01\x04self\x19\0\x1c\x04\0#[method]in\
+coming-response.redirects\x01\x1d\x01@\x01\x04self\x19\0\x09\x04\0![method]incom\
+ing-response.headers\x01\x1e\x01o\x02s\x05\x01p\x1f\x01@\x01\x04self\x19\0\x20\x04\
+\0%[method]incoming-response.raw-headers\x01!\x01@\x01\x04self\x19\0\x14\x04\0\x1f\
+[method]incoming-response.bytes\x01\"\x01j\x01s\x01\x03\x01@\x02\x04self\x19\x08\
+encoding\x04\0#\x04\0\x1e[method]incoming-response.text\x01$\x01i\x11\x01j\x01%\0\
+\x01@\x01\x04self\x19\0&\x04\0\x1e[method]incoming-response.body\x01'\x03\0\x17m\
+idoku:http/types@0.2.0\x05\0\x02\x03\0\0\x06method\x02\x03\0\0\x0crequest-body\x02\
+\x03\0\0\x0fredirect-policy\x02\x03\0\0\x11incoming-response\x02\x03\0\0\x0ahttp\
+-error\x01B\x17\x02\x03\x02\x01\x01\x04\0\x06method\x03\0\0\x02\x03\x02\x01\x02\x04\
+\0\x0crequest-body\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x0fredirect-policy\x03\0\x04\
+\x02\x03\x02\x01\x04\x04\0\x11incoming-response\x03\0\x06\x02\x03\x02\x01\x05\x04\
+\0\x0ahttp-error\x03\0\x08\x01o\x02ss\x01p\x0a\x01k\x0b\x01p}\x01o\x02s\x0d\x01p\
+\x0e\x01k\x0f\x01k\x03\x01k\x05\x01i\x07\x01j\x01\x13\x01\x09\x01@\x06\x06method\
+\x01\x03urls\x07headers\x0c\x0braw-headers\x10\x04body\x11\x08redirect\x12\0\x14\
+\x04\0\x06handle\x01\x15\x03\0\"midoku:http/outgoing-handler@0.2.0\x05\x06\x01B\x1b\
+\x01ky\x01@\0\0\0\x04\0\x05burst\x01\x01\x04\0\x09period-ms\x01\x01\x01j\0\0\x01\
+@\x01\x05bursty\0\x02\x04\0\x09set-burst\x01\x03\x01@\x01\x09period-msy\0\x02\x04\
+\0\x0dset-period-ms\x01\x04\x01@\0\0\x7f\x04\0\x05ready\x01\x05\x01@\0\x01\0\x04\
+\0\x05block\x01\x06\x01@\x03\x03keys\x05bursty\x09period-msy\0\x02\x04\0\x09set-\
+keyed\x01\x07\x01o\x02yy\x01k\x08\x01@\x01\x03keys\0\x09\x04\0\x05keyed\x01\x0a\x01\
+@\x01\x03keys\0\x7f\x04\0\x0cremove-keyed\x01\x0b\x04\0\x0bready-keyed\x01\x0b\x01\
+@\x01\x03keys\x01\0\x04\0\x0bblock-keyed\x01\x0c\x04\0\x0aauto-block\x01\x05\x01\
+@\x01\x07enabled\x7f\x01\0\x04\0\x0eset-auto-block\x01\x0d\x03\0!midoku:limiter/\
+rate-limiter@0.2.0\x05\x07\x01B\x0a\x01q\x03\x03s64\x01x\0\x03u64\x01w\0\x03f64\x01\
+u\0\x04\0\x06number\x03\0\0\x01ps\x01o\x02ss\x01p\x03\x01q\x05\x04bool\x01\x7f\0\
+\x06number\x01\x01\0\x06string\x01s\0\x05array\x01\x02\0\x03map\x01\x04\0\x04\0\x05\
+value\x03\0\x05\x01j\x01\x06\0\x01@\x01\x03keys\0\x07\x04\0\x03get\x01\x08\x03\0\
+\x1emidoku:settings/settings@0.1.0\x05\x08\x01B\x02\x01r\x08\x02ids\x05titles\x06\
+volumev\x07chapterv\x0cdate-updatedy\x09scanlators\x03urls\x08languages\x04\0\x07\
+chapter\x03\0\0\x04\0\x1amidoku:types/chapter@0.2.0\x05\x09\x01B\x02\x01q\x07\x07\
+network\x01s\0\x05parse\x01s\0\x09not-found\x01s\0\x0crate-limited\x01s\0\x0ereq\
+uires-login\x01s\0\x0bunsupported\x01s\0\x05other\x01s\0\x04\0\x0csource-error\x03\
+\0\0\x04\0\x18midoku:types/error@0.2.0\x05\x0a\x01B\x06\x01r\x01\x05querys\x04\0\
+\x0cfilter-title\x03\0\0\x01r\x02\x0coption-indexy\x0foption-reversed\x7f\x04\0\x0b\
+filter-sort\x03\0\x02\x01q\x02\x05title\x01\x01\0\x04sort\x01\x03\0\x04\0\x06fil\
+ter\x03\0\x04\x04\0\x19midoku:types/filter@0.2.0\x05\x0b\x01B\x09\x01m\x05\x07un\
+known\x07ongoing\x09completed\x06hiatus\x09cancelled\x04\0\x06status\x03\0\0\x01\
+m\x03\x04safe\x0asuggestive\x04nsfw\x04\0\x0econtent-rating\x03\0\x02\x01m\x04\x0d\
+right-to-left\x0dleft-to-right\x08vertical\x06scroll\x04\0\x0creading-mode\x03\0\
+\x04\x01ps\x01r\x0b\x02ids\x05titles\x03urls\x0bdescriptions\x09cover-urls\x0bau\
+thor-names\x0bartist-names\x0acategories\x06\x06status\x01\x0econtent-rating\x03\
+\x0creading-mode\x05\x04\0\x05manga\x03\0\x07\x04\0\x18midoku:types/manga@0.2.0\x05\
+\x0c\x01B\x03\x01p}\x01r\x03\x05indexy\x03urls\x06base64\0\x04\0\x04page\x03\0\x01\
+\x04\0\x17midoku:types/page@0.2.0\x05\x0d\x01B\x06\x01ps\x01p}\x01k\x01\x01k\0\x01\
+r\x07\x04names\x07versions\x09languages\0\x08base-urls\x04nsfw\x7f\x04icon\x02\x0d\
+allowed-hosts\x03\x04\0\x0bsource-info\x03\0\x04\x04\0\x1emidoku:types/source-in\
+fo@0.2.0\x05\x0e\x02\x03\0\x04\x07chapter\x02\x03\0\x05\x0csource-error\x02\x03\0\
+\x06\x06filter\x02\x03\0\x07\x05manga\x02\x03\0\x08\x04page\x02\x03\0\x09\x0bsou\
+rce-info\x01B\"\x02\x03\x02\x01\x0f\x04\0\x07chapter\x03\0\0\x02\x03\x02\x01\x10\
+\x04\0\x0csource-error\x03\0\x02\x02\x03\x02\x01\x11\x04\0\x06filter\x03\0\x04\x02\
+\x03\x02\x01\x12\x04\0\x05manga\x03\0\x06\x02\x03\x02\x01\x13\x04\0\x04page\x03\0\
+\x08\x02\x03\x02\x01\x14\x04\0\x0bsource-info\x03\0\x0a\x01@\0\0\x0b\x04\0\x0fge\
+t-source-info\x01\x0c\x01j\0\x01\x03\x01@\0\0\x0d\x04\0\x0ainitialize\x01\x0e\x01\
+p\x05\x01p\x07\x01o\x02\x10\x7f\x01j\x01\x11\x01\x03\x01@\x02\x07filters\x0f\x04\
+pagey\0\x12\x04\0\x0eget-manga-list\x01\x13\x01j\x01\x07\x01\x03\x01@\x01\x08man\
+ga-ids\0\x14\x04\0\x11get-manga-details\x01\x15\x01p\x01\x01j\x01\x16\x01\x03\x01\
+@\x01\x08manga-ids\0\x17\x04\0\x10get-chapter-list\x01\x18\x01p\x09\x01j\x01\x19\
+\x01\x03\x01@\x02\x08manga-ids\x0achapter-ids\0\x1a\x04\0\x0dget-page-list\x01\x1b\
+\x04\0\x19midoku:bindings/api@0.2.0\x05\x15\x04\0(midoku:example-extension/endpo\
+ints@0.1.0\x04\0\x0b\x0f\x01\0\x09endpoints\x03\0\0\0G\x09producers\x01\x0cproce\
+ssed-by\x02\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -3,9 +3,11 @@ mod bindings;
 
 use bindings::exports::midoku::bindings::api::Guest;
 use bindings::exports::midoku::types::chapter::Chapter;
+use bindings::exports::midoku::types::error::SourceError;
 use bindings::exports::midoku::types::filter::Filter;
 use bindings::exports::midoku::types::manga::{ContentRating, Manga, ReadingMode, Status};
 use bindings::exports::midoku::types::page::Page;
+use bindings::exports::midoku::types::source_info::SourceInfo;
 use bindings::midoku::http::outgoing_handler::{handle, IncomingResponse, Method};
 use bindings::midoku::limiter::rate_limiter::{block, set_burst, set_period_ms};
 
@@ -14,43 +16,62 @@ const URL: &str = "http://example.com";
 struct Component;
 
 impl Guest for Component {
-    fn initialize() -> Result<(), ()> {
+    fn get_source_info() -> SourceInfo {
+        // This is read by the host without calling `initialize`, it must not
+        // send any request.
+        SourceInfo {
+            name: "Example".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            languages: vec!["en".to_string()],
+            base_url: URL.to_string(),
+            nsfw: false,
+            icon: None,
+            allowed_hosts: Some(vec!["example.com".to_string()]),
+        }
+    }
+
+    fn initialize() -> Result<(), SourceError> {
         // The actual rate limiter configuration will depend on the API.
 
         // For example, to configure a rate limiter to allow 3 requests per
         // second:
-        set_burst(3)?;
-        set_period_ms(1000)?;
+        set_burst(3).map_err(|_| SourceError::Other("invalid burst".to_string()))?;
+        set_period_ms(1000).map_err(|_| SourceError::Other("invalid period".to_string()))?;
 
         Ok(())
     }
 
-    fn get_manga_list(_filter: Vec<Filter>, _page: u32) -> Result<(Vec<Manga>, bool), ()> {
+    fn get_manga_list(_filter: Vec<Filter>, _page: u32) -> Result<(Vec<Manga>, bool), SourceError> {
         // Block until the rate limiter allows the request
         block();
 
         // Send a GET request to the API
         let url = format!("{}/manga", URL);
-        let response: IncomingResponse = handle(Method::Get, &url, None, None)?;
+        let response: IncomingResponse = handle(&Method::Get, &url, None, None, None, None)
+            .map_err(|err| SourceError::Network(format!("failed to fetch {url}: {err:?}")))?;
 
         // Get the response bytes
-        let _bytes: Vec<u8> = response.bytes();
+        let _bytes: Vec<u8> = response
+            .bytes()
+            .map_err(|err| SourceError::Network(format!("failed to read {url}: {err:?}")))?;
 
         // Parse the response bytes into a list of manga with serde or similar
         // library. This is just a placeholder to show how to use the response.
-        // The actual implementation will depend on the API.
+        // The actual implementation will depend on the API. Parsing failures
+        // should be reported with `SourceError::Parse`.
 
         // Example result
         Ok((vec![], false))
     }
 
-    fn get_manga_details(_manga_id: String) -> Result<Manga, ()> {
+    fn get_manga_details(_manga_id: String) -> Result<Manga, SourceError> {
         // You might want to block until the rate limiter allows the request
         // block();
 
         // This is where you would send a request to get the manga details,
         // parse the response, and return the details. The actual implementation
-        // will depend on the API.
+        // will depend on the API. If the manga does not exist anymore, return
+        // `SourceError::NotFound` so the host can tell the user.
 
         // Example result
         Ok(Manga {
@@ -68,7 +89,7 @@ impl Guest for Component {
         })
     }
 
-    fn get_chapter_list(_manga_id: String) -> Result<Vec<Chapter>, ()> {
+    fn get_chapter_list(_manga_id: String) -> Result<Vec<Chapter>, SourceError> {
         // You might want to block until the rate limiter allows the request
         // block();
 
@@ -80,7 +101,7 @@ impl Guest for Component {
         Ok(vec![])
     }
 
-    fn get_page_list(_manga_id: String, _chapter_id: String) -> Result<Vec<Page>, ()> {
+    fn get_page_list(_manga_id: String, _chapter_id: String) -> Result<Vec<Page>, SourceError> {
         // You might want to block until the rate limiter allows the request
         // block();
 
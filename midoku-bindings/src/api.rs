@@ -0,0 +1,246 @@
+use std::fmt;
+
+use midoku_types::chapter::Chapter;
+use midoku_types::error::SourceError;
+use midoku_types::filter::Filter;
+use midoku_types::manga::Manga;
+use midoku_types::page::Page;
+use midoku_types::source_info::SourceInfo;
+use wasmtime::component::types::ComponentItem;
+use wasmtime::component::{Component, Instance, TypedFunc};
+use wasmtime::{Engine, Store};
+
+use crate::error::Error;
+use crate::state::State;
+
+/// Prefix of the name of the Midoku API interface exported by sources,
+/// followed by its version.
+const API_PREFIX: &str = "midoku:bindings/api@";
+
+/// A version of the Midoku API supported by the host.
+///
+/// Sources built against any of these versions can be loaded, the host adapts
+/// the older versions to the current types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    /// `midoku:bindings/api@0.1.0`.
+    ///
+    /// Errors carry no information, they are reported as
+    /// [`SourceError::Other`] with an empty message. Getting the source info
+    /// is not supported.
+    V0_1,
+    /// `midoku:bindings/api@0.2.0`, the current version.
+    V0_2,
+}
+
+impl ApiVersion {
+    /// Every supported version, from the oldest to the latest.
+    pub const SUPPORTED: &[Self] = &[Self::V0_1, Self::V0_2];
+
+    /// The latest version of the API.
+    pub const LATEST: Self = Self::V0_2;
+
+    /// Returns the semantic version of the API, e.g. `0.2.0`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V0_1 => "0.1.0",
+            Self::V0_2 => "0.2.0",
+        }
+    }
+
+    /// Parse a semantic version of the API, e.g. `0.2.0`.
+    pub fn from_version(version: &str) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .copied()
+            .find(|supported| supported.as_str() == version)
+    }
+
+    /// Returns the name of the interface exported by sources for this version.
+    pub fn interface(&self) -> String {
+        format!("{API_PREFIX}{}", self.as_str())
+    }
+
+    /// Detect the latest supported version of the API exported by a component.
+    ///
+    /// Returns [`Error::IncompatibleApi`] with the exported versions if none
+    /// of them is supported.
+    pub fn detect(engine: &Engine, component: &Component) -> Result<Self, Error> {
+        let versions: Vec<String> = component
+            .component_type()
+            .exports(engine)
+            .filter(|(_, item)| matches!(item, ComponentItem::ComponentInstance(_)))
+            .filter_map(|(name, _)| name.strip_prefix(API_PREFIX).map(str::to_string))
+            .collect();
+
+        versions
+            .iter()
+            .filter_map(|version| Self::from_version(version))
+            .max()
+            .ok_or(Error::IncompatibleApi(versions))
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[doc(hidden)]
+macro_rules! get_typed_func {
+    ($instance:expr, $store:expr, $api:expr, $name:expr) => {{
+        let index = $instance
+            .get_export(&mut $store, Some(&$api), $name)
+            .ok_or_else(|| Error::Abi(format!("missing export `{}`", $name)))?;
+        $instance
+            .get_typed_func(&mut $store, index)
+            .map_err(|err| Error::Abi(format!("export `{}`: {err}", $name)))
+    }};
+}
+
+/// A function of the Midoku API, returning an error of type `E` on failure.
+pub(crate) type ApiFunc<Params, Return, E> = TypedFunc<Params, (Result<Return, E>,)>;
+
+/// A page of the manga list, and whether there is a next page.
+type MangaList = (Vec<Manga>, bool);
+
+/// The typed functions of a version of the Midoku API.
+///
+/// The only difference between the versions so far is the error type, `()`
+/// for 0.1.0 and [`SourceError`] for 0.2.0.
+pub(crate) struct Funcs<E: 'static> {
+    pub initialize: ApiFunc<(), (), E>,
+    pub get_manga_list: ApiFunc<(Vec<Filter>, u32), MangaList, E>,
+    pub get_manga_details: ApiFunc<(String,), Manga, E>,
+    pub get_chapter_list: ApiFunc<(String,), Vec<Chapter>, E>,
+    pub get_page_list: ApiFunc<(String, String), Vec<Page>, E>,
+}
+
+// Derived `Clone` and `Copy` would require `E: Copy`.
+impl<E> Clone for Funcs<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Funcs<E> {}
+
+/// The typed functions of the Midoku API exported by an instance.
+#[derive(Clone, Copy)]
+pub(crate) enum Api {
+    V0_1(Funcs<()>),
+    V0_2 {
+        get_source_info: TypedFunc<(), (SourceInfo,)>,
+        funcs: Funcs<SourceError>,
+    },
+}
+
+impl Api {
+    /// Get the typed functions of the API from an instance.
+    pub fn new(
+        instance: &Instance,
+        store: &mut Store<State>,
+        version: ApiVersion,
+    ) -> Result<Self, Error> {
+        let name = version.interface();
+        let api = instance
+            .get_export(&mut *store, None, &name)
+            .ok_or_else(|| Error::Abi(format!("missing export `{name}`")))?;
+
+        macro_rules! funcs {
+            () => {
+                Funcs {
+                    initialize: get_typed_func!(instance, *store, api, "initialize")?,
+                    get_manga_list: get_typed_func!(instance, *store, api, "get-manga-list")?,
+                    get_manga_details: get_typed_func!(instance, *store, api, "get-manga-details")?,
+                    get_chapter_list: get_typed_func!(instance, *store, api, "get-chapter-list")?,
+                    get_page_list: get_typed_func!(instance, *store, api, "get-page-list")?,
+                }
+            };
+        }
+
+        match version {
+            ApiVersion::V0_1 => Ok(Self::V0_1(funcs!())),
+            ApiVersion::V0_2 => Ok(Self::V0_2 {
+                get_source_info: get_typed_func!(instance, *store, api, "get-source-info")?,
+                funcs: funcs!(),
+            }),
+        }
+    }
+
+    /// Returns the version of the API.
+    pub fn version(&self) -> ApiVersion {
+        match self {
+            Self::V0_1(_) => ApiVersion::V0_1,
+            Self::V0_2 { .. } => ApiVersion::V0_2,
+        }
+    }
+
+    /// Returns the functions of the 0.1.0 API.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instance exports another version.
+    pub fn v0_1(&self) -> Funcs<()> {
+        match self {
+            Self::V0_1(funcs) => *funcs,
+            _ => unreachable!("the instance does not export the 0.1.0 API"),
+        }
+    }
+
+    /// Returns the functions of the 0.2.0 API.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instance exports another version.
+    pub fn v0_2(&self) -> Funcs<SourceError> {
+        match self {
+            Self::V0_2 { funcs, .. } => *funcs,
+            _ => unreachable!("the instance does not export the 0.2.0 API"),
+        }
+    }
+
+    /// Returns the `get-source-info` function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instance exports the 0.1.0 API, which has no such
+    /// function.
+    pub fn get_source_info(&self) -> TypedFunc<(), (SourceInfo,)> {
+        match self {
+            Self::V0_2 {
+                get_source_info, ..
+            } => *get_source_info,
+            _ => unreachable!("the instance does not export `get-source-info`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_version_from_version() {
+        assert_eq!(ApiVersion::from_version("0.1.0"), Some(ApiVersion::V0_1));
+        assert_eq!(ApiVersion::from_version("0.2.0"), Some(ApiVersion::V0_2));
+        assert_eq!(ApiVersion::from_version("0.3.0"), None);
+    }
+
+    #[test]
+    fn test_api_version_interface() {
+        assert_eq!(ApiVersion::V0_1.interface(), "midoku:bindings/api@0.1.0");
+        assert_eq!(ApiVersion::LATEST.interface(), "midoku:bindings/api@0.2.0");
+    }
+
+    #[test]
+    fn test_api_version_detect_missing() {
+        let engine = Engine::default();
+        let component = Component::new(&engine, "(component)").unwrap();
+
+        let err = ApiVersion::detect(&engine, &component).unwrap_err();
+        assert!(matches!(err, Error::IncompatibleApi(versions) if versions.is_empty()));
+    }
+}
@@ -1,49 +1,132 @@
 use std::collections::HashMap;
+use std::fs::File;
+use std::future::{pending, Future};
+use std::io::{BufWriter, Read, Write};
+use std::num::NonZeroUsize;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use midoku_http::cookie::CookieJar;
+use midoku_http::transport::Request;
+use midoku_http::types::{Method, RedirectPolicy};
 use midoku_settings::types::Value;
 use midoku_types::chapter::Chapter;
+use midoku_types::error::SourceError;
 use midoku_types::filter::Filter;
 use midoku_types::manga::Manga;
 use midoku_types::page::Page;
-use tokio::sync::{RwLock, RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard};
-use wasmtime::component::{Component, Linker, TypedFunc};
-use wasmtime::{Config, Engine, Store};
+use midoku_types::source_info::SourceInfo;
+use tokio::select;
+use tokio::sync::{oneshot, RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard};
+use tokio::task::spawn;
+use tokio::time::{sleep_until, Instant};
+use wasmtime::component::{
+    Component, ComponentNamedList, ComponentType, InstancePre, Lift, Linker, Lower, TypedFunc,
+};
 
+use crate::api::{Api, ApiFunc, ApiVersion};
+use crate::builder::BindingsBuilder;
+use crate::error::Error;
 use crate::func::FuncExt;
-use crate::instance_impl::midoku_http::map_midoku_http;
-use crate::instance_impl::midoku_limiter::map_midoku_limiter;
-use crate::instance_impl::midoku_settings::map_midoku_settings;
-use crate::state::State;
+use crate::instance::Instance;
+use crate::instance_impl::midoku_http::send_request;
+use crate::limits::Limits;
+use crate::pool::Pool;
+use crate::runtime::Runtime;
+use crate::state::{Shared, State};
 
 /// Bindings to a Midoku source.
 ///
 /// This struct contains the bindings to a Midoku source. It is used to call
 /// functions in the WebAssembly component.
+///
+/// Errors reported by the source are returned as [`Error::Guest`] with the
+/// [`SourceError`] describing them.
+///
+/// # Cancellation
+///
+/// Dropping the future of a call interrupts the guest and releases the
+/// instance right away, a call can also be given a deadline with
+/// [`Bindings::set_timeout`]. An interrupted instance, like one that trapped,
+/// is replaced by a fresh instance of the same component, initialized again
+/// if the bindings were. The settings and the rate limiter are kept, but
+/// anything else stored in the memory of the guest is lost.
+///
+/// # Concurrency
+///
+/// Calls into a single instance are serialized. Bindings created with
+/// [`Runtime::instantiate_pool`] hold several instances of the source, which
+/// share the same settings and rate limiter, and dispatch each call to a free
+/// instance.
+///
+/// # Versions
+///
+/// Sources built against any of the [supported versions](ApiVersion) of the
+/// Midoku API can be loaded. Older versions are adapted to the current types,
+/// at the cost of some features, e.g. detailed errors.
 pub struct Bindings {
-    store: Arc<RwLock<Store<State>>>,
-    initialize: TypedFunc<(), (Result<(), ()>,)>,
-    get_manga_list: TypedFunc<(Vec<Filter>, u32), (Result<(Vec<Manga>, bool), ()>,)>,
-    get_manga_details: TypedFunc<(String,), (Result<Manga, ()>,)>,
-    get_chapter_list: TypedFunc<(String,), (Result<Vec<Chapter>, ()>,)>,
-    get_page_list: TypedFunc<(String, String), (Result<Vec<Page>, ()>,)>,
+    api_version: ApiVersion,
+    pool: Arc<Pool>,
+    /// Whether the source was initialized, so that recycled instances are
+    /// initialized again.
+    initialized: Arc<AtomicBool>,
+    instance_pre: InstancePre<State>,
+    shared: Shared,
+    timeout: Option<Duration>,
 }
 
+/// Call a function of the Midoku API, adapting its result to the version
+/// exported by the source.
 #[doc(hidden)]
-macro_rules! get_typed_func {
-    ($instance:expr, $store:expr, $api:expr, $name:expr) => {{
-        let index = $instance
-            .get_export(&mut $store, Some(&$api), $name)
-            .unwrap();
-        $instance.get_typed_func(&mut $store, index)
-    }};
+macro_rules! call_api {
+    ($bindings:expr, $index:expr, $name:ident, $params:expr) => {
+        match $bindings.api_version {
+            ApiVersion::V0_1 => $bindings
+                .call(
+                    $index,
+                    |api| api.v0_1().$name,
+                    $params,
+                    CallLimits::Enforced,
+                )
+                .await?
+                .map_err(guest_error_v0_1),
+            ApiVersion::V0_2 => $bindings
+                .call(
+                    $index,
+                    |api| api.v0_2().$name,
+                    $params,
+                    CallLimits::Enforced,
+                )
+                .await?
+                .map_err(Error::Guest),
+        }
+    };
+}
+
+/// Whether a call is bound by the fuel limit and the timeout of the bindings.
+#[derive(Clone, Copy, PartialEq)]
+enum CallLimits {
+    Enforced,
+    /// Used for the calls made by the host on its own, e.g. while
+    /// instantiating, which must not fail because of tight limits.
+    Lifted,
+}
+
+/// Convert the error of a 0.1.0 source, which carries no details.
+pub(crate) fn guest_error_v0_1((): ()) -> Error {
+    Error::Guest(SourceError::Other(String::new()))
 }
 
 impl Bindings {
     /// Create a new instance of the bindings from a WebAssembly component
     /// .wasm file.
     ///
+    /// This creates a new [`Runtime`] for the source. When loading several
+    /// sources, prefer sharing a single runtime and using
+    /// [`Runtime::instantiate_file`] instead.
+    ///
     /// # Example
     /// ```ignore
     /// let bindings = Bindings::from_file("example.wasm").await?;
@@ -55,50 +138,281 @@ impl Bindings {
     /// // Call the wasm `get_manga_list` function to get the list of manga.
     /// let (manga_list, has_next) = bindings.get_manga_list(0).await?;
     /// ```
-    pub async fn from_file<T: AsRef<Path>>(path: T) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut config = Config::default();
-        config.async_support(true);
-
-        let engine = Engine::new(&config).unwrap();
-
-        let mut store = Store::new(&engine, State::default());
+    pub async fn from_file<T: AsRef<Path>>(path: T) -> Result<Self, Error> {
+        Runtime::new()?.instantiate_file(path).await
+    }
 
-        let component = Component::from_file(&engine, path.as_ref())?;
+    /// Create a new instance of the bindings from the bytes of a WebAssembly
+    /// component.
+    ///
+    /// Like [`Bindings::from_file`], this creates a new [`Runtime`] for the
+    /// source.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let bytes = download_extension(url).await?;
+    /// let bindings = Bindings::from_bytes(&bytes).await?;
+    /// ```
+    pub async fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let runtime = Runtime::new()?;
+        let component = runtime.compile(bytes)?;
+        runtime.instantiate(&component).await
+    }
 
-        let mut linker: Linker<State> = Linker::new(&engine);
-        map_midoku_http(&mut linker)?;
-        map_midoku_limiter(&mut linker)?;
-        map_midoku_settings(&mut linker)?;
+    /// Create a new instance of the bindings from a WebAssembly component
+    /// read from `reader`.
+    ///
+    /// Like [`Bindings::from_file`], this creates a new [`Runtime`] for the
+    /// source.
+    pub async fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        let runtime = Runtime::new()?;
+        let component = runtime.compile_reader(reader)?;
+        runtime.instantiate(&component).await
+    }
 
-        let instance = linker.instantiate_async(&mut store, &component).await?;
+    /// Create a new instance of the bindings from a component precompiled
+    /// with [`Runtime::precompile`], e.g. the contents of a `.cwasm` file.
+    ///
+    /// Like [`Bindings::from_file`], this creates a new [`Runtime`] for the
+    /// source.
+    ///
+    /// # Safety
+    ///
+    /// The bytes are loaded as executable code without being validated, see
+    /// [`Runtime::deserialize`].
+    pub async unsafe fn from_precompiled(bytes: &[u8]) -> Result<Self, Error> {
+        let runtime = Runtime::new()?;
+        // SAFETY: upheld by the caller.
+        let component = unsafe { runtime.deserialize(bytes)? };
+        runtime.instantiate(&component).await
+    }
 
-        let api = instance
-            .get_export(&mut store, None, "midoku:bindings/api@0.1.0")
-            .ok_or("export not found")?;
+    /// Instantiate a compiled component `size` times with the given linker.
+    ///
+    /// Every instance shares the settings and rate limiter of `shared`. The
+    /// memory and table limits also apply to the instantiation itself.
+    pub(crate) async fn instantiate(
+        linker: &Linker<State>,
+        component: &Component,
+        shared: Shared,
+        limits: Limits,
+        size: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        let api_version = ApiVersion::detect(linker.engine(), component)?;
+        let instance_pre = linker
+            .instantiate_pre(component)
+            .map_err(Error::Instantiation)?;
 
-        let initialize = get_typed_func!(instance, store, api, "initialize")?;
-        let get_manga_list = get_typed_func!(instance, store, api, "get-manga-list")?;
-        let get_manga_details = get_typed_func!(instance, store, api, "get-manga-details")?;
-        let get_chapter_list = get_typed_func!(instance, store, api, "get-chapter-list")?;
-        let get_page_list = get_typed_func!(instance, store, api, "get-page-list")?;
+        let mut instances = Vec::with_capacity(size.get());
+        for _ in 0..size.get() {
+            let state = State::new(shared.clone(), limits.clone());
+            instances.push(Instance::new(&instance_pre, api_version, state).await?);
+        }
 
         Ok(Self {
-            store: Arc::new(RwLock::new(store)),
-            initialize,
-            get_manga_list,
-            get_manga_details,
-            get_chapter_list,
-            get_page_list,
+            api_version,
+            pool: Arc::new(Pool::new(instances)),
+            initialized: Arc::default(),
+            instance_pre,
+            shared,
+            timeout: None,
         })
     }
 
+    /// Create a builder to configure the bindings before instantiating them.
+    pub fn builder() -> BindingsBuilder {
+        BindingsBuilder::new()
+    }
+
+    /// Get the version of the Midoku API exported by the source.
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    pub(crate) fn shared(&self) -> &Shared {
+        &self.shared
+    }
+
+    /// Get the number of instances of the source.
+    pub fn pool_size(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Get the timeout applied to each call, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set the timeout applied to each call.
+    ///
+    /// The timeout includes the time spent waiting for a free instance. A call
+    /// that does not complete in time returns [`Error::Timeout`].
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Call a function of the Midoku API.
+    ///
+    /// The call goes to the instance at `index`, or to any free instance if
+    /// `index` is `None`. Unless `limits` are lifted, the call is bound by the
+    /// fuel limit and the timeout of the bindings.
+    ///
+    /// The call runs in its own task, holding the instance for its whole
+    /// duration, so that an interrupted call never leaves the instance
+    /// half-used. The task is spawned right away, before the returned future
+    /// is polled. If the future is dropped, the task interrupts the guest and
+    /// recycles the instance.
+    fn call<Params, Return>(
+        &self,
+        index: Option<usize>,
+        func: fn(&Api) -> TypedFunc<Params, (Return,)>,
+        params: Params,
+        limits: CallLimits,
+    ) -> impl Future<Output = Result<Return, Error>>
+    where
+        Params: Send + Sync + 'static,
+        Return: Send + Sync + 'static,
+        Params: ComponentNamedList + Lower,
+        (Return,): ComponentNamedList + Lift,
+    {
+        let pool = self.pool.clone();
+        let initialized = self.initialized.clone();
+        let instance_pre = self.instance_pre.clone();
+        let deadline = self
+            .timeout
+            .filter(|_| limits == CallLimits::Enforced)
+            .map(|timeout| Instant::now() + timeout);
+
+        let (tx, rx) = oneshot::channel();
+        // Never sent to, the receiver completes when the sender is dropped
+        // along with the future of the caller.
+        let (cancel_tx, mut cancel_rx) = oneshot::channel::<()>();
+
+        spawn(async move {
+            // Acquire an instance for the whole call.
+            // It prevents multiple calls to the same instance, thus preventing
+            // non-cleanups of resources.
+            let acquire = async {
+                match index {
+                    Some(index) => pool.acquire_at(index).await,
+                    None => pool.acquire().await,
+                }
+            };
+            let mut instance = select! {
+                instance = acquire => instance,
+                _ = &mut cancel_rx => return,
+                _ = sleep_until_deadline(deadline) => {
+                    _ = tx.send(Err(Error::Timeout));
+                    return;
+                }
+            };
+
+            let result = {
+                let Instance { store, api } = &mut *instance;
+                store
+                    .data_mut()
+                    .set_deadline(deadline.map(Instant::into_std));
+
+                let fuel = match limits {
+                    CallLimits::Enforced => store.data().limits().fuel,
+                    CallLimits::Lifted => None,
+                };
+
+                let func = func(api);
+                select! {
+                    result = func.execute(store, params, fuel) => result.map_err(Error::from_call),
+                    _ = &mut cancel_rx => Err(Error::Cancelled),
+                    _ = sleep_until_deadline(deadline) => Err(Error::Timeout),
+                }
+            };
+
+            // The instance cannot be entered anymore after a trap or an
+            // interrupted call. Should recycling fail, the next call traps
+            // and tries again.
+            if result.is_err() {
+                let initialize = initialized.load(Ordering::Relaxed);
+                _ = instance.recycle(&instance_pre, initialize).await;
+            }
+
+            _ = tx.send(result);
+        });
+
+        async move {
+            let result = rx.await.map_err(|_| Error::Cancelled);
+            drop(cancel_tx);
+
+            result?
+        }
+    }
+
+    /// Get information about the source.
+    ///
+    /// The source does not need to be initialized first, nor does it send any
+    /// request. Returns [`Error::Unsupported`] if the source was built against
+    /// the 0.1.0 API, which has no `get-source-info` function.
+    pub async fn source_info(&self) -> Result<SourceInfo, Error> {
+        self.call_source_info(CallLimits::Enforced).await
+    }
+
+    /// Get the hosts the source declared it sends requests to, see
+    /// [`SourceInfo::allowed_hosts`].
+    ///
+    /// Unlike [`Bindings::source_info`], the call is not bound by the fuel
+    /// limit nor the timeout, as it is made while instantiating the source.
+    pub(crate) async fn declared_hosts(&self) -> Result<Option<Vec<String>>, Error> {
+        let source_info = self.call_source_info(CallLimits::Lifted).await?;
+        Ok(source_info.allowed_hosts)
+    }
+
+    async fn call_source_info(&self, limits: CallLimits) -> Result<SourceInfo, Error> {
+        if self.api_version == ApiVersion::V0_1 {
+            return Err(Error::Unsupported("get-source-info"));
+        }
+
+        self.call(None, |api| api.get_source_info(), (), limits)
+            .await
+    }
+
     /// Initialize the bindings instance.
     ///
     /// Sources may have initialization logic that needs to be called before
     /// calling other functions. This may include setting up rate limiters or
     /// other configuration.
-    pub async fn initialize(&self) -> Result<(), ()> {
-        self.initialize.execute(self.store.clone(), ()).await?
+    ///
+    /// Every instance of the pool is initialized.
+    pub async fn initialize(&self) -> Result<(), Error> {
+        match self.api_version {
+            ApiVersion::V0_1 => {
+                self.initialize_all(|api| api.v0_1().initialize, guest_error_v0_1)
+                    .await
+            }
+            ApiVersion::V0_2 => {
+                self.initialize_all(|api| api.v0_2().initialize, Error::Guest)
+                    .await
+            }
+        }
+    }
+
+    /// Initialize every instance of the pool concurrently.
+    async fn initialize_all<E>(
+        &self,
+        func: fn(&Api) -> ApiFunc<(), (), E>,
+        map_err: fn(E) -> Error,
+    ) -> Result<(), Error>
+    where
+        E: ComponentType + Lift + Send + Sync + 'static,
+    {
+        // Every call is spawned before waiting for any of them.
+        let calls: Vec<_> = (0..self.pool.len())
+            .map(|index| self.call(Some(index), func, (), CallLimits::Enforced))
+            .collect();
+
+        for call in calls {
+            call.await?.map_err(map_err)?;
+        }
+        self.initialized.store(true, Ordering::Relaxed);
+
+        Ok(())
     }
 
     /// Get a list of manga from the source.
@@ -111,10 +425,8 @@ impl Bindings {
         &self,
         filters: Vec<Filter>,
         page: u32,
-    ) -> Result<(Vec<Manga>, bool), ()> {
-        self.get_manga_list
-            .execute(self.store.clone(), (filters, page))
-            .await?
+    ) -> Result<(Vec<Manga>, bool), Error> {
+        call_api!(self, None, get_manga_list, (filters, page))
     }
 
     /// Get details for a specific manga.
@@ -122,10 +434,8 @@ impl Bindings {
     /// # Arguments
     ///
     /// * `id` - The ID of the manga to get details for.
-    pub async fn get_manga_details(&self, id: String) -> Result<Manga, ()> {
-        self.get_manga_details
-            .execute(self.store.clone(), (id,))
-            .await?
+    pub async fn get_manga_details(&self, id: String) -> Result<Manga, Error> {
+        call_api!(self, None, get_manga_details, (id,))
     }
 
     /// Get a list of chapters for a specific manga.
@@ -133,10 +443,8 @@ impl Bindings {
     /// # Arguments
     ///
     /// * `id` - The ID of the manga to get chapters for.
-    pub async fn get_chapter_list(&self, id: String) -> Result<Vec<Chapter>, ()> {
-        self.get_chapter_list
-            .execute(self.store.clone(), (id,))
-            .await?
+    pub async fn get_chapter_list(&self, id: String) -> Result<Vec<Chapter>, Error> {
+        call_api!(self, None, get_chapter_list, (id,))
     }
 
     /// Get a list of pages for a specific chapter.
@@ -145,15 +453,62 @@ impl Bindings {
     ///
     /// * `id` - The ID of the manga.
     /// * `chapter_id` - The ID of the chapter.
-    pub async fn get_page_list(&self, id: String, chapter_id: String) -> Result<Vec<Page>, ()> {
-        self.get_page_list
-            .execute(self.store.clone(), (id, chapter_id))
-            .await?
+    pub async fn get_page_list(&self, id: String, chapter_id: String) -> Result<Vec<Page>, Error> {
+        call_api!(self, None, get_page_list, (id, chapter_id))
+    }
+
+    /// Download the resource at `url`, e.g. the image of a page, and stream it
+    /// to `writer` without holding it in memory.
+    ///
+    /// The request is sent like the requests of the source, with its
+    /// transport, cookies and network policy. Returns the number of bytes
+    /// written.
+    pub async fn download<W: Write>(&self, url: &str, writer: &mut W) -> Result<u64, Error> {
+        let request = Request {
+            method: Method::Get,
+            url: url.to_string(),
+            headers: Vec::new(),
+            raw_headers: Vec::new(),
+            body: None,
+        };
+
+        let mut response = send_request(&self.shared, request, RedirectPolicy::Follow)
+            .await
+            .map_err(|err| Error::Http(format!("request to {url} failed: {err}")))?;
+
+        let status_code = response.status_code();
+        if !(200..300).contains(&status_code) {
+            return Err(Error::Http(format!(
+                "request to {url} returned status {status_code}"
+            )));
+        }
+
+        match response.take_body() {
+            Some(mut body) => Ok(body.write_to(writer).await?),
+            None => Ok(0),
+        }
+    }
+
+    /// Download the resource at `url` to a file, see [`Bindings::download`].
+    ///
+    /// The file is created, or truncated if it exists.
+    pub async fn download_to_file<T: AsRef<Path>>(&self, url: &str, path: T) -> Result<u64, Error> {
+        let mut file = BufWriter::new(File::create(path)?);
+        self.download(url, &mut file).await
+    }
+
+    /// Get the cookie jar of the source.
+    ///
+    /// The cookies set by the responses to the source are stored in the jar,
+    /// and sent with its next requests. The jar can be inspected, seeded,
+    /// cleared and saved to disk.
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        self.shared.cookie_jar()
     }
 
     /// Get a reference to the settings
     pub async fn settings(&self) -> RwLockReadGuard<'_, HashMap<String, Value>> {
-        RwLockReadGuard::map(self.store.read().await, |store| store.data().settings())
+        self.shared.settings().await
     }
 
     /// Get a mutable reference to the settings.
@@ -169,8 +524,14 @@ impl Bindings {
     /// );
     /// ```
     pub async fn settings_mut(&mut self) -> RwLockMappedWriteGuard<'_, HashMap<String, Value>> {
-        RwLockWriteGuard::map(self.store.write().await, |store| {
-            store.data_mut().settings_mut()
-        })
+        RwLockWriteGuard::map(self.shared.settings_mut().await, |settings| settings)
+    }
+}
+
+/// Wait until the deadline, or forever if there is none.
+async fn sleep_until_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => sleep_until(deadline).await,
+        None => pending().await,
     }
 }
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use midoku_http::cache::{CachingTransport, HttpCache};
+use midoku_http::cookie::CookieJar;
+use midoku_http::outgoing_handler::Client;
+use midoku_http::policy::NetworkPolicy;
+use midoku_http::retry::RetryPolicy;
+use midoku_http::transport::{HttpTransport, ReqwestTransport};
+use midoku_limiter::rate_limiter::RateLimiter;
+use midoku_settings::types::Value;
+use wasmtime::component::Component;
+
+use crate::bindings::Bindings;
+use crate::error::Error;
+use crate::limits::Limits;
+use crate::runtime::{new_linker, Runtime};
+use crate::state::Shared;
+
+/// Midoku host interfaces exposed to a source.
+///
+/// The imports of an interface that is not exposed are still satisfied, but
+/// calling them traps. This allows loading a source while denying it a
+/// capability, e.g. network access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostInterfaces {
+    /// `midoku:http`, to send HTTP requests.
+    pub http: bool,
+    /// `midoku:limiter`, to configure and wait on the rate limiters.
+    pub limiter: bool,
+    /// `midoku:settings`, to read the settings.
+    pub settings: bool,
+}
+
+impl HostInterfaces {
+    /// Returns `true` if every interface is exposed.
+    pub fn all(&self) -> bool {
+        self.http && self.limiter && self.settings
+    }
+}
+
+impl Default for HostInterfaces {
+    fn default() -> Self {
+        Self {
+            http: true,
+            limiter: true,
+            settings: true,
+        }
+    }
+}
+
+/// Builder to configure [`Bindings`] before instantiating them.
+///
+/// Everything set here is in place before the first call into the source, so
+/// e.g. the settings are visible to the source during
+/// [`Bindings::initialize`].
+///
+/// # Example
+/// ```ignore
+/// let bindings = Bindings::builder()
+///     .with_settings(HashMap::from([(
+///         "user-agent".to_string(),
+///         Value::String("Midoku".to_string()),
+///     )]))
+///     .with_limits(Limits {
+///         memory_size: Some(64 << 20),
+///         ..Default::default()
+///     })
+///     .instantiate(&runtime, &component)
+///     .await?;
+///
+/// bindings.initialize().await?;
+/// ```
+#[derive(Default)]
+pub struct BindingsBuilder {
+    settings: HashMap<String, Value>,
+    rate_limiter: Option<RateLimiter>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    cookie_jar: Option<Arc<CookieJar>>,
+    http_cache: Option<Arc<HttpCache>>,
+    network_policy: NetworkPolicy,
+    retry_policy: RetryPolicy,
+    limits: Option<Limits>,
+    interfaces: HostInterfaces,
+    pool_size: Option<NonZeroUsize>,
+    timeout: Option<Duration>,
+}
+
+impl BindingsBuilder {
+    /// Create a new builder with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the initial settings of the source.
+    pub fn with_settings(mut self, settings: HashMap<String, Value>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Set the rate limiter of the source.
+    ///
+    /// The source can still reconfigure it through `midoku:limiter`.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Set the client used to send the HTTP requests of the source.
+    ///
+    /// Defaults to the client of the runtime, see
+    /// [`Runtime::with_http_client`]. The client must check the addresses it
+    /// connects to itself if the network policy denies private addresses,
+    /// see [`HttpConfig::build_with_policy`](midoku_http::client::HttpConfig::build_with_policy).
+    pub fn with_http_client(mut self, http_client: Client) -> Self {
+        self.transport = Some(Arc::new(ReqwestTransport::new(http_client)));
+        self
+    }
+
+    /// Set the transport used to send the HTTP requests of the source,
+    /// replacing the HTTP client.
+    ///
+    /// This allows running a source offline, e.g. with a
+    /// [`MockTransport`](midoku_http::transport::MockTransport) serving canned
+    /// responses in tests. Like a client set with
+    /// [`BindingsBuilder::with_http_client`], the transport must check the
+    /// addresses it connects to itself.
+    pub fn with_transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Set the cookie jar of the source, e.g. seeded with the cookies of a
+    /// login or loaded from disk with [`CookieJar::load`].
+    ///
+    /// Defaults to an empty jar.
+    pub fn with_cookie_jar(mut self, cookie_jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
+
+    /// Set the cache of the HTTP responses of the source.
+    ///
+    /// Each source should have its own cache, so that its size is limited
+    /// per source. The host keeps the cache to e.g.
+    /// [force a refresh](HttpCache::force_refresh). Defaults to no cache.
+    pub fn with_http_cache(mut self, http_cache: Arc<HttpCache>) -> Self {
+        self.http_cache = Some(http_cache);
+        self
+    }
+
+    /// Set the network access granted to the source, e.g. the hosts it may
+    /// send requests to.
+    ///
+    /// Requests denied by the policy fail with
+    /// [`HttpError::Blocked`](midoku_http::types::HttpError::Blocked) and are
+    /// logged, each redirect being checked before it is followed. The hosts
+    /// declared by the source in its
+    /// [`SourceInfo`](midoku_types::source_info::SourceInfo) restrict the
+    /// policy further. Defaults to [`NetworkPolicy::default`], which allows
+    /// any request.
+    pub fn with_network_policy(mut self, network_policy: NetworkPolicy) -> Self {
+        self.network_policy = network_policy;
+        self
+    }
+
+    /// Set the policy to retry the requests of the source that failed
+    /// transiently, or disable retries with [`RetryPolicy::disabled`].
+    ///
+    /// Retries wait on the rate limiter of the source. Defaults to
+    /// [`RetryPolicy::default`].
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the resource limits of the instances.
+    ///
+    /// Defaults to the limits of the runtime, see [`Runtime::with_limits`].
+    /// A fuel limit requires the runtime to consume fuel, otherwise
+    /// instantiating returns [`Error::Instantiation`].
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Set the host interfaces exposed to the source.
+    pub fn with_interfaces(mut self, interfaces: HostInterfaces) -> Self {
+        self.interfaces = interfaces;
+        self
+    }
+
+    /// Set the number of instances of the source, see
+    /// [`Runtime::instantiate_pool`].
+    pub fn with_pool_size(mut self, pool_size: NonZeroUsize) -> Self {
+        self.pool_size = Some(pool_size);
+        self
+    }
+
+    /// Set the timeout applied to each call, see [`Bindings::set_timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Instantiate a compiled component with this configuration.
+    pub async fn instantiate(
+        self,
+        runtime: &Runtime,
+        component: &Component,
+    ) -> Result<Bindings, Error> {
+        let mut transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let http_client = runtime.http_client_for(&self.network_policy)?;
+                Arc::new(ReqwestTransport::new(http_client.clone()))
+            }
+        };
+        if let Some(http_cache) = self.http_cache {
+            transport = Arc::new(CachingTransport::new(transport, http_cache));
+        }
+
+        let shared = Shared::new(
+            self.settings,
+            self.rate_limiter,
+            transport,
+            self.cookie_jar.unwrap_or_default(),
+            self.network_policy,
+            self.retry_policy,
+            runtime.http_config().redirect,
+        );
+        let limits = self.limits.unwrap_or_else(|| runtime.limits().clone());
+        if limits.fuel.is_some() && !runtime.consumes_fuel() {
+            return Err(Error::Instantiation(wasmtime::Error::msg(
+                "fuel limit set on a runtime that does not consume fuel",
+            )));
+        }
+        let pool_size = self.pool_size.unwrap_or(NonZeroUsize::MIN);
+
+        let mut bindings = if self.interfaces.all() {
+            Bindings::instantiate(runtime.linker(), component, shared, limits, pool_size).await?
+        } else {
+            let mut linker = new_linker(runtime.engine(), &self.interfaces)?;
+            linker
+                .define_unknown_imports_as_traps(component)
+                .map_err(Error::Instantiation)?;
+
+            Bindings::instantiate(&linker, component, shared, limits, pool_size).await?
+        };
+        bindings.set_timeout(self.timeout);
+
+        match bindings.declared_hosts().await {
+            Ok(Some(allowed_hosts)) => {
+                let mut network_policy = bindings.shared().network_policy_mut().await;
+                network_policy.restrict_to_hosts(&allowed_hosts);
+            }
+            Ok(None) | Err(Error::Unsupported(_)) => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(bindings)
+    }
+
+    /// Instantiate a WebAssembly component .wasm file with this
+    /// configuration.
+    pub async fn instantiate_file<T: AsRef<Path>>(
+        self,
+        runtime: &Runtime,
+        path: T,
+    ) -> Result<Bindings, Error> {
+        let component = runtime.compile_file(path)?;
+        self.instantiate(runtime, &component).await
+    }
+}
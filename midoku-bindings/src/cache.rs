@@ -0,0 +1,209 @@
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sha2::{Digest, Sha256};
+use wasmtime::component::Component;
+use wasmtime::Engine;
+
+/// File extension used for the serialized components stored in the cache.
+const EXTENSION: &str = "cwasm";
+
+/// Number of entries written so far, to give each write its own temporary
+/// file.
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// An on-disk cache of compiled components.
+///
+/// Compiling a component with Cranelift is by far the most expensive part of
+/// loading a source. The cache stores the serialized result of the
+/// compilation so that subsequent loads only need to deserialize it.
+///
+/// Entries are keyed by a hash of the component bytes and of the engine
+/// configuration, meaning an entry is only ever reused by an engine able to
+/// run it.
+#[derive(Debug, Clone)]
+pub struct ComponentCache {
+    dir: PathBuf,
+}
+
+impl ComponentCache {
+    /// Create a new cache storing its entries in `dir`.
+    ///
+    /// The directory is created when the first entry is written.
+    pub fn new<T: Into<PathBuf>>(dir: T) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory where the cache entries are stored.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the cache entry for the given component bytes.
+    pub fn entry_path(&self, engine: &Engine, bytes: &[u8]) -> PathBuf {
+        let key = cache_key(engine, bytes);
+        self.dir.join(key).with_extension(EXTENSION)
+    }
+
+    /// Get the compiled component from the cache, or compile it and store the
+    /// result in the cache.
+    ///
+    /// Entries that cannot be deserialized (e.g. truncated files) are
+    /// recompiled and overwritten. Failing to write an entry is not an error,
+    /// the component is compiled and returned as if there was no cache.
+    pub fn get_or_compile(
+        &self,
+        engine: &Engine,
+        bytes: &[u8],
+    ) -> Result<Component, wasmtime::Error> {
+        let path = self.entry_path(engine, bytes);
+
+        if path.is_file() {
+            // SAFETY: the entry was written by `Component::serialize` from an
+            // engine with the same compatibility hash as `engine`, which is
+            // part of the cache key.
+            if let Ok(component) = unsafe { Component::deserialize_file(engine, &path) } {
+                return Ok(component);
+            }
+        }
+
+        let component = Component::new(engine, bytes)?;
+        _ = self.write_entry(&path, &component);
+
+        Ok(component)
+    }
+
+    /// Remove every entry from the cache.
+    pub fn clear(&self) -> std::io::Result<()> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION)
+            {
+                std::fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the serialized component to `path`.
+    ///
+    /// The entry is first written to a temporary file and then renamed, so
+    /// that concurrent readers never observe a partially written entry.
+    fn write_entry(&self, path: &Path, component: &Component) -> Result<(), wasmtime::Error> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let bytes = component.serialize()?;
+        let write = WRITES.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = path.with_extension(format!("{}.{write}.tmp", std::process::id()));
+        let result =
+            std::fs::write(&tmp_path, bytes).and_then(|()| std::fs::rename(&tmp_path, path));
+        if let Err(err) = result {
+            _ = std::fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Hasher`] feeding everything it is given to a SHA-256 digest.
+///
+/// This allows hashing the engine compatibility hash, which only implements
+/// [`Hash`], with a hash function that is stable across runs.
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn finish(&self) -> u64 {
+        unreachable!("the digest is read with `Sha256::finalize` instead")
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+/// Compute the cache key of a component for the given engine.
+fn cache_key(engine: &Engine, bytes: &[u8]) -> String {
+    let mut hasher = Sha256Hasher(Sha256::new());
+    engine.precompile_compatibility_hash().hash(&mut hasher);
+    hasher.write(bytes);
+
+    format!("{:x}", hasher.0.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key() {
+        let engine = Engine::default();
+
+        let key = cache_key(&engine, &[1, 2, 3]);
+
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, cache_key(&engine, &[1, 2, 3]));
+        assert_ne!(key, cache_key(&engine, &[1, 2, 4]));
+    }
+
+    #[test]
+    fn test_cache_key_engine_config() {
+        let engine = Engine::default();
+
+        let mut config = wasmtime::Config::default();
+        config.consume_fuel(true);
+        let fuel_engine = Engine::new(&config).unwrap();
+
+        assert_ne!(
+            cache_key(&engine, &[1, 2, 3]),
+            cache_key(&fuel_engine, &[1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_cache_entry_path() {
+        let engine = Engine::default();
+        let cache = ComponentCache::new("cache");
+
+        let path = cache.entry_path(&engine, &[1, 2, 3]);
+
+        assert_eq!(path.parent(), Some(Path::new("cache")));
+        assert_eq!(path.extension().unwrap(), EXTENSION);
+    }
+
+    #[test]
+    fn test_cache_concurrent_writes() {
+        let engine = Engine::default();
+        let dir = std::env::temp_dir().join("midoku-bindings-test-component-cache");
+        _ = std::fs::remove_dir_all(&dir);
+        let cache = ComponentCache::new(&dir);
+
+        // Threads compiling the same component each write their own
+        // temporary file.
+        let bytes = b"(component)";
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| cache.get_or_compile(&engine, bytes).unwrap());
+            }
+        });
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files, vec![cache.entry_path(&engine, bytes)]);
+        assert!(cache.get_or_compile(&engine, bytes).is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
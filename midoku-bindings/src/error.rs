@@ -0,0 +1,212 @@
+use std::fmt;
+
+use midoku_http::types::HttpError;
+use midoku_types::error::SourceError;
+use wasmtime::component::ResourceTableError;
+use wasmtime::{Trap, WasmBacktrace};
+
+use crate::api::ApiVersion;
+use crate::limits::ResourceLimit;
+
+/// Errors returned by the Midoku bindings.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The guest trapped while executing a function.
+    ///
+    /// The trap code and the WebAssembly backtrace, when available, can be
+    /// retrieved with [`Error::trap_code`] and [`Error::backtrace`].
+    Trap(wasmtime::Error),
+    /// A host function imported by the source failed, e.g. because the
+    /// source passed a resource it does not own, or the body of a response
+    /// read by a 0.1.0 source could not be read.
+    ///
+    /// Like a trap, this aborts the call and causes the instance to be
+    /// recycled. The WebAssembly backtrace, when available, can be retrieved
+    /// with [`Error::backtrace`].
+    Host(wasmtime::Error),
+    /// The source returned an error.
+    Guest(SourceError),
+    /// The component could not be compiled, linked or instantiated.
+    Instantiation(wasmtime::Error),
+    /// The component does not implement the Midoku API expected by the host,
+    /// e.g. a missing export or a function with a mismatched signature.
+    Abi(String),
+    /// The component does not export a version of the Midoku API supported by
+    /// the host, see [`ApiVersion`](crate::ApiVersion).
+    ///
+    /// Contains the versions exported by the component, if any.
+    IncompatibleApi(Vec<String>),
+    /// The source does not export an optional function of the Midoku API,
+    /// e.g. `get-source-info` for sources built against the 0.1.0 API.
+    ///
+    /// Contains the name of the function.
+    Unsupported(&'static str),
+    /// The call was cancelled before it could complete.
+    Cancelled,
+    /// The call did not complete before the timeout of the bindings, see
+    /// [`Bindings::set_timeout`](crate::Bindings::set_timeout).
+    Timeout,
+    /// The instance exceeded one of its resource limits.
+    ///
+    /// Like any trap, this causes the instance to be recycled, see
+    /// [`Bindings`](crate::Bindings).
+    ResourceLimit(ResourceLimit),
+    /// An HTTP request sent by the host failed, e.g. when downloading a page.
+    Http(String),
+    /// An I/O error occurred while reading a component or writing a file.
+    Io(std::io::Error),
+}
+
+impl Error {
+    /// Create an error from the error returned by a call into the guest.
+    pub(crate) fn from_call(err: wasmtime::Error) -> Self {
+        if let Some(limit) = err.downcast_ref::<ResourceLimit>() {
+            return Self::ResourceLimit(*limit);
+        }
+        if err.is::<ResourceTableError>() || err.is::<HttpError>() {
+            return Self::Host(err);
+        }
+
+        match err.downcast_ref::<Trap>() {
+            Some(Trap::OutOfFuel) => Self::ResourceLimit(ResourceLimit::Fuel),
+            // Epoch interruptions only ever happen when the deadline of a call
+            // is reached.
+            Some(Trap::Interrupt) => Self::Timeout,
+            _ => Self::Trap(err),
+        }
+    }
+
+    /// Returns the trap code if the guest trapped.
+    pub fn trap_code(&self) -> Option<Trap> {
+        match self {
+            Self::Trap(err) => err.downcast_ref::<Trap>().copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the WebAssembly backtrace if the guest trapped or a host
+    /// function failed, and the backtrace was captured.
+    pub fn backtrace(&self) -> Option<&WasmBacktrace> {
+        match self {
+            Self::Trap(err) | Self::Host(err) => err.downcast_ref::<WasmBacktrace>(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Trap(err) => match self.trap_code() {
+                Some(trap) => write!(f, "guest trapped: {trap}"),
+                None => write!(f, "guest trapped: {}", err.root_cause()),
+            },
+            Self::Host(err) => write!(f, "host function failed: {}", err.root_cause()),
+            Self::Guest(err) => write!(f, "source returned an error: {err}"),
+            Self::Instantiation(err) => write!(f, "failed to instantiate component: {err}"),
+            Self::Abi(message) => write!(f, "ABI mismatch: {message}"),
+            Self::IncompatibleApi(versions) if versions.is_empty() => {
+                write!(f, "component does not export the Midoku API")
+            }
+            Self::IncompatibleApi(versions) => write!(
+                f,
+                "incompatible Midoku API version {}, supported versions are {}",
+                versions.join(", "),
+                ApiVersion::SUPPORTED
+                    .iter()
+                    .map(ApiVersion::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Unsupported(name) => write!(f, "source does not export `{name}`"),
+            Self::Cancelled => write!(f, "call was cancelled"),
+            Self::Timeout => write!(f, "call timed out"),
+            Self::ResourceLimit(limit) => write!(f, "resource limit exceeded: {limit}"),
+            Self::Http(message) => write!(f, "HTTP error: {message}"),
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Trap(err) | Self::Host(err) | Self::Instantiation(err) => Some(err.as_ref()),
+            Self::Guest(err) => Some(err),
+            Self::ResourceLimit(limit) => Some(limit),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_trap_code() {
+        let error = Error::Trap(wasmtime::Error::new(Trap::UnreachableCodeReached));
+        assert_eq!(error.trap_code(), Some(Trap::UnreachableCodeReached));
+
+        let error = Error::Trap(wasmtime::Error::msg("host error"));
+        assert_eq!(error.trap_code(), None);
+
+        let error = Error::Guest(SourceError::Other(String::new()));
+        assert_eq!(error.trap_code(), None);
+    }
+
+    #[test]
+    fn test_error_from_call() {
+        let error = Error::from_call(wasmtime::Error::new(Trap::OutOfFuel));
+        assert!(matches!(error, Error::ResourceLimit(ResourceLimit::Fuel)));
+
+        let error = Error::from_call(wasmtime::Error::new(ResourceLimit::Memory));
+        assert!(matches!(error, Error::ResourceLimit(ResourceLimit::Memory)));
+
+        let error = Error::from_call(wasmtime::Error::new(Trap::Interrupt));
+        assert!(matches!(error, Error::Timeout));
+
+        let error = Error::from_call(wasmtime::Error::new(Trap::UnreachableCodeReached));
+        assert!(matches!(error, Error::Trap(_)));
+
+        let error = Error::from_call(wasmtime::Error::new(ResourceTableError::NotPresent));
+        assert!(matches!(error, Error::Host(_)));
+        assert_eq!(error.trap_code(), None);
+
+        let error = Error::from_call(wasmtime::Error::new(HttpError::BodyTooLarge));
+        assert_eq!(
+            error.to_string(),
+            "host function failed: response body too large"
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            Error::Guest(SourceError::NotFound("manga removed".to_string())).to_string(),
+            "source returned an error: not found: manga removed"
+        );
+        assert_eq!(Error::Cancelled.to_string(), "call was cancelled");
+        assert_eq!(Error::Timeout.to_string(), "call timed out");
+        assert_eq!(
+            Error::IncompatibleApi(vec!["1.0.0".to_string()]).to_string(),
+            "incompatible Midoku API version 1.0.0, supported versions are 0.1.0, 0.2.0"
+        );
+        assert_eq!(
+            Error::Unsupported("get-source-info").to_string(),
+            "source does not export `get-source-info`"
+        );
+        assert_eq!(
+            Error::Abi("missing export `initialize`".to_string()).to_string(),
+            "ABI mismatch: missing export `initialize`"
+        );
+    }
+}
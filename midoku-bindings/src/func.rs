@@ -1,10 +1,8 @@
-use std::sync::Arc;
-
-use tokio::sync::{oneshot, RwLock};
-use tokio::task::spawn;
 use wasmtime::component::{ComponentNamedList, Lift, Lower, TypedFunc};
 use wasmtime::{AsContextMut, Store};
 
+use crate::state::State;
+
 pub(crate) trait FuncExt<Params, Return>
 where
     Params: Send + Sync,
@@ -14,9 +12,20 @@ where
     (Return,): ComponentNamedList + Lift,
 {
     /// Runs [`wasmtime::Func::call_async`] followed by [`wasmtime::Func::post_return_async`].
-    async fn execute<T>(&self, store: Arc<RwLock<Store<T>>>, params: Params) -> Result<Return, ()>
-    where
-        T: Send + Sync + 'static;
+    ///
+    /// The call is given `fuel`, usually the limit of the store, see
+    /// [`Limits::fuel`](crate::limits::Limits::fuel). It is unbounded if
+    /// `None`.
+    ///
+    /// Dropping the returned future interrupts the guest at its next yield
+    /// point. The instance cannot be entered anymore afterwards and has to be
+    /// recycled.
+    async fn execute(
+        &self,
+        store: &mut Store<State>,
+        params: Params,
+        fuel: Option<u64>,
+    ) -> Result<Return, wasmtime::Error>;
 }
 
 impl<Params, Return> FuncExt<Params, Return> for TypedFunc<Params, (Return,)>
@@ -27,35 +36,20 @@ where
     Params: ComponentNamedList + Lower,
     (Return,): ComponentNamedList + Lift,
 {
-    async fn execute<T>(&self, store: Arc<RwLock<Store<T>>>, params: Params) -> Result<Return, ()>
-    where
-        T: Send + Sync + 'static,
-    {
-        let func = self.clone();
-        let (tx, rx) = oneshot::channel();
-
-        spawn(async move {
-            // Acquire the store write lock.
-            // It also serves as a semaphore to prevent multiple calls to the same function,
-            // thus preventing non-cleanups of resources.
-            let mut store = store.write().await;
-
-            let result = func
-                .call_async(store.as_context_mut(), params)
-                .await
-                .map(|(ret,)| ret);
-
-            let result = match result {
-                Ok(ret) => func
-                    .post_return_async(store.as_context_mut())
-                    .await
-                    .map(|_| ret),
-                Err(err) => Err(err),
-            };
-
-            _ = tx.send(result);
-        });
-
-        rx.await.unwrap().map_err(|_| ())
+    async fn execute(
+        &self,
+        store: &mut Store<State>,
+        params: Params,
+        fuel: Option<u64>,
+    ) -> Result<Return, wasmtime::Error> {
+        if store.get_fuel().is_ok() {
+            store.set_fuel(fuel.unwrap_or(u64::MAX))?;
+        }
+        store.set_epoch_deadline(1);
+
+        let (ret,) = self.call_async(store.as_context_mut(), params).await?;
+        self.post_return_async(store.as_context_mut()).await?;
+
+        Ok(ret)
     }
 }
@@ -0,0 +1,97 @@
+use wasmtime::component::InstancePre;
+use wasmtime::{Store, Trap, UpdateDeadline};
+
+use crate::api::{Api, ApiVersion};
+use crate::bindings::guest_error_v0_1;
+use crate::error::Error;
+use crate::func::FuncExt;
+use crate::limits::ResourceLimit;
+use crate::state::State;
+
+/// An instance of a source along with its store.
+pub(crate) struct Instance {
+    pub store: Store<State>,
+    pub api: Api,
+}
+
+impl Instance {
+    /// Instantiate a source exporting the given version of the API with the
+    /// given state.
+    ///
+    /// The memory and table limits of the state also apply to the
+    /// instantiation itself.
+    pub async fn new(
+        instance_pre: &InstancePre<State>,
+        version: ApiVersion,
+        state: State,
+    ) -> Result<Self, Error> {
+        let mut store = Store::new(instance_pre.engine(), state);
+        store.limiter(|state| state.limits_mut());
+
+        // Yield back to the executor on every epoch tick, so that a call can
+        // be cancelled even if the guest never calls an async host function.
+        // The deadline of the call is checked here as well, the timers of the
+        // executor may not fire while the guest keeps it busy.
+        store.epoch_deadline_callback(|store| match store.data().deadline() {
+            Some(deadline) if std::time::Instant::now() >= deadline => Err(Trap::Interrupt.into()),
+            _ => Ok(UpdateDeadline::Yield(1)),
+        });
+        store.set_epoch_deadline(1);
+
+        let instance = instance_pre
+            .instantiate_async(&mut store)
+            .await
+            .map_err(|err| match err.downcast::<ResourceLimit>() {
+                Ok(limit) => Error::ResourceLimit(limit),
+                Err(err) => Error::Instantiation(err),
+            })?;
+
+        let api = Api::new(&instance, &mut store, version)?;
+
+        Ok(Self { store, api })
+    }
+
+    /// Replace the instance with a fresh one, keeping the state of the host.
+    ///
+    /// An instance cannot be entered anymore once a call trapped or was
+    /// interrupted. Recycling it gives back a usable instance, with the same
+    /// settings and rate limiter, but resets the memory of the guest.
+    ///
+    /// If `initialize` is set, the source is initialized again, see
+    /// [`Bindings::initialize`](crate::Bindings::initialize). Should that
+    /// fail, the instance is left as is, so that the next call traps and
+    /// recycles it again.
+    pub async fn recycle(
+        &mut self,
+        instance_pre: &InstancePre<State>,
+        initialize: bool,
+    ) -> Result<(), Error> {
+        let state = self.store.data();
+        let state = State::new(state.shared().clone(), state.limits().clone());
+
+        let mut instance = Self::new(instance_pre, self.api.version(), state).await?;
+        if initialize {
+            instance.initialize().await?;
+        }
+        *self = instance;
+
+        Ok(())
+    }
+
+    /// Call the `initialize` function of the source.
+    async fn initialize(&mut self) -> Result<(), Error> {
+        let fuel = self.store.data().limits().fuel;
+        match self.api.version() {
+            ApiVersion::V0_1 => {
+                let initialize = self.api.v0_1().initialize;
+                let result = initialize.execute(&mut self.store, (), fuel).await;
+                result.map_err(Error::from_call)?.map_err(guest_error_v0_1)
+            }
+            ApiVersion::V0_2 => {
+                let initialize = self.api.v0_2().initialize;
+                let result = initialize.execute(&mut self.store, (), fuel).await;
+                result.map_err(Error::from_call)?.map_err(Error::Guest)
+            }
+        }
+    }
+}
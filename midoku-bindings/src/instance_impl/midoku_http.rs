@@ -1,13 +1,97 @@
-use midoku_http::outgoing_handler::handle;
-use midoku_http::types::{IncomingResponse, Method};
+use std::sync::Arc;
+use std::time::Duration;
+
+use midoku_http::outgoing_handler::Url;
+use midoku_http::redirect::redirect_request;
+use midoku_http::transport::Request;
+use midoku_http::types::{
+    HttpError, IncomingBody, IncomingResponse, Method, MethodV0_1, Redirect, RedirectPolicy,
+    RequestBody,
+};
+use midoku_limiter::rate_limiter::RateLimiter;
 use wasmtime::component::{Linker, LinkerInstance, Resource, ResourceType};
 use wasmtime::StoreContextMut;
 
-use crate::state::State;
+use crate::limits::ResourceLimit;
+use crate::state::{Shared, State};
+
+/// Versions of `midoku:http` implemented by the host.
+///
+/// Sources built against 0.1.0 send requests with a [`MethodV0_1`], a body of
+/// bytes, no raw headers and no [`RedirectPolicy`], and receive errors
+/// without an [`HttpError`], the other functions are the same in every
+/// version.
+const VERSIONS: &[&str] = &["0.1.0", "0.2.0"];
+
+/// Parameters of the `handle` function.
+type HandleParams = (
+    Method,
+    Box<str>,
+    Option<Box<[(String, String)]>>,
+    Option<RawHeaders>,
+    Option<RequestBody>,
+    Option<RedirectPolicy>,
+);
+
+/// Parameters of the `handle` function of `midoku:http@0.1.0`.
+type HandleParamsV0_1 = (
+    MethodV0_1,
+    Box<str>,
+    Option<Box<[(String, String)]>>,
+    Option<Vec<u8>>,
+);
+
+pub fn map_midoku_http(linker: &mut Linker<State>) -> Result<(), wasmtime::Error> {
+    for version in VERSIONS {
+        map_types(linker, version)?;
+    }
+
+    let mut outgoing_handler_instance: LinkerInstance<'_, State> =
+        linker.instance("midoku:http/outgoing-handler@0.1.0")?;
+    outgoing_handler_instance.func_wrap_async(
+        "handle",
+        |store, (method, url, headers, body): HandleParamsV0_1| {
+            Box::new(async move {
+                let body = body.map(RequestBody::Bytes);
+                let params = (method.into(), url, headers, None, body, None);
+                let (response,) = host_handle(store, params).await?;
+                Ok((response.map_err(|_| ()),))
+            })
+        },
+    )?;
+
+    let mut outgoing_handler_instance: LinkerInstance<'_, State> =
+        linker.instance("midoku:http/outgoing-handler@0.2.0")?;
+    outgoing_handler_instance.func_wrap_async("handle", |store, params| {
+        Box::new(async move { host_handle(store, params).await })
+    })?;
 
-pub fn map_midoku_http(linker: &mut Linker<State>) -> Result<(), Box<dyn std::error::Error>> {
+    Ok(())
+}
+
+/// Headers with their values as received, see `raw-headers`.
+type RawHeaders = Vec<(String, Vec<u8>)>;
+
+/// Map the `types` interface of a version of `midoku:http`.
+fn map_types(linker: &mut Linker<State>, version: &str) -> Result<(), wasmtime::Error> {
     let mut types_instance: LinkerInstance<'_, State> =
-        linker.instance("midoku:http/types@0.1.0")?;
+        linker.instance(&format!("midoku:http/types@{version}"))?;
+    types_instance.resource(
+        "incoming-body",
+        ResourceType::host::<IncomingBody>(),
+        HostIncomingBody::destructor,
+    )?;
+    types_instance.func_wrap_async("[method]incoming-body.read", |store, params| {
+        Box::new(async move { HostIncomingBody::read(store, params).await })
+    })?;
+    types_instance.func_wrap_async("[method]incoming-body.skip", |store, params| {
+        Box::new(async move { HostIncomingBody::skip(store, params).await })
+    })?;
+    types_instance.func_wrap(
+        "[method]incoming-body.end-of-stream",
+        HostIncomingBody::end_of_stream,
+    )?;
+
     types_instance.resource(
         "incoming-response",
         ResourceType::host::<IncomingResponse>(),
@@ -17,57 +101,70 @@ pub fn map_midoku_http(linker: &mut Linker<State>) -> Result<(), Box<dyn std::er
         "[method]incoming-response.status-code",
         HostIncomingResponse::status_code,
     )?;
+    types_instance.func_wrap("[method]incoming-response.url", HostIncomingResponse::url)?;
+    types_instance.func_wrap(
+        "[method]incoming-response.redirects",
+        HostIncomingResponse::redirects,
+    )?;
     types_instance.func_wrap(
         "[method]incoming-response.headers",
         HostIncomingResponse::headers,
     )?;
     types_instance.func_wrap(
-        "[method]incoming-response.bytes",
-        HostIncomingResponse::bytes,
+        "[method]incoming-response.raw-headers",
+        HostIncomingResponse::raw_headers,
     )?;
-
-    let mut outgoing_handler_instance: LinkerInstance<'_, State> =
-        linker.instance("midoku:http/outgoing-handler@0.1.0")?;
-    outgoing_handler_instance.func_wrap_async("handle", |store, params| {
-        Box::new(async move { host_handle(store, params).await })
+    if version == "0.1.0" {
+        // The body cannot be read without an error being reported, so a
+        // failure traps instead of returning fewer bytes.
+        types_instance.func_wrap_async("[method]incoming-response.bytes", |store, params| {
+            Box::new(async move {
+                let (bytes,) = HostIncomingResponse::bytes(store, params).await?;
+                Ok((bytes?,))
+            })
+        })?;
+    } else {
+        types_instance.func_wrap_async("[method]incoming-response.bytes", |store, params| {
+            Box::new(async move { HostIncomingResponse::bytes(store, params).await })
+        })?;
+    }
+    types_instance.func_wrap_async("[method]incoming-response.text", |store, params| {
+        Box::new(async move { HostIncomingResponse::text(store, params).await })
     })?;
+    types_instance.func_wrap("[method]incoming-response.body", HostIncomingResponse::body)?;
 
     Ok(())
 }
 
-/// Helper macro to get a resource from the resource table in the store.
+/// Helper macro to get a resource from a resource table in the store.
 #[doc(hidden)]
 macro_rules! resource_table_get {
-    ($store:expr, $resource:expr) => {
-        $store
-            .data()
-            .resource_tables
-            .incoming_response
-            .get(&$resource)
+    ($store:expr, $table:ident, $resource:expr) => {
+        $store.data().resource_tables.$table.get(&$resource)
+    };
+}
+
+/// Helper macro to get a mutable resource from a resource table in the store.
+#[doc(hidden)]
+macro_rules! resource_table_get_mut {
+    ($store:expr, $table:ident, $resource:expr) => {
+        $store.data_mut().resource_tables.$table.get_mut(&$resource)
     };
 }
 
-/// Helper macro to push a resource to the resource table in the store.
+/// Helper macro to push a resource to a resource table in the store.
 #[doc(hidden)]
 macro_rules! resource_table_push {
-    ($store:expr, $resource:expr) => {
-        $store
-            .data_mut()
-            .resource_tables
-            .incoming_response
-            .push($resource)
+    ($store:expr, $table:ident, $resource:expr) => {
+        $store.data_mut().resource_tables.$table.push($resource)
     };
 }
 
-/// Helper macro to delete a resource from the resource table in the store.
+/// Helper macro to delete a resource from a resource table in the store.
 #[doc(hidden)]
 macro_rules! resource_table_delete {
-    ($store:expr, $rep:expr) => {
-        $store
-            .data_mut()
-            .resource_tables
-            .incoming_response
-            .delete($rep)
+    ($store:expr, $table:ident, $rep:expr) => {
+        $store.data_mut().resource_tables.$table.delete($rep)
     };
 }
 
@@ -82,7 +179,7 @@ impl HostIncomingResponse {
         resource_rep: u32,
     ) -> Result<(), wasmtime::Error> {
         let incoming_response: Resource<IncomingResponse> = Resource::new_own(resource_rep);
-        resource_table_delete!(store, incoming_response)?;
+        resource_table_delete!(store, incoming_response, incoming_response)?;
         Ok(())
     }
 
@@ -92,49 +189,679 @@ impl HostIncomingResponse {
         store: StoreContextMut<State>,
         (resource,): (Resource<IncomingResponse>,),
     ) -> Result<(u16,), wasmtime::Error> {
-        let incoming_response = resource_table_get!(store, resource)?;
+        let incoming_response = resource_table_get!(store, incoming_response, resource)?;
         Ok((incoming_response.status_code(),))
     }
 
+    /// Host function implementation for the `url` method of the
+    /// `IncomingResponse` resource.
+    fn url(
+        store: StoreContextMut<State>,
+        (resource,): (Resource<IncomingResponse>,),
+    ) -> Result<(String,), wasmtime::Error> {
+        let incoming_response = resource_table_get!(store, incoming_response, resource)?;
+        // The host sets the URL of every response it sends to the source.
+        let url = incoming_response.url().unwrap_or_default();
+        Ok((url.to_string(),))
+    }
+
+    /// Host function implementation for the `redirects` method of the
+    /// `IncomingResponse` resource.
+    fn redirects(
+        store: StoreContextMut<State>,
+        (resource,): (Resource<IncomingResponse>,),
+    ) -> Result<(Vec<Redirect>,), wasmtime::Error> {
+        let incoming_response = resource_table_get!(store, incoming_response, resource)?;
+        Ok((incoming_response.redirects().to_vec(),))
+    }
+
     /// Host function implementation for the `headers` method of the
     /// `IncomingResponse` resource.
     fn headers(
         store: StoreContextMut<State>,
         (resource,): (Resource<IncomingResponse>,),
     ) -> Result<(Vec<(String, String)>,), wasmtime::Error> {
-        let incoming_response = resource_table_get!(store, resource)?;
+        let incoming_response = resource_table_get!(store, incoming_response, resource)?;
         Ok((incoming_response.headers().clone(),))
     }
 
-    /// Host function implementation for the `bytes` method of the
+    /// Host function implementation for the `raw_headers` method of the
     /// `IncomingResponse` resource.
-    fn bytes(
+    fn raw_headers(
         store: StoreContextMut<State>,
         (resource,): (Resource<IncomingResponse>,),
-    ) -> Result<(Vec<u8>,), wasmtime::Error> {
-        let incoming_response = resource_table_get!(store, resource)?;
-        Ok((incoming_response.bytes().clone(),))
+    ) -> Result<(RawHeaders,), wasmtime::Error> {
+        let incoming_response = resource_table_get!(store, incoming_response, resource)?;
+        Ok((incoming_response.raw_headers().clone(),))
+    }
+
+    /// Host function implementation for the `bytes` method of the
+    /// `IncomingResponse` resource.
+    async fn bytes(
+        mut store: StoreContextMut<'_, State>,
+        (resource,): (Resource<IncomingResponse>,),
+    ) -> Result<(Result<Vec<u8>, HttpError>,), wasmtime::Error> {
+        let incoming_response = resource_table_get_mut!(store, incoming_response, resource)?;
+        let bytes = incoming_response.bytes().await.map(<[u8]>::to_vec);
+        Ok((bytes,))
+    }
+
+    /// Host function implementation for the `text` method of the
+    /// `IncomingResponse` resource.
+    async fn text(
+        mut store: StoreContextMut<'_, State>,
+        (resource, encoding): (Resource<IncomingResponse>, Option<String>),
+    ) -> Result<(Result<String, HttpError>,), wasmtime::Error> {
+        let incoming_response = resource_table_get_mut!(store, incoming_response, resource)?;
+        Ok((incoming_response.text(encoding.as_deref()).await,))
+    }
+
+    /// Host function implementation for the `body` method of the
+    /// `IncomingResponse` resource.
+    fn body(
+        mut store: StoreContextMut<State>,
+        (resource,): (Resource<IncomingResponse>,),
+    ) -> Result<(Result<Resource<IncomingBody>, ()>,), wasmtime::Error> {
+        let incoming_response = resource_table_get_mut!(store, incoming_response, resource)?;
+        let Some(incoming_body) = incoming_response.take_body() else {
+            return Ok((Err(()),));
+        };
+
+        let incoming_body_resource = resource_table_push!(store, incoming_body, incoming_body)?;
+        Ok((Ok(incoming_body_resource),))
+    }
+}
+
+/// Host functions implementation for the `incoming body` resource.
+struct HostIncomingBody;
+
+impl HostIncomingBody {
+    /// Host function implementation for the `destructor` behavior of the
+    /// `IncomingBody` resource.
+    fn destructor(
+        mut store: StoreContextMut<State>,
+        resource_rep: u32,
+    ) -> Result<(), wasmtime::Error> {
+        let incoming_body: Resource<IncomingBody> = Resource::new_own(resource_rep);
+        resource_table_delete!(store, incoming_body, incoming_body)?;
+        Ok(())
+    }
+
+    /// Host function implementation for the `read` method of the
+    /// `IncomingBody` resource.
+    async fn read(
+        mut store: StoreContextMut<'_, State>,
+        (resource, max_len): (Resource<IncomingBody>, u64),
+    ) -> Result<(Result<Vec<u8>, HttpError>,), wasmtime::Error> {
+        let incoming_body = resource_table_get_mut!(store, incoming_body, resource)?;
+        let max_len = usize::try_from(max_len).unwrap_or(usize::MAX);
+        Ok((incoming_body.read(max_len).await,))
+    }
+
+    /// Host function implementation for the `skip` method of the
+    /// `IncomingBody` resource.
+    async fn skip(
+        mut store: StoreContextMut<'_, State>,
+        (resource, len): (Resource<IncomingBody>, u64),
+    ) -> Result<(Result<u64, HttpError>,), wasmtime::Error> {
+        let incoming_body = resource_table_get_mut!(store, incoming_body, resource)?;
+        Ok((incoming_body.skip(len).await,))
+    }
+
+    /// Host function implementation for the `end-of-stream` method of the
+    /// `IncomingBody` resource.
+    fn end_of_stream(
+        store: StoreContextMut<State>,
+        (resource,): (Resource<IncomingBody>,),
+    ) -> Result<(bool,), wasmtime::Error> {
+        let incoming_body = resource_table_get!(store, incoming_body, resource)?;
+        Ok((incoming_body.is_end_of_stream(),))
     }
 }
 
 /// Host function implementation for the `handle` function.
 async fn host_handle(
     mut store: StoreContextMut<'_, State>,
-    (method, url, headers, body): (
-        Method,
-        Box<str>,
-        Option<Box<[(String, String)]>>,
-        Option<Box<[u8]>>,
-    ),
-) -> Result<(Result<Resource<IncomingResponse>, ()>,), wasmtime::Error> {
-    let headers = headers.map(|headers| headers.to_vec());
-    let body = body.map(|body| body.to_vec());
-
-    let incoming_response = handle(method, url.to_string(), headers, body).await;
-    if incoming_response.is_err() {
-        return Ok((Err(()),));
-    }
-
-    let incoming_response_resource = resource_table_push!(store, incoming_response.unwrap())?;
+    (method, url, headers, raw_headers, body, redirect_policy): HandleParams,
+) -> Result<(Result<Resource<IncomingResponse>, HttpError>,), wasmtime::Error> {
+    // Trap instead of sending the request if the guest holds too many
+    // responses already. Bodies taken from responses count as responses.
+    let resource_tables = &store.data().resource_tables;
+    let incoming_responses =
+        resource_tables.incoming_response.len() + resource_tables.incoming_body.len();
+    if let Some(limit) = store.data().limits().incoming_responses {
+        if incoming_responses >= limit {
+            return Err(ResourceLimit::IncomingResponses.into());
+        }
+    }
+
+    let mut request = Request {
+        method,
+        url: url.to_string(),
+        headers: headers.map(|headers| headers.to_vec()).unwrap_or_default(),
+        raw_headers: raw_headers.unwrap_or_default(),
+        body: None,
+    };
+    if let Some(body) = body {
+        request.set_body(body);
+    }
+
+    let shared = store.data().shared().clone();
+    let redirect_policy = redirect_policy.unwrap_or_default();
+    let incoming_response = match send_request(&shared, request, redirect_policy).await {
+        Ok(incoming_response) => incoming_response,
+        Err(err) => return Ok((Err(err),)),
+    };
+    let incoming_response =
+        incoming_response.with_max_body_size(store.data().limits().response_body_size);
+
+    let incoming_response_resource =
+        resource_table_push!(store, incoming_response, incoming_response)?;
     Ok((Ok(incoming_response_resource),))
 }
+
+/// Send a request of a source with its transport and cookie jar, if its
+/// network policy allows it.
+///
+/// Redirects are followed as allowed by `redirect_policy` and by the host,
+/// each of them is sent like a new request of the source.
+pub(crate) async fn send_request(
+    shared: &Shared,
+    mut request: Request,
+    redirect_policy: RedirectPolicy,
+) -> Result<IncomingResponse, HttpError> {
+    let max_redirects = shared.redirect_policy().max_redirects(redirect_policy);
+    let mut redirects = Vec::new();
+    loop {
+        let incoming_response = send_hop(shared, request.clone()).await?;
+
+        let Some(max_redirects) = max_redirects else {
+            return Ok(incoming_response);
+        };
+        let Some(next_request) = redirect_request(&request, &incoming_response)? else {
+            return Ok(incoming_response.with_redirects(redirects));
+        };
+        if redirects.len() >= max_redirects {
+            return Err(HttpError::TooManyRedirects);
+        }
+
+        redirects.push(Redirect {
+            url: request.url,
+            status_code: incoming_response.status_code(),
+        });
+        request = next_request;
+    }
+}
+
+/// Send a single request of a source, without following redirects.
+async fn send_hop(shared: &Shared, mut request: Request) -> Result<IncomingResponse, HttpError> {
+    check_network_policy(shared, &request.method, &request.url).await?;
+
+    if shared.auto_block() {
+        match host_limiter(shared, &request.url) {
+            Some(limiter) => limiter.block().await,
+            None => {
+                if let Some(limiter) = shared.limiter().await {
+                    limiter.block().await;
+                }
+            }
+        }
+    }
+
+    let cookie_jar = shared.cookie_jar();
+    // Send the cookies of the jar, unless the source sets them itself.
+    let url = Url::parse(&request.url).ok();
+    if let Some(url) = &url {
+        let is_cookie = |name: &str| name.eq_ignore_ascii_case("cookie");
+        let has_cookie = request.headers.iter().any(|(name, _)| is_cookie(name))
+            || request.raw_headers.iter().any(|(name, _)| is_cookie(name));
+        if !has_cookie {
+            if let Some(cookie) = cookie_jar.header(url) {
+                request.headers.push(("cookie".to_string(), cookie));
+            }
+        }
+    }
+
+    let method = request.method.clone();
+    let request_url = request.url.clone();
+    let incoming_response = send_with_retry(shared, request).await?;
+
+    // A client given by the host may still follow redirects itself, the
+    // response is withheld from the source if it was redirected to a URL it
+    // may not access.
+    let incoming_response = match incoming_response.url() {
+        Some(final_url) => {
+            if url.as_ref().map(Url::as_str) != Some(final_url) {
+                check_network_policy(shared, &method, final_url).await?;
+            }
+            incoming_response
+        }
+        None => incoming_response.with_url(Some(request_url)),
+    };
+
+    if let Some(url) = &url {
+        cookie_jar.set_cookies(url, incoming_response.headers());
+    }
+
+    Ok(incoming_response)
+}
+
+/// Send a request with the transport of a source, retrying it as allowed by
+/// its retry policy.
+///
+/// Every retry waits on the rate limiter of the source, or on the limiter of
+/// the host of the request if requests block on it automatically. A response
+/// asking to retry later pauses this limiter, so that the other requests
+/// waiting on it wait too.
+async fn send_with_retry(shared: &Shared, request: Request) -> Result<IncomingResponse, HttpError> {
+    let mut attempt = 1;
+    loop {
+        let result = shared.transport().send(request.clone()).await;
+        let Some(delay) = shared
+            .retry_policy()
+            .retry_delay(&request.method, attempt, &result)
+        else {
+            return result;
+        };
+
+        let status = match &result {
+            Ok(response) => format!("status {}", response.status_code()),
+            Err(err) => err.to_string(),
+        };
+        log::debug!(
+            "retrying {} request to {} in {delay:?} after {status}",
+            request.method.as_str(),
+            request.url
+        );
+
+        // The server rejected the request, e.g. with `429 Too Many Requests`.
+        let rejected = result.is_ok();
+        drop(result);
+
+        match host_limiter(shared, &request.url) {
+            Some(limiter) => wait_to_retry(Some(&limiter), delay, rejected).await,
+            None => wait_to_retry(shared.limiter().await.as_deref(), delay, rejected).await,
+        }
+
+        attempt += 1;
+    }
+}
+
+/// Wait `delay` before retrying a request, then wait on `limiter`.
+///
+/// The limiter is paused instead if the server rejected the request, so that
+/// the other requests waiting on it wait too.
+async fn wait_to_retry(limiter: Option<&RateLimiter>, delay: Duration, rejected: bool) {
+    match limiter {
+        Some(limiter) if rejected => limiter.pause(delay),
+        _ => tokio::time::sleep(delay).await,
+    }
+    if let Some(limiter) = limiter {
+        limiter.block().await;
+    }
+}
+
+/// Returns the limiter keyed by the host of `url`, if requests block on a
+/// limiter automatically and the source set one for this host.
+fn host_limiter(shared: &Shared, url: &str) -> Option<Arc<RateLimiter>> {
+    if !shared.auto_block() {
+        return None;
+    }
+
+    let url = Url::parse(url).ok()?;
+    shared.keyed_limiters().get(url.host_str()?)
+}
+
+/// Check that the network policy of a source allows a request to `url`, and
+/// log the request if it does not.
+async fn check_network_policy(
+    shared: &Shared,
+    method: &Method,
+    url: &str,
+) -> Result<(), HttpError> {
+    let result = shared.network_policy().await.check(url);
+    if let Err(HttpError::Blocked(reason)) = &result {
+        log::warn!("blocked {} request to {url}: {reason}", method.as_str());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use midoku_http::client::RedirectPolicy as HostRedirectPolicy;
+    use midoku_http::policy::NetworkPolicy;
+    use midoku_http::retry::RetryPolicy;
+    use midoku_http::transport::MockTransport;
+    use midoku_limiter::rate_limiter::RateLimiter;
+
+    use super::*;
+
+    const URL: &str = "http://example.com";
+
+    fn shared(transport: Arc<MockTransport>, retry_policy: RetryPolicy) -> Shared {
+        Shared::new(
+            HashMap::new(),
+            Some(RateLimiter::default()),
+            transport,
+            Arc::default(),
+            NetworkPolicy::default(),
+            retry_policy,
+            HostRedirectPolicy::default(),
+        )
+    }
+
+    fn request(method: Method) -> Request {
+        Request {
+            method,
+            url: URL.to_string(),
+            headers: vec![],
+            raw_headers: vec![],
+            body: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_request_retry() {
+        let transport = Arc::new(MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(
+                429,
+                vec![("retry-after".to_string(), "0".to_string())],
+                vec![],
+            ),
+        ));
+        let shared = shared(
+            transport.clone(),
+            RetryPolicy {
+                max_attempts: 3,
+                ..Default::default()
+            },
+        );
+
+        let response = send_request(&shared, request(Method::Get), RedirectPolicy::Follow)
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), 429);
+        assert_eq!(transport.requests().len(), 3);
+
+        // Requests that are not idempotent are not retried.
+        let response = send_request(&shared, request(Method::Post), RedirectPolicy::Follow).await;
+        assert!(response.is_err());
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_request_retry_pauses_limiter() {
+        let transport = Arc::new(MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(
+                503,
+                vec![("retry-after".to_string(), "1".to_string())],
+                vec![],
+            ),
+        ));
+        let shared = shared(
+            transport.clone(),
+            RetryPolicy {
+                max_attempts: 2,
+                ..Default::default()
+            },
+        );
+
+        let start_time = Instant::now();
+        let (response, ready) = tokio::join!(
+            send_request(&shared, request(Method::Get), RedirectPolicy::Follow),
+            async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                shared.limiter().await.as_ref().unwrap().ready()
+            }
+        );
+        assert!(response.is_ok());
+        assert!(start_time.elapsed() >= Duration::from_secs(1));
+        assert_eq!(transport.requests().len(), 2);
+
+        // Other requests of the source wait for the server too.
+        assert!(!ready);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_request_retry_does_not_lock_limiter() {
+        let transport = Arc::new(MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(
+                503,
+                vec![("retry-after".to_string(), "10".to_string())],
+                vec![],
+            ),
+        ));
+        let shared = shared(
+            transport,
+            RetryPolicy {
+                max_attempts: 2,
+                ..Default::default()
+            },
+        );
+
+        let start_time = Instant::now();
+        let (_, replaced_after) = tokio::join!(
+            send_request(&shared, request(Method::Get), RedirectPolicy::Follow),
+            async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                *shared.limiter_mut().await = None;
+                start_time.elapsed()
+            }
+        );
+
+        // The limiter is replaced while the request waits to be retried.
+        assert!(replaced_after < Duration::from_secs(1));
+    }
+
+    fn redirect(status_code: u16, location: &str) -> IncomingResponse {
+        let headers = vec![
+            ("location".to_string(), location.to_string()),
+            ("set-cookie".to_string(), "session=1".to_string()),
+        ];
+        IncomingResponse::new(status_code, headers, vec![])
+    }
+
+    #[tokio::test]
+    async fn test_send_request_redirects() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_response(Method::Post, URL, redirect(302, "/manga/one-piece"))
+                .with_response(
+                    Method::Get,
+                    format!("{URL}/manga/one-piece"),
+                    redirect(301, "https://example.org/manga/13"),
+                )
+                .with_response(
+                    Method::Get,
+                    "https://example.org/manga/13",
+                    IncomingResponse::new(200, vec![], b"One Piece".to_vec()),
+                ),
+        );
+        let shared = shared(transport.clone(), RetryPolicy::disabled());
+
+        let mut response = send_request(&shared, request(Method::Post), RedirectPolicy::Follow)
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.url(), Some("https://example.org/manga/13"));
+        assert_eq!(
+            response.redirects(),
+            &[
+                Redirect {
+                    url: URL.to_string(),
+                    status_code: 302,
+                },
+                Redirect {
+                    url: format!("{URL}/manga/one-piece"),
+                    status_code: 301,
+                },
+            ]
+        );
+        assert_eq!(response.bytes().await.unwrap(), b"One Piece");
+
+        // The cookie set by the first redirect is sent to its origin only.
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].headers.is_empty());
+        assert_eq!(
+            requests[1].headers,
+            vec![("cookie".to_string(), "session=1".to_string())]
+        );
+        assert!(requests[2].headers.is_empty());
+
+        let response = send_request(&shared, request(Method::Post), RedirectPolicy::None)
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), 302);
+        assert!(response.redirects().is_empty());
+
+        let response =
+            send_request(&shared, request(Method::Post), RedirectPolicy::Limited(1)).await;
+        assert_eq!(response.unwrap_err(), HttpError::TooManyRedirects);
+    }
+
+    #[tokio::test]
+    async fn test_send_request_host_redirect_policy() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_response(Method::Get, URL, redirect(302, "/manga"))
+                .with_response(Method::Get, format!("{URL}/manga"), redirect(302, "/home"))
+                .with_response(
+                    Method::Get,
+                    format!("{URL}/home"),
+                    IncomingResponse::new(200, vec![], vec![]),
+                ),
+        );
+        let shared = |redirect_policy| {
+            Shared::new(
+                HashMap::new(),
+                None,
+                transport.clone(),
+                Arc::default(),
+                NetworkPolicy::default(),
+                RetryPolicy::disabled(),
+                redirect_policy,
+            )
+        };
+
+        // The host follows fewer redirects than the source asks for.
+        let response = send_request(
+            &shared(HostRedirectPolicy::Limited(1)),
+            request(Method::Get),
+            RedirectPolicy::Follow,
+        )
+        .await;
+        assert_eq!(response.unwrap_err(), HttpError::TooManyRedirects);
+
+        let response = send_request(
+            &shared(HostRedirectPolicy::Limited(2)),
+            request(Method::Get),
+            RedirectPolicy::Follow,
+        )
+        .await;
+        assert_eq!(response.unwrap().status_code(), 200);
+
+        let response = send_request(
+            &shared(HostRedirectPolicy::None),
+            request(Method::Get),
+            RedirectPolicy::Follow,
+        )
+        .await;
+        assert_eq!(response.unwrap().status_code(), 302);
+    }
+
+    #[tokio::test]
+    async fn test_send_request_redirect_blocked() {
+        let transport = Arc::new(MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            redirect(302, "http://example.org/manga"),
+        ));
+        let network_policy = NetworkPolicy {
+            allowed_hosts: Some(vec!["example.com".to_string()]),
+            allow_private_addresses: true,
+        };
+        let shared = Shared::new(
+            HashMap::new(),
+            None,
+            transport.clone(),
+            Arc::default(),
+            network_policy,
+            RetryPolicy::disabled(),
+            HostRedirectPolicy::default(),
+        );
+
+        let response = send_request(&shared, request(Method::Get), RedirectPolicy::Follow).await;
+        assert!(matches!(response, Err(HttpError::Blocked(_))));
+        // The redirect is not sent.
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_request_auto_block() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_response(Method::Get, URL, IncomingResponse::new(200, vec![], vec![]))
+                .with_response(
+                    Method::Get,
+                    "http://cdn.example.com/1.png",
+                    IncomingResponse::new(200, vec![], vec![]),
+                ),
+        );
+        let shared = shared(transport.clone(), RetryPolicy::disabled());
+        shared
+            .keyed_limiters()
+            .insert("example.com", RateLimiter::new(1, 60_000).unwrap());
+        let timeout = Duration::from_secs(1);
+
+        // The source blocks on the limiters itself by default.
+        for _ in 0..2 {
+            let send = send_request(&shared, request(Method::Get), RedirectPolicy::Follow);
+            tokio::time::timeout(timeout, send).await.unwrap().unwrap();
+        }
+
+        shared.set_auto_block(true);
+        let send = send_request(&shared, request(Method::Get), RedirectPolicy::Follow);
+        tokio::time::timeout(timeout, send).await.unwrap().unwrap();
+        // The limiter allows one request per minute.
+        let send = send_request(&shared, request(Method::Get), RedirectPolicy::Follow);
+        assert!(tokio::time::timeout(timeout, send).await.is_err());
+        assert_eq!(transport.requests().len(), 3);
+
+        // The other hosts are limited by the default limiter only.
+        for _ in 0..5 {
+            let mut request = request(Method::Get);
+            request.url = "http://cdn.example.com/1.png".to_string();
+            let send = send_request(&shared, request, RedirectPolicy::Follow);
+            tokio::time::timeout(timeout, send).await.unwrap().unwrap();
+        }
+        assert_eq!(transport.requests().len(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_send_request_retry_disabled() {
+        let transport = Arc::new(MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(503, vec![], vec![]),
+        ));
+        let shared = shared(transport.clone(), RetryPolicy::disabled());
+
+        let response = send_request(&shared, request(Method::Get), RedirectPolicy::Follow).await;
+        assert_eq!(response.unwrap().status_code(), 503);
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -1,83 +1,205 @@
+use std::sync::Arc;
+
+use midoku_limiter::rate_limiter::RateLimiter;
+use tokio::time::Instant;
 use wasmtime::component::{Linker, LinkerInstance};
 use wasmtime::StoreContextMut;
 
 use crate::state::State;
 
-pub fn map_midoku_limiter(linker: &mut Linker<State>) -> Result<(), Box<dyn std::error::Error>> {
+/// Versions of `midoku:limiter` implemented by the host.
+///
+/// Sources built against 0.1.0 only use the default limiter, the functions of
+/// the keyed limiters are added in 0.2.0.
+const VERSIONS: &[&str] = &["0.1.0", "0.2.0"];
+
+pub fn map_midoku_limiter(linker: &mut Linker<State>) -> Result<(), wasmtime::Error> {
+    for version in VERSIONS {
+        map_rate_limiter(linker, version)?;
+    }
+
+    Ok(())
+}
+
+/// Map the `rate-limiter` interface of a version of `midoku:limiter`.
+fn map_rate_limiter(linker: &mut Linker<State>, version: &str) -> Result<(), wasmtime::Error> {
     let mut rate_limiter_instance: LinkerInstance<'_, State> =
-        linker.instance("midoku:limiter/rate-limiter@0.1.0")?;
-    rate_limiter_instance.func_wrap("burst", host_burst)?;
-    rate_limiter_instance.func_wrap("period-ms", host_period_ms)?;
-    rate_limiter_instance.func_wrap("set-burst", host_set_burst)?;
-    rate_limiter_instance.func_wrap("set-period-ms", host_set_period_ms)?;
-    rate_limiter_instance.func_wrap("ready", host_ready)?;
+        linker.instance(&format!("midoku:limiter/rate-limiter@{version}"))?;
+    rate_limiter_instance.func_wrap_async("burst", |store, params| {
+        Box::new(async move { host_burst(store, params).await })
+    })?;
+    rate_limiter_instance.func_wrap_async("period-ms", |store, params| {
+        Box::new(async move { host_period_ms(store, params).await })
+    })?;
+    rate_limiter_instance.func_wrap_async("set-burst", |store, params| {
+        Box::new(async move { host_set_burst(store, params).await })
+    })?;
+    rate_limiter_instance.func_wrap_async("set-period-ms", |store, params| {
+        Box::new(async move { host_set_period_ms(store, params).await })
+    })?;
+    rate_limiter_instance.func_wrap_async("ready", |store, params| {
+        Box::new(async move { host_ready(store, params).await })
+    })?;
     rate_limiter_instance.func_wrap_async("block", |store, params| {
         Box::new(async move { host_block(store, params).await })
     })?;
+    rate_limiter_instance.func_wrap("set-keyed", host_set_keyed)?;
+    rate_limiter_instance.func_wrap("keyed", host_keyed)?;
+    rate_limiter_instance.func_wrap("remove-keyed", host_remove_keyed)?;
+    rate_limiter_instance.func_wrap("ready-keyed", host_ready_keyed)?;
+    rate_limiter_instance.func_wrap_async("block-keyed", |store, params| {
+        Box::new(async move { host_block_keyed(store, params).await })
+    })?;
+    rate_limiter_instance.func_wrap("auto-block", host_auto_block)?;
+    rate_limiter_instance.func_wrap("set-auto-block", host_set_auto_block)?;
 
     Ok(())
 }
 
 /// Host function implementation for the `burst` function.
-fn host_burst(store: StoreContextMut<State>, _: ()) -> Result<(Option<u32>,), wasmtime::Error> {
-    let limiter = store.data().limiter();
-    let burst = limiter.map(|limiter| limiter.burst());
+async fn host_burst(
+    store: StoreContextMut<'_, State>,
+    _: (),
+) -> Result<(Option<u32>,), wasmtime::Error> {
+    let limiter = store.data().limiter().await;
+    let burst = limiter.as_ref().map(|limiter| limiter.burst());
     Ok((burst,))
 }
 
 /// Host function implementation for the `period-ms` function.
-fn host_period_ms(store: StoreContextMut<State>, _: ()) -> Result<(Option<u32>,), wasmtime::Error> {
-    let limiter = store.data().limiter();
-    let period_ms = limiter.map(|limiter| limiter.period_ms());
+async fn host_period_ms(
+    store: StoreContextMut<'_, State>,
+    _: (),
+) -> Result<(Option<u32>,), wasmtime::Error> {
+    let limiter = store.data().limiter().await;
+    let period_ms = limiter.as_ref().map(|limiter| limiter.period_ms());
     Ok((period_ms,))
 }
 
-/// Helper macro to get the limiter or insert a default limiter into the store.
-#[doc(hidden)]
-macro_rules! get_or_insert_default_limiter {
-    ($store:expr) => {{
-        match $store.data_mut().limiter_mut() {
-            Some(limiter) => limiter,
-            None => {
-                $store.data_mut().set_limiter(Default::default());
-                $store.data_mut().limiter_mut().unwrap()
-            }
-        }
-    }};
-}
-
 /// Host function implementation for the `set-burst` function.
-fn host_set_burst(
-    mut store: StoreContextMut<State>,
+async fn host_set_burst(
+    store: StoreContextMut<'_, State>,
     (burst,): (u32,),
 ) -> Result<(Result<(), ()>,), wasmtime::Error> {
-    let limiter = get_or_insert_default_limiter!(store);
-    let result = limiter.set_burst(burst);
+    let mut limiter = store.data().limiter_mut().await;
+    let result = update_limiter(&mut limiter, |limiter| limiter.set_burst(burst));
     Ok((result,))
 }
 
 /// Host function implementation for the `set-period-ms` function.
-fn host_set_period_ms(
-    mut store: StoreContextMut<State>,
+async fn host_set_period_ms(
+    store: StoreContextMut<'_, State>,
     (period_ms,): (u32,),
 ) -> Result<(Result<(), ()>,), wasmtime::Error> {
-    let limiter = get_or_insert_default_limiter!(store);
-    let result = limiter.set_period_ms(period_ms);
+    let mut limiter = store.data().limiter_mut().await;
+    let result = update_limiter(&mut limiter, |limiter| limiter.set_period_ms(period_ms));
     Ok((result,))
 }
 
+/// Replace the default limiter, or a new one if there is none, with a copy
+/// changed by `update`.
+///
+/// The limiter is shared with the calls waiting on it, it is thus replaced
+/// instead of being changed in place. Its pause carries over.
+fn update_limiter(
+    limiter: &mut Option<Arc<RateLimiter>>,
+    update: impl FnOnce(&mut RateLimiter) -> Result<(), ()>,
+) -> Result<(), ()> {
+    let mut updated = RateLimiter::default();
+    if let Some(current) = limiter.as_deref() {
+        updated = RateLimiter::new(current.burst(), current.period_ms()).ok_or(())?;
+        if let Some(paused_until) = current.paused_until() {
+            updated.pause(paused_until.saturating_duration_since(Instant::now()));
+        }
+    }
+
+    update(&mut updated)?;
+    *limiter = Some(Arc::new(updated));
+    Ok(())
+}
+
 /// Host function implementation for the `ready` function.
-fn host_ready(store: StoreContextMut<State>, _: ()) -> Result<(bool,), wasmtime::Error> {
-    let limiter = store.data().limiter();
-    let ready = limiter.map(|limiter| limiter.ready()).unwrap_or(true);
+async fn host_ready(store: StoreContextMut<'_, State>, _: ()) -> Result<(bool,), wasmtime::Error> {
+    let limiter = store.data().limiter().await;
+    let ready = limiter
+        .as_ref()
+        .map(|limiter| limiter.ready())
+        .unwrap_or(true);
     Ok((ready,))
 }
 
 /// Host function implementation for the `block` function.
 async fn host_block(store: StoreContextMut<'_, State>, _: ()) -> Result<(), wasmtime::Error> {
-    let Some(limiter) = store.data().limiter() else {
+    let Some(limiter) = store.data().limiter().await else {
+        return Ok(());
+    };
+    limiter.block().await;
+    Ok(())
+}
+
+/// Host function implementation for the `set-keyed` function.
+fn host_set_keyed(
+    store: StoreContextMut<State>,
+    (key, burst, period_ms): (String, u32, u32),
+) -> Result<(Result<(), ()>,), wasmtime::Error> {
+    let Some(limiter) = RateLimiter::new(burst, period_ms) else {
+        return Ok((Err(()),));
+    };
+    store.data().keyed_limiters().insert(key, limiter);
+    Ok((Ok(()),))
+}
+
+/// Host function implementation for the `keyed` function.
+fn host_keyed(
+    store: StoreContextMut<State>,
+    (key,): (String,),
+) -> Result<(Option<(u32, u32)>,), wasmtime::Error> {
+    let limiter = store.data().keyed_limiters().get(&key);
+    let limit = limiter.map(|limiter| (limiter.burst(), limiter.period_ms()));
+    Ok((limit,))
+}
+
+/// Host function implementation for the `remove-keyed` function.
+fn host_remove_keyed(
+    store: StoreContextMut<State>,
+    (key,): (String,),
+) -> Result<(bool,), wasmtime::Error> {
+    Ok((store.data().keyed_limiters().remove(&key),))
+}
+
+/// Host function implementation for the `ready-keyed` function.
+fn host_ready_keyed(
+    store: StoreContextMut<State>,
+    (key,): (String,),
+) -> Result<(bool,), wasmtime::Error> {
+    let limiter = store.data().keyed_limiters().get(&key);
+    let ready = limiter.is_none_or(|limiter| limiter.ready());
+    Ok((ready,))
+}
+
+/// Host function implementation for the `block-keyed` function.
+async fn host_block_keyed(
+    store: StoreContextMut<'_, State>,
+    (key,): (String,),
+) -> Result<(), wasmtime::Error> {
+    // The limiter is shared, the other limiters can be set while waiting.
+    let Some(limiter) = store.data().keyed_limiters().get(&key) else {
         return Ok(());
     };
     limiter.block().await;
     Ok(())
 }
+
+/// Host function implementation for the `auto-block` function.
+fn host_auto_block(store: StoreContextMut<State>, _: ()) -> Result<(bool,), wasmtime::Error> {
+    Ok((store.data().auto_block(),))
+}
+
+/// Host function implementation for the `set-auto-block` function.
+fn host_set_auto_block(
+    store: StoreContextMut<State>,
+    (enabled,): (bool,),
+) -> Result<(), wasmtime::Error> {
+    store.data().set_auto_block(enabled);
+    Ok(())
+}
@@ -4,20 +4,22 @@ use wasmtime::StoreContextMut;
 
 use crate::state::State;
 
-pub fn map_midoku_settings(linker: &mut Linker<State>) -> Result<(), Box<dyn std::error::Error>> {
+pub fn map_midoku_settings(linker: &mut Linker<State>) -> Result<(), wasmtime::Error> {
     let mut settings_instance: LinkerInstance<'_, State> =
         linker.instance("midoku:settings/settings@0.1.0")?;
-    settings_instance.func_wrap("get", host_get)?;
+    settings_instance.func_wrap_async("get", |store, params| {
+        Box::new(async move { host_get(store, params).await })
+    })?;
 
     Ok(())
 }
 
 /// Host function implementation for the `get` function.
-fn host_get(
-    store: StoreContextMut<State>,
+async fn host_get(
+    store: StoreContextMut<'_, State>,
     (key,): (String,),
 ) -> Result<(Result<Value, ()>,), wasmtime::Error> {
-    let settings = store.data().settings();
+    let settings = store.data().settings().await;
     let value = settings.get(&key).cloned().ok_or(());
     Ok((value,))
 }
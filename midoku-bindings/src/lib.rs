@@ -1,16 +1,32 @@
+pub mod api;
 pub mod bindings;
+pub mod builder;
+pub mod cache;
+pub mod error;
 mod func;
+mod instance;
 pub mod instance_impl;
+pub mod limits;
+mod pool;
 mod resource_table;
+pub mod runtime;
 pub mod state;
 
+pub use api::ApiVersion;
 pub use bindings::Bindings;
+pub use builder::{BindingsBuilder, HostInterfaces};
+pub use cache::ComponentCache;
+pub use error::Error;
+pub use limits::Limits;
+pub use runtime::Runtime;
 
 /// Re-export the types from the `midoku-types` crate.
 pub mod exports {
     pub use midoku_settings::types::*;
     pub use midoku_types::chapter::*;
+    pub use midoku_types::error::*;
     pub use midoku_types::filter::*;
     pub use midoku_types::manga::*;
     pub use midoku_types::page::*;
+    pub use midoku_types::source_info::*;
 }
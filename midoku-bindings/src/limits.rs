@@ -0,0 +1,148 @@
+use std::fmt;
+
+use wasmtime::ResourceLimiter;
+
+/// Resource limits applied to each instance of a source.
+///
+/// Every limit is optional, `None` meaning the resource is unbounded. When a
+/// limit is exceeded the guest traps and the call returns
+/// [`Error::ResourceLimit`](crate::Error::ResourceLimit).
+///
+/// # Example
+/// ```ignore
+/// let limits = Limits {
+///     fuel: Some(1_000_000_000),
+///     memory_size: Some(64 << 20),
+///     ..Default::default()
+/// };
+///
+/// let runtime = Runtime::new()?.with_limits(limits)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Limits {
+    /// Maximum amount of fuel a single call may consume.
+    ///
+    /// Fuel is consumed as the guest executes instructions, this is a
+    /// deterministic bound of the CPU time spent in a call. Metering fuel
+    /// slows down the guest, so it is only enabled when this is set through
+    /// [`Runtime::with_limits`](crate::Runtime::with_limits).
+    pub fuel: Option<u64>,
+
+    /// Maximum size in bytes of the linear memory of the instance.
+    pub memory_size: Option<usize>,
+
+    /// Maximum number of elements of each table of the instance.
+    pub table_elements: Option<usize>,
+
+    /// Maximum number of live `incoming-response` and `incoming-body`
+    /// resources held by the instance.
+    pub incoming_responses: Option<usize>,
+
+    /// Maximum size in bytes of a response body read into memory by the host,
+    /// e.g. with `bytes` or `text`.
+    ///
+    /// Larger bodies fail with
+    /// [`HttpError::BodyTooLarge`](midoku_http::types::HttpError::BodyTooLarge)
+    /// instead of trapping. Bodies read one chunk at a time through
+    /// `incoming-body` are not limited.
+    pub response_body_size: Option<usize>,
+}
+
+impl ResourceLimiter for Limits {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool, wasmtime::Error> {
+        match self.memory_size {
+            Some(memory_size) if desired > memory_size => Err(ResourceLimit::Memory.into()),
+            _ => Ok(true),
+        }
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool, wasmtime::Error> {
+        match self.table_elements {
+            Some(table_elements) if desired > table_elements => {
+                Err(ResourceLimit::TableElements.into())
+            }
+            _ => Ok(true),
+        }
+    }
+}
+
+/// A resource limit exceeded by an instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimit {
+    /// The call consumed all of its fuel.
+    Fuel,
+    /// The linear memory grew past [`Limits::memory_size`].
+    Memory,
+    /// A table grew past [`Limits::table_elements`].
+    TableElements,
+    /// The instance held more than [`Limits::incoming_responses`] responses.
+    IncomingResponses,
+}
+
+impl fmt::Display for ResourceLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fuel => write!(f, "fuel exhausted"),
+            Self::Memory => write!(f, "memory size limit exceeded"),
+            Self::TableElements => write!(f, "table elements limit exceeded"),
+            Self::IncomingResponses => write!(f, "incoming responses limit exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for ResourceLimit {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_default() {
+        let mut limits = Limits::default();
+
+        assert!(limits.memory_growing(0, usize::MAX, None).unwrap());
+        assert!(limits.table_growing(0, usize::MAX, None).unwrap());
+    }
+
+    #[test]
+    fn test_limits_memory_size() {
+        let mut limits = Limits {
+            memory_size: Some(1024),
+            ..Default::default()
+        };
+
+        assert!(limits.memory_growing(0, 1024, None).unwrap());
+
+        let err = limits.memory_growing(1024, 2048, None).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ResourceLimit>(),
+            Some(&ResourceLimit::Memory)
+        );
+    }
+
+    #[test]
+    fn test_limits_table_elements() {
+        let mut limits = Limits {
+            table_elements: Some(10),
+            ..Default::default()
+        };
+
+        assert!(limits.table_growing(0, 10, None).unwrap());
+
+        let err = limits.table_growing(10, 11, None).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ResourceLimit>(),
+            Some(&ResourceLimit::TableElements)
+        );
+    }
+}
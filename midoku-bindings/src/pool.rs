@@ -0,0 +1,128 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, Notify, OwnedMutexGuard, OwnedSemaphorePermit, Semaphore};
+
+use crate::instance::Instance;
+
+/// A pool of instances of the same source.
+///
+/// Each call is dispatched to a free instance, so that up to [`Pool::len`]
+/// calls run concurrently.
+pub(crate) struct Pool {
+    instances: Vec<Arc<Mutex<Instance>>>,
+    semaphore: Arc<Semaphore>,
+    released: Arc<Notify>,
+}
+
+/// An instance acquired from a [`Pool`], released when dropped.
+pub(crate) struct PooledInstance {
+    // Fields are dropped in declaration order, the instance must be released
+    // before the permit, and both before notifying the waiters.
+    instance: OwnedMutexGuard<Instance>,
+    _permit: OwnedSemaphorePermit,
+    _released: Released,
+}
+
+/// Notifies the callers of [`Pool::acquire`] waiting for an instance when
+/// dropped.
+struct Released(Arc<Notify>);
+
+impl Drop for Released {
+    fn drop(&mut self) {
+        self.0.notify_waiters();
+    }
+}
+
+impl Pool {
+    pub fn new(instances: Vec<Instance>) -> Self {
+        let semaphore = Arc::new(Semaphore::new(instances.len()));
+        let instances = instances
+            .into_iter()
+            .map(|instance| Arc::new(Mutex::new(instance)))
+            .collect();
+
+        Self {
+            instances,
+            semaphore,
+            released: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Returns the number of instances in the pool.
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Acquire a free instance, waiting for one if they are all busy.
+    pub async fn acquire(&self) -> PooledInstance {
+        loop {
+            // Registered before looking for an instance, so that a release
+            // happening in between is not missed.
+            let released = self.released.notified();
+            let permit = self.acquire_permit().await;
+
+            let instance = self
+                .instances
+                .iter()
+                .find_map(|instance| instance.clone().try_lock_owned().ok());
+            if let Some(instance) = instance {
+                return self.pooled(instance, permit);
+            }
+
+            // Every instance is locked, some by callers of `acquire_at` still
+            // waiting for a permit. Give the permit back to them and try again
+            // once an instance is released.
+            drop(permit);
+            released.await;
+        }
+    }
+
+    /// Acquire the instance at `index`, waiting for it if it is busy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub async fn acquire_at(&self, index: usize) -> PooledInstance {
+        // The instance is locked first, so that waiting for a busy instance
+        // does not hold a permit another caller could use.
+        let instance = self.instances[index].clone().lock_owned().await;
+        let permit = self.acquire_permit().await;
+
+        self.pooled(instance, permit)
+    }
+
+    fn pooled(
+        &self,
+        instance: OwnedMutexGuard<Instance>,
+        permit: OwnedSemaphorePermit,
+    ) -> PooledInstance {
+        PooledInstance {
+            instance,
+            _permit: permit,
+            _released: Released(self.released.clone()),
+        }
+    }
+
+    async fn acquire_permit(&self) -> OwnedSemaphorePermit {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed")
+    }
+}
+
+impl Deref for PooledInstance {
+    type Target = Instance;
+
+    fn deref(&self) -> &Self::Target {
+        &self.instance
+    }
+}
+
+impl DerefMut for PooledInstance {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.instance
+    }
+}
@@ -18,6 +18,11 @@ where
         }
     }
 
+    /// Returns the number of resources in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.free_cells.len()
+    }
+
     /// Get an immutable reference to a resource at a given index.
     ///
     /// Multiple shared references can be borrowed at any given time.
@@ -28,6 +33,14 @@ where
             .ok_or(ResourceTableError::NotPresent)
     }
 
+    /// Get a mutable reference to a resource at a given index.
+    pub fn get_mut(&mut self, key: &Resource<T>) -> Result<&mut T, ResourceTableError> {
+        self.entries
+            .get_mut(key.rep() as usize)
+            .and_then(|entry| entry.as_mut())
+            .ok_or(ResourceTableError::NotPresent)
+    }
+
     /// Inserts a new value `T` into this table, returning a corresponding
     /// `Resource<T>` which can be used to refer to it after it was inserted.
     pub fn push(&mut self, entry: T) -> Result<Resource<T>, ResourceTableError> {
@@ -0,0 +1,369 @@
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use midoku_http::client::{ConfigError, HttpConfig};
+use midoku_http::outgoing_handler::Client;
+use midoku_http::policy::NetworkPolicy;
+use wasmtime::component::{Component, Linker};
+use wasmtime::{Config, Engine, EngineWeak};
+
+use crate::bindings::Bindings;
+use crate::builder::{BindingsBuilder, HostInterfaces};
+use crate::cache::ComponentCache;
+use crate::error::Error;
+use crate::instance_impl::midoku_http::map_midoku_http;
+use crate::instance_impl::midoku_limiter::map_midoku_limiter;
+use crate::instance_impl::midoku_settings::map_midoku_settings;
+use crate::limits::Limits;
+use crate::state::State;
+
+/// Interval at which the epoch of the engine is incremented.
+///
+/// Running guests yield back to the executor on every tick, this bounds the
+/// time it takes for a cancelled call to be interrupted.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Host runtime for Midoku sources.
+///
+/// The runtime owns the [`Engine`] and a [`Linker`] with the Midoku host
+/// interfaces already mapped. Creating them is not free, so a single runtime
+/// should be shared to instantiate every source of the application.
+///
+/// # Example
+/// ```ignore
+/// let runtime = Runtime::new()?.with_cache_dir("cache");
+///
+/// // Each source gets its own store, but they all share the same engine and
+/// // linker.
+/// let source1 = runtime.instantiate_file("source1.wasm").await?;
+/// let source2 = runtime.instantiate_file("source2.wasm").await?;
+/// ```
+pub struct Runtime {
+    engine: Engine,
+    consume_fuel: bool,
+    linker: Linker<State>,
+    cache: Option<ComponentCache>,
+    limits: Limits,
+    http_config: HttpConfig,
+    http_client: Client,
+    /// Client denying private addresses when connecting, `None` if it cannot
+    /// be built from the configuration or the client was set by the host.
+    public_http_client: Option<Client>,
+}
+
+impl Runtime {
+    /// Create a new runtime with the Midoku host interfaces mapped.
+    pub fn new() -> Result<Self, Error> {
+        let engine = new_engine(false)?;
+        let linker = new_linker(&engine, &HostInterfaces::default())?;
+        let http_config = HttpConfig::default();
+        let http_client = http_config
+            .build()
+            .map_err(|err| Error::Http(err.to_string()))?;
+        let public_http_client = build_public_http_client(&http_config)?;
+
+        Ok(Self {
+            engine,
+            consume_fuel: false,
+            linker,
+            cache: None,
+            limits: Limits::default(),
+            http_config,
+            http_client,
+            public_http_client,
+        })
+    }
+
+    /// Store the compiled components in `dir`.
+    ///
+    /// Components loaded through this runtime are compiled once and then
+    /// deserialized from the cache, see [`ComponentCache`].
+    pub fn with_cache_dir<T: Into<PathBuf>>(mut self, dir: T) -> Self {
+        self.cache = Some(ComponentCache::new(dir));
+        self
+    }
+
+    /// Apply `limits` to every instance created by this runtime.
+    ///
+    /// Fuel is only consumed by the guests if [`Limits::fuel`] is set, as
+    /// metering every instruction slows down their execution. Setting it
+    /// recreates the engine, so this must be called before compiling any
+    /// component. Per-source fuel limits, see
+    /// [`BindingsBuilder::with_limits`], also require the fuel limit of the
+    /// runtime to be set.
+    pub fn with_limits(mut self, limits: Limits) -> Result<Self, Error> {
+        if limits.fuel.is_some() && !self.consume_fuel {
+            self.engine = new_engine(true)?;
+            self.linker = new_linker(&self.engine, &HostInterfaces::default())?;
+            self.consume_fuel = true;
+        }
+
+        self.limits = limits;
+        Ok(self)
+    }
+
+    /// Send the HTTP requests of every source with a client built from
+    /// `http_config`.
+    ///
+    /// Redirects are followed by the host, up to
+    /// [`HttpConfig::redirect`]. Sources whose network policy denies private
+    /// addresses cannot be instantiated if a proxy is set, see
+    /// [`HttpConfig::build_with_policy`].
+    pub fn with_http_config(mut self, http_config: HttpConfig) -> Result<Self, Error> {
+        self.http_client = http_config
+            .build()
+            .map_err(|err| Error::Http(err.to_string()))?;
+        self.public_http_client = build_public_http_client(&http_config)?;
+        self.http_config = http_config;
+        Ok(self)
+    }
+
+    /// Send the HTTP requests of every source with `http_client`.
+    ///
+    /// Sharing a single client allows reusing connections between requests,
+    /// see [`HttpConfig`] to configure it. Redirects are followed by the host,
+    /// the client should not follow them itself. The addresses the client
+    /// connects to cannot be checked, so sources whose network policy denies
+    /// private addresses cannot be instantiated.
+    pub fn with_http_client(mut self, http_client: Client) -> Self {
+        self.http_client = http_client;
+        self.public_http_client = None;
+        self
+    }
+
+    /// Get a reference to the engine.
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Get a reference to the linker.
+    pub fn linker(&self) -> &Linker<State> {
+        &self.linker
+    }
+
+    /// Returns `true` if the guests consume fuel, see [`Runtime::with_limits`].
+    pub fn consumes_fuel(&self) -> bool {
+        self.consume_fuel
+    }
+
+    /// Get a reference to the limits applied to the instances.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Get a reference to the configuration of the HTTP client.
+    pub fn http_config(&self) -> &HttpConfig {
+        &self.http_config
+    }
+
+    /// Get a reference to the HTTP client shared by the sources.
+    pub fn http_client(&self) -> &Client {
+        &self.http_client
+    }
+
+    /// Get the HTTP client enforcing `network_policy` when connecting, see
+    /// [`HttpConfig::build_with_policy`].
+    pub(crate) fn http_client_for(&self, network_policy: &NetworkPolicy) -> Result<&Client, Error> {
+        if network_policy.allow_private_addresses {
+            return Ok(&self.http_client);
+        }
+
+        self.public_http_client.as_ref().ok_or_else(|| {
+            Error::Http("the network policy cannot be enforced by the HTTP client".to_string())
+        })
+    }
+
+    /// Get a reference to the component cache, if any.
+    pub fn cache(&self) -> Option<&ComponentCache> {
+        self.cache.as_ref()
+    }
+
+    /// Compile a WebAssembly component, going through the cache if enabled.
+    pub fn compile(&self, bytes: &[u8]) -> Result<Component, Error> {
+        let component = match &self.cache {
+            Some(cache) => cache.get_or_compile(&self.engine, bytes),
+            None => Component::new(&self.engine, bytes),
+        };
+
+        component.map_err(Error::Instantiation)
+    }
+
+    /// Compile a WebAssembly component .wasm file, going through the cache if
+    /// enabled.
+    pub fn compile_file<T: AsRef<Path>>(&self, path: T) -> Result<Component, Error> {
+        let bytes = std::fs::read(path)?;
+        self.compile(&bytes)
+    }
+
+    /// Compile a WebAssembly component read from `reader`, going through the
+    /// cache if enabled.
+    pub fn compile_reader<R: Read>(&self, mut reader: R) -> Result<Component, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        self.compile(&bytes)
+    }
+
+    /// Compile a WebAssembly component ahead of time.
+    ///
+    /// The returned bytes, usually stored as a `.cwasm` file, can be loaded
+    /// with [`Runtime::deserialize`] by a runtime with the same configuration.
+    pub fn precompile(&self, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+        self.engine
+            .precompile_component(bytes)
+            .map_err(Error::Instantiation)
+    }
+
+    /// Load a component precompiled with [`Runtime::precompile`].
+    ///
+    /// Artifacts produced by another version of wasmtime or another
+    /// configuration of the engine are rejected with
+    /// [`Error::Instantiation`].
+    ///
+    /// # Safety
+    ///
+    /// The bytes are loaded as executable code without being validated, they
+    /// must come from a trusted source, see [`Component::deserialize`].
+    pub unsafe fn deserialize(&self, bytes: &[u8]) -> Result<Component, Error> {
+        Component::deserialize(&self.engine, bytes).map_err(Error::Instantiation)
+    }
+
+    /// Create a new instance of the bindings from a compiled component.
+    ///
+    /// The same component can be instantiated any number of times, each
+    /// instance having its own store.
+    pub async fn instantiate(&self, component: &Component) -> Result<Bindings, Error> {
+        self.instantiate_pool(component, NonZeroUsize::MIN).await
+    }
+
+    /// Create a new instance of the bindings backed by a pool of `size`
+    /// instances of a compiled component.
+    ///
+    /// The instances share the same settings and rate limiter, calls are
+    /// dispatched to a free instance so that up to `size` of them run
+    /// concurrently.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let component = runtime.compile_file("source.wasm")?;
+    /// let bindings = runtime
+    ///     .instantiate_pool(&component, NonZeroUsize::new(4).unwrap())
+    ///     .await?;
+    ///
+    /// // The page list does not wait for the manga list to complete.
+    /// let (manga_list, page_list) = tokio::join!(
+    ///     bindings.get_manga_list(vec![], 0),
+    ///     bindings.get_page_list(manga_id, chapter_id),
+    /// );
+    /// ```
+    pub async fn instantiate_pool(
+        &self,
+        component: &Component,
+        size: NonZeroUsize,
+    ) -> Result<Bindings, Error> {
+        BindingsBuilder::new()
+            .with_pool_size(size)
+            .instantiate(self, component)
+            .await
+    }
+
+    /// Create a new instance of the bindings from a WebAssembly component
+    /// .wasm file.
+    pub async fn instantiate_file<T: AsRef<Path>>(&self, path: T) -> Result<Bindings, Error> {
+        let component = self.compile_file(path)?;
+        self.instantiate(&component).await
+    }
+}
+
+/// Build the client of the sources denying private addresses, if the
+/// configuration allows enforcing it.
+fn build_public_http_client(http_config: &HttpConfig) -> Result<Option<Client>, Error> {
+    let network_policy = NetworkPolicy {
+        allowed_hosts: None,
+        allow_private_addresses: false,
+    };
+
+    match http_config.build_with_policy(&network_policy) {
+        Ok(client) => Ok(Some(client)),
+        Err(ConfigError::UnenforceablePolicy) => Ok(None),
+        Err(err) => Err(Error::Http(err.to_string())),
+    }
+}
+
+/// Create an engine for the Midoku sources, metering fuel if `consume_fuel`.
+fn new_engine(consume_fuel: bool) -> Result<Engine, Error> {
+    let mut config = Config::default();
+    config.async_support(true);
+    config.consume_fuel(consume_fuel);
+    config.epoch_interruption(true);
+
+    let engine = Engine::new(&config).map_err(Error::Instantiation)?;
+    register_epoch_ticker(engine.weak());
+
+    Ok(engine)
+}
+
+/// Create a linker with the given Midoku host interfaces mapped.
+pub(crate) fn new_linker(
+    engine: &Engine,
+    interfaces: &HostInterfaces,
+) -> Result<Linker<State>, Error> {
+    let mut linker: Linker<State> = Linker::new(engine);
+    if interfaces.http {
+        map_midoku_http(&mut linker).map_err(Error::Instantiation)?;
+    }
+    if interfaces.limiter {
+        map_midoku_limiter(&mut linker).map_err(Error::Instantiation)?;
+    }
+    if interfaces.settings {
+        map_midoku_settings(&mut linker).map_err(Error::Instantiation)?;
+    }
+
+    Ok(linker)
+}
+
+/// Engines whose epoch is incremented by the ticker thread.
+struct EpochTicker {
+    engines: Vec<EngineWeak>,
+    running: bool,
+}
+
+static EPOCH_TICKER: Mutex<EpochTicker> = Mutex::new(EpochTicker {
+    engines: Vec::new(),
+    running: false,
+});
+
+/// Increment the epoch of the engine every [`EPOCH_TICK`], until the engine is
+/// dropped.
+///
+/// A single thread ticks every engine of the process, it exits once all of
+/// them are dropped and is spawned again by the next registration.
+fn register_epoch_ticker(engine: EngineWeak) {
+    let mut ticker = EPOCH_TICKER.lock().unwrap();
+    ticker.engines.push(engine);
+    if ticker.running {
+        return;
+    }
+    ticker.running = true;
+
+    std::thread::spawn(|| loop {
+        {
+            let mut ticker = EPOCH_TICKER.lock().unwrap();
+            ticker.engines.retain(|engine| match engine.upgrade() {
+                Some(engine) => {
+                    engine.increment_epoch();
+                    true
+                }
+                None => false,
+            });
+            if ticker.engines.is_empty() {
+                ticker.running = false;
+                return;
+            }
+        }
+
+        std::thread::sleep(EPOCH_TICK);
+    });
+}
@@ -1,40 +1,224 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
-use midoku_http::types::IncomingResponse;
+use midoku_http::client::RedirectPolicy;
+use midoku_http::cookie::CookieJar;
+use midoku_http::policy::NetworkPolicy;
+use midoku_http::retry::RetryPolicy;
+use midoku_http::transport::{HttpTransport, ReqwestTransport};
+use midoku_http::types::{IncomingBody, IncomingResponse};
+use midoku_limiter::keyed::KeyedRateLimiters;
 use midoku_limiter::rate_limiter::RateLimiter;
 use midoku_settings::types::Value;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+use crate::limits::Limits;
 use crate::resource_table::ResourceTable;
 
 pub(crate) struct ResourceTables {
     pub incoming_response: ResourceTable<IncomingResponse>,
+    pub incoming_body: ResourceTable<IncomingBody>,
+}
+
+/// State shared by every instance of a source.
+#[derive(Clone)]
+pub(crate) struct Shared {
+    limiter: Arc<RwLock<Option<Arc<RateLimiter>>>>,
+    keyed_limiters: Arc<KeyedRateLimiters>,
+    auto_block: Arc<AtomicBool>,
+    settings: Arc<RwLock<HashMap<String, Value>>>,
+    transport: Arc<dyn HttpTransport>,
+    cookie_jar: Arc<CookieJar>,
+    network_policy: Arc<RwLock<NetworkPolicy>>,
+    retry_policy: Arc<RetryPolicy>,
+    redirect_policy: RedirectPolicy,
+}
+
+impl Shared {
+    pub fn new(
+        settings: HashMap<String, Value>,
+        limiter: Option<RateLimiter>,
+        transport: Arc<dyn HttpTransport>,
+        cookie_jar: Arc<CookieJar>,
+        network_policy: NetworkPolicy,
+        retry_policy: RetryPolicy,
+        redirect_policy: RedirectPolicy,
+    ) -> Self {
+        Self {
+            limiter: Arc::new(RwLock::new(limiter.map(Arc::new))),
+            keyed_limiters: Arc::default(),
+            auto_block: Arc::default(),
+            settings: Arc::new(RwLock::new(settings)),
+            transport,
+            cookie_jar,
+            network_policy: Arc::new(RwLock::new(network_policy)),
+            retry_policy: Arc::new(retry_policy),
+            redirect_policy,
+        }
+    }
+
+    /// Returns the default limiter.
+    ///
+    /// The limiter is shared, waiting on it does not prevent it from being
+    /// replaced.
+    pub async fn limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.limiter.read().await.clone()
+    }
+
+    pub async fn limiter_mut(&self) -> RwLockWriteGuard<'_, Option<Arc<RateLimiter>>> {
+        self.limiter.write().await
+    }
+
+    pub fn keyed_limiters(&self) -> &KeyedRateLimiters {
+        &self.keyed_limiters
+    }
+
+    /// Returns whether requests block on a limiter automatically, see
+    /// `set-auto-block`.
+    pub fn auto_block(&self) -> bool {
+        self.auto_block.load(Ordering::Relaxed)
+    }
+
+    pub fn set_auto_block(&self, enabled: bool) {
+        self.auto_block.store(enabled, Ordering::Relaxed);
+    }
+
+    pub async fn settings(&self) -> RwLockReadGuard<'_, HashMap<String, Value>> {
+        self.settings.read().await
+    }
+
+    pub async fn settings_mut(&self) -> RwLockWriteGuard<'_, HashMap<String, Value>> {
+        self.settings.write().await
+    }
+
+    pub fn transport(&self) -> &Arc<dyn HttpTransport> {
+        &self.transport
+    }
+
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
+    pub async fn network_policy(&self) -> RwLockReadGuard<'_, NetworkPolicy> {
+        self.network_policy.read().await
+    }
+
+    pub async fn network_policy_mut(&self) -> RwLockWriteGuard<'_, NetworkPolicy> {
+        self.network_policy.write().await
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Returns the maximum number of redirects followed by the host.
+    pub fn redirect_policy(&self) -> RedirectPolicy {
+        self.redirect_policy
+    }
+}
+
+impl Default for Shared {
+    fn default() -> Self {
+        Self::new(
+            HashMap::new(),
+            None,
+            Arc::new(ReqwestTransport::default()),
+            Arc::default(),
+            NetworkPolicy::default(),
+            RetryPolicy::default(),
+            RedirectPolicy::default(),
+        )
+    }
 }
 
 pub struct State {
     pub(crate) resource_tables: ResourceTables,
-    limiter: Option<RateLimiter>,
-    settings: HashMap<String, Value>,
+    shared: Shared,
+    limits: Limits,
+    deadline: Option<Instant>,
 }
 
 impl State {
-    pub fn limiter(&self) -> Option<&RateLimiter> {
-        self.limiter.as_ref()
+    /// Create a new state for an instance of a source.
+    ///
+    /// Every instance created with the same `shared` state sees the same
+    /// settings and rate limiters.
+    pub(crate) fn new(shared: Shared, limits: Limits) -> Self {
+        Self {
+            shared,
+            limits,
+            ..Default::default()
+        }
+    }
+
+    pub async fn limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.shared.limiter().await
+    }
+
+    pub async fn limiter_mut(&self) -> RwLockWriteGuard<'_, Option<Arc<RateLimiter>>> {
+        self.shared.limiter_mut().await
+    }
+
+    pub async fn set_limiter(&self, limiter: RateLimiter) {
+        *self.limiter_mut().await = Some(Arc::new(limiter));
+    }
+
+    pub fn keyed_limiters(&self) -> &KeyedRateLimiters {
+        self.shared.keyed_limiters()
+    }
+
+    pub fn auto_block(&self) -> bool {
+        self.shared.auto_block()
+    }
+
+    pub fn set_auto_block(&self, enabled: bool) {
+        self.shared.set_auto_block(enabled);
+    }
+
+    pub async fn settings(&self) -> RwLockReadGuard<'_, HashMap<String, Value>> {
+        self.shared.settings().await
+    }
+
+    pub async fn settings_mut(&self) -> RwLockWriteGuard<'_, HashMap<String, Value>> {
+        self.shared.settings_mut().await
+    }
+
+    pub fn transport(&self) -> &Arc<dyn HttpTransport> {
+        self.shared.transport()
+    }
+
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        self.shared.cookie_jar()
+    }
+
+    pub async fn network_policy(&self) -> RwLockReadGuard<'_, NetworkPolicy> {
+        self.shared.network_policy().await
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        self.shared.retry_policy()
+    }
+
+    pub(crate) fn shared(&self) -> &Shared {
+        &self.shared
     }
 
-    pub fn limiter_mut(&mut self) -> Option<&mut RateLimiter> {
-        self.limiter.as_mut()
+    pub fn limits(&self) -> &Limits {
+        &self.limits
     }
 
-    pub fn set_limiter(&mut self, limiter: RateLimiter) {
-        self.limiter = Some(limiter);
+    pub(crate) fn limits_mut(&mut self) -> &mut Limits {
+        &mut self.limits
     }
 
-    pub fn settings(&self) -> &HashMap<String, Value> {
-        &self.settings
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
     }
 
-    pub fn settings_mut(&mut self) -> &mut HashMap<String, Value> {
-        &mut self.settings
+    pub(crate) fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
     }
 }
 
@@ -42,12 +226,14 @@ impl Default for State {
     fn default() -> Self {
         let resource_tables = ResourceTables {
             incoming_response: ResourceTable::new(),
+            incoming_body: ResourceTable::new(),
         };
 
         Self {
             resource_tables,
-            limiter: None,
-            settings: HashMap::new(),
+            shared: Shared::default(),
+            limits: Limits::default(),
+            deadline: None,
         }
     }
 }
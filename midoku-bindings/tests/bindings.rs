@@ -37,6 +37,42 @@ static EXTENSION_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
     workspace_path.join("target/wasm32-unknown-unknown/release/example_extension.wasm")
 });
 
+/// Instantiate the example extension with `builder`, its requests being sent
+/// to a mock transport answering the `GET` requests to the given URLs.
+async fn instantiate_with_mock(
+    builder: midoku_bindings::BindingsBuilder,
+    responses: Vec<(&str, midoku_http::types::IncomingResponse)>,
+) -> (
+    midoku_bindings::Bindings,
+    std::sync::Arc<midoku_http::transport::MockTransport>,
+) {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+
+    let transport = responses.into_iter().fold(
+        midoku_http::transport::MockTransport::new(),
+        |transport, (url, response)| {
+            transport.with_response(midoku_http::types::Method::Get, url, response)
+        },
+    );
+    let transport = std::sync::Arc::new(transport);
+
+    let bindings = builder
+        .with_transport(transport.clone())
+        .instantiate_file(&runtime, EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    (bindings, transport)
+}
+
+/// The response to the request sent by `get-manga-list`.
+fn manga_list_response() -> (&'static str, midoku_http::types::IncomingResponse) {
+    (
+        "http://example.com/manga",
+        midoku_http::types::IncomingResponse::new(200, vec![], b"[]".to_vec()),
+    )
+}
+
 #[tokio::test]
 async fn test_bindings_from_file() {
     let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path()).await;
@@ -44,6 +80,48 @@ async fn test_bindings_from_file() {
     assert!(bindings.is_ok());
 }
 
+#[tokio::test]
+async fn test_bindings_source_info() {
+    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    // The source info is available without initializing the source
+    let source_info = bindings.source_info().await.unwrap();
+
+    assert_eq!(source_info.name, "Example");
+    assert_eq!(source_info.languages, vec!["en".to_string()]);
+    assert_eq!(source_info.base_url, "http://example.com");
+    assert!(!source_info.nsfw);
+    assert_eq!(source_info.icon, None);
+    assert_eq!(
+        source_info.allowed_hosts,
+        Some(vec!["example.com".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn test_bindings_api_version() {
+    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    assert_eq!(bindings.api_version(), midoku_bindings::ApiVersion::LATEST);
+}
+
+#[tokio::test]
+async fn test_bindings_incompatible_api() {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+    let component = runtime.compile(b"(component)").unwrap();
+
+    let bindings = runtime.instantiate(&component).await;
+
+    assert!(matches!(
+        bindings,
+        Err(midoku_bindings::Error::IncompatibleApi(versions)) if versions.is_empty()
+    ));
+}
+
 #[tokio::test]
 async fn test_bindings_initialize() {
     let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
@@ -57,9 +135,11 @@ async fn test_bindings_initialize() {
 
 #[tokio::test]
 async fn test_bindings_get_manga_list() {
-    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
-        .await
-        .unwrap();
+    let (bindings, transport) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder(),
+        vec![manga_list_response()],
+    )
+    .await;
 
     let get_manga_list = bindings.get_manga_list(vec![], 0).await;
 
@@ -70,13 +150,15 @@ async fn test_bindings_get_manga_list() {
     // Example result
     assert!(manga_list.is_empty());
     assert!(!has_next);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url, "http://example.com/manga");
 }
 
 #[tokio::test]
 async fn test_bindings_get_manga_details() {
-    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
-        .await
-        .unwrap();
+    let (bindings, _) = instantiate_with_mock(midoku_bindings::Bindings::builder(), vec![]).await;
 
     let get_manga_details = bindings.get_manga_details("manga_id".to_string()).await;
 
@@ -100,9 +182,7 @@ async fn test_bindings_get_manga_details() {
 
 #[tokio::test]
 async fn test_bindings_get_chapter_list() {
-    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
-        .await
-        .unwrap();
+    let (bindings, _) = instantiate_with_mock(midoku_bindings::Bindings::builder(), vec![]).await;
 
     let get_chapter_list = bindings.get_chapter_list("manga_id".to_string()).await;
 
@@ -116,9 +196,7 @@ async fn test_bindings_get_chapter_list() {
 
 #[tokio::test]
 async fn test_bindings_get_page_list() {
-    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
-        .await
-        .unwrap();
+    let (bindings, _) = instantiate_with_mock(midoku_bindings::Bindings::builder(), vec![]).await;
 
     let get_page_list = bindings
         .get_page_list("manga_id".to_string(), "chapter_id".to_string())
@@ -179,9 +257,11 @@ async fn test_bindings_setting_mut() {
 async fn test_cancellation_safety() {
     use tokio::time::{timeout, Duration};
 
-    let bindings = midoku_bindings::Bindings::from_file(EXTENSION_PATH.as_path())
-        .await
-        .unwrap();
+    let (bindings, _) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder(),
+        vec![manga_list_response()],
+    )
+    .await;
 
     let future = || bindings.get_manga_list(vec![], 0);
 
@@ -193,3 +273,549 @@ async fn test_cancellation_safety() {
     // The operation should not panic
     future().await.unwrap();
 }
+
+#[tokio::test]
+async fn test_timeout() {
+    use tokio::time::Duration;
+
+    let (mut bindings, _) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder(),
+        vec![manga_list_response()],
+    )
+    .await;
+
+    bindings.set_timeout(Some(Duration::from_nanos(1)));
+    assert_eq!(bindings.timeout(), Some(Duration::from_nanos(1)));
+
+    let res = bindings.get_manga_list(vec![], 0).await;
+    assert!(matches!(res, Err(midoku_bindings::Error::Timeout)));
+
+    // The instance should be usable again
+    bindings.set_timeout(None);
+    bindings.get_manga_list(vec![], 0).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_bindings_from_bytes() {
+    let bytes = std::fs::read(EXTENSION_PATH.as_path()).unwrap();
+
+    let bindings = midoku_bindings::Bindings::from_bytes(&bytes).await;
+
+    assert!(bindings.is_ok());
+}
+
+#[tokio::test]
+async fn test_bindings_from_reader() {
+    let file = std::fs::File::open(EXTENSION_PATH.as_path()).unwrap();
+
+    let bindings = midoku_bindings::Bindings::from_reader(file).await;
+
+    assert!(bindings.is_ok());
+}
+
+#[tokio::test]
+async fn test_bindings_from_precompiled() {
+    let bytes = std::fs::read(EXTENSION_PATH.as_path()).unwrap();
+    let precompiled = midoku_bindings::Runtime::new()
+        .unwrap()
+        .precompile(&bytes)
+        .unwrap();
+
+    let bindings = unsafe { midoku_bindings::Bindings::from_precompiled(&precompiled) }.await;
+    assert!(bindings.is_ok());
+
+    // A component that was not precompiled should be rejected
+    let bindings = unsafe { midoku_bindings::Bindings::from_precompiled(&bytes) }.await;
+    assert!(matches!(
+        bindings,
+        Err(midoku_bindings::Error::Instantiation(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_builder_settings() {
+    use std::collections::HashMap;
+
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+
+    let settings = HashMap::from([(
+        "key".to_string(),
+        midoku_settings::types::Value::String("value".to_string()),
+    )]);
+    let bindings = midoku_bindings::Bindings::builder()
+        .with_settings(settings)
+        .instantiate_file(&runtime, EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        bindings.settings().await.get("key"),
+        Some(&midoku_settings::types::Value::String("value".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_builder_rate_limiter() {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+
+    let mut rate_limiter = midoku_limiter::rate_limiter::RateLimiter::default();
+    rate_limiter.set_limiter(5, 1000).unwrap();
+
+    let bindings = midoku_bindings::Bindings::builder()
+        .with_rate_limiter(rate_limiter)
+        .with_timeout(std::time::Duration::from_secs(30))
+        .instantiate_file(&runtime, EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    assert_eq!(bindings.timeout(), Some(std::time::Duration::from_secs(30)));
+    assert!(bindings.initialize().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_builder_interfaces() {
+    let (bindings, transport) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder().with_interfaces(midoku_bindings::HostInterfaces {
+            http: false,
+            ..Default::default()
+        }),
+        vec![manga_list_response()],
+    )
+    .await;
+
+    bindings.initialize().await.unwrap();
+
+    // The source cannot send requests
+    let res = bindings.get_manga_list(vec![], 0).await;
+    assert!(matches!(res, Err(midoku_bindings::Error::Trap(_))));
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_runtime_instantiate_file() {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+
+    let bindings1 = runtime.instantiate_file(EXTENSION_PATH.as_path()).await;
+    let bindings2 = runtime.instantiate_file(EXTENSION_PATH.as_path()).await;
+
+    assert!(bindings1.is_ok());
+    assert!(bindings2.is_ok());
+}
+
+#[tokio::test]
+async fn test_runtime_instantiate_component() {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+
+    let component = runtime.compile_file(EXTENSION_PATH.as_path()).unwrap();
+
+    let bindings1 = runtime.instantiate(&component).await.unwrap();
+    let bindings2 = runtime.instantiate(&component).await.unwrap();
+
+    assert!(bindings1.initialize().await.is_ok());
+    assert!(bindings2.initialize().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_runtime_instantiate_pool() {
+    use std::num::NonZeroUsize;
+
+    let (mut bindings, _) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder().with_pool_size(NonZeroUsize::new(2).unwrap()),
+        vec![manga_list_response()],
+    )
+    .await;
+    assert_eq!(bindings.pool_size(), 2);
+
+    bindings.initialize().await.unwrap();
+
+    // Every instance shares the same settings
+    bindings.settings_mut().await.insert(
+        "key".to_string(),
+        midoku_settings::types::Value::String("value".to_string()),
+    );
+    assert_eq!(bindings.settings().await.len(), 1);
+
+    let (manga_list, manga_details) = tokio::join!(
+        bindings.get_manga_list(vec![], 0),
+        bindings.get_manga_details("manga_id".to_string()),
+    );
+    assert!(manga_list.is_ok());
+    assert!(manga_details.is_ok());
+}
+
+#[tokio::test]
+async fn test_runtime_cache() {
+    let cache_dir = std::env::temp_dir().join("midoku-bindings-test-runtime-cache");
+
+    let runtime = midoku_bindings::Runtime::new()
+        .unwrap()
+        .with_cache_dir(&cache_dir);
+    let cache = runtime.cache().unwrap();
+    cache.clear().unwrap();
+
+    let bytes = std::fs::read(EXTENSION_PATH.as_path()).unwrap();
+    let entry_path = cache.entry_path(runtime.engine(), &bytes);
+
+    assert!(!entry_path.exists());
+
+    // The first compilation stores the compiled component in the cache.
+    runtime.compile(&bytes).unwrap();
+    assert!(entry_path.exists());
+
+    // The second compilation loads the compiled component from the cache.
+    let component = runtime.compile(&bytes).unwrap();
+    let bindings = runtime.instantiate(&component).await.unwrap();
+    assert!(bindings.initialize().await.is_ok());
+
+    cache.clear().unwrap();
+    assert!(!entry_path.exists());
+}
+
+#[tokio::test]
+async fn test_bindings_from_file_not_found() {
+    let bindings = midoku_bindings::Bindings::from_file("not_found.wasm").await;
+
+    assert!(matches!(bindings, Err(midoku_bindings::Error::Io(_))));
+}
+
+#[tokio::test]
+async fn test_runtime_compile_invalid() {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+
+    let component = runtime.compile(&[0, 1, 2, 3]);
+
+    assert!(matches!(
+        component,
+        Err(midoku_bindings::Error::Instantiation(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_runtime_limits_fuel() {
+    let limits = midoku_bindings::Limits {
+        fuel: Some(1),
+        ..Default::default()
+    };
+    let runtime = midoku_bindings::Runtime::new()
+        .unwrap()
+        .with_limits(limits)
+        .unwrap();
+
+    let bindings = runtime
+        .instantiate_file(EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    let initialize = bindings.initialize().await;
+
+    assert!(matches!(
+        initialize,
+        Err(midoku_bindings::Error::ResourceLimit(
+            midoku_bindings::limits::ResourceLimit::Fuel
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_runtime_limits_memory_size() {
+    let limits = midoku_bindings::Limits {
+        memory_size: Some(1),
+        ..Default::default()
+    };
+    let runtime = midoku_bindings::Runtime::new()
+        .unwrap()
+        .with_limits(limits)
+        .unwrap();
+
+    let bindings = runtime.instantiate_file(EXTENSION_PATH.as_path()).await;
+
+    assert!(matches!(
+        bindings,
+        Err(midoku_bindings::Error::ResourceLimit(
+            midoku_bindings::limits::ResourceLimit::Memory
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_runtime_http_client() {
+    let http_client = midoku_http::client::HttpConfig {
+        connect_timeout: Some(std::time::Duration::from_secs(10)),
+        default_headers: vec![("user-agent".to_string(), "Midoku".to_string())],
+        ..Default::default()
+    }
+    .build()
+    .unwrap();
+    let runtime = midoku_bindings::Runtime::new()
+        .unwrap()
+        .with_http_client(http_client);
+
+    let bindings = runtime
+        .instantiate_file(EXTENSION_PATH.as_path())
+        .await
+        .unwrap();
+
+    assert!(bindings.initialize().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_record_replay_fixture() {
+    let runtime = midoku_bindings::Runtime::new().unwrap();
+    let component = runtime.compile_file(EXTENSION_PATH.as_path()).unwrap();
+    let path = std::env::temp_dir().join("midoku-bindings-test-record-replay.json");
+
+    // Record the exchanges of the source.
+    let mock = midoku_http::transport::MockTransport::new().with_response(
+        midoku_http::types::Method::Get,
+        "http://example.com/manga",
+        midoku_http::types::IncomingResponse::new(200, vec![], b"[]".to_vec()),
+    );
+    let recording = std::sync::Arc::new(midoku_http::fixture::RecordingTransport::new(
+        std::sync::Arc::new(mock),
+    ));
+
+    let bindings = midoku_bindings::Bindings::builder()
+        .with_transport(recording.clone())
+        .instantiate(&runtime, &component)
+        .await
+        .unwrap();
+    assert!(bindings.get_manga_list(vec![], 0).await.is_ok());
+    recording.save(&path).unwrap();
+
+    // Replay them.
+    let replay = std::sync::Arc::new(midoku_http::fixture::ReplayTransport::load(&path).unwrap());
+
+    let bindings = midoku_bindings::Bindings::builder()
+        .with_transport(replay.clone())
+        .instantiate(&runtime, &component)
+        .await
+        .unwrap();
+    assert!(bindings.get_manga_list(vec![], 0).await.is_ok());
+    assert!(replay.remaining().is_empty());
+
+    // The fixture holds a single exchange.
+    let res = bindings.get_manga_list(vec![], 0).await;
+    assert!(matches!(
+        res,
+        Err(midoku_bindings::Error::Guest(
+            midoku_types::error::SourceError::Network(_)
+        ))
+    ));
+    assert_eq!(replay.unexpected().len(), 1);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_bindings_cookie_jar() {
+    // Seed the jar, e.g. with the cookies of a login.
+    let url = midoku_http::outgoing_handler::Url::parse("http://example.com").unwrap();
+    let cookie_jar = std::sync::Arc::new(midoku_http::cookie::CookieJar::new());
+    cookie_jar.insert(midoku_http::cookie::Cookie::parse(&url, "login=1; Path=/").unwrap());
+
+    let (bindings, transport) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder().with_cookie_jar(cookie_jar),
+        vec![(
+            "http://example.com/manga",
+            midoku_http::types::IncomingResponse::new(
+                200,
+                vec![("set-cookie".to_string(), "session=abc; Path=/".to_string())],
+                b"[]".to_vec(),
+            ),
+        )],
+    )
+    .await;
+
+    bindings.get_manga_list(vec![], 0).await.unwrap();
+    bindings.get_manga_list(vec![], 0).await.unwrap();
+
+    let requests = transport.requests();
+    let cookie = |request: &midoku_http::transport::Request| {
+        request
+            .headers
+            .iter()
+            .find(|(name, _)| name == "cookie")
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(cookie(&requests[0]).as_deref(), Some("login=1"));
+    assert_eq!(
+        cookie(&requests[1]).as_deref(),
+        Some("login=1; session=abc")
+    );
+
+    // The cookies set by the source can be inspected and cleared.
+    assert_eq!(bindings.cookie_jar().cookies().len(), 2);
+    bindings.cookie_jar().clear();
+    assert!(bindings.cookie_jar().header(&url).is_none());
+}
+
+#[tokio::test]
+async fn test_bindings_download() {
+    let (bindings, _) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder()
+            .with_retry_policy(midoku_http::retry::RetryPolicy::disabled()),
+        vec![
+            (
+                "http://example.com/page.png",
+                midoku_http::types::IncomingResponse::new(200, vec![], vec![0x89, 0x50, 0x4e]),
+            ),
+            (
+                "http://example.com/missing.png",
+                midoku_http::types::IncomingResponse::new(404, vec![], vec![]),
+            ),
+        ],
+    )
+    .await;
+
+    let mut bytes = Vec::new();
+    let written = bindings
+        .download("http://example.com/page.png", &mut bytes)
+        .await
+        .unwrap();
+    assert_eq!(written, 3);
+    assert_eq!(bytes, vec![0x89, 0x50, 0x4e]);
+
+    let path = std::env::temp_dir().join("midoku-bindings-test-download.png");
+    bindings
+        .download_to_file("http://example.com/page.png", &path)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), vec![0x89, 0x50, 0x4e]);
+    std::fs::remove_file(&path).unwrap();
+
+    let res = bindings
+        .download("http://example.com/missing.png", &mut Vec::new())
+        .await;
+    assert!(matches!(res, Err(midoku_bindings::Error::Http(_))));
+
+    // Failed requests report their error.
+    let res = bindings
+        .download("http://example.com/unknown.png", &mut Vec::new())
+        .await;
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "HTTP error: request to http://example.com/unknown.png failed: \
+        no mock response for GET http://example.com/unknown.png"
+    );
+}
+
+#[tokio::test]
+async fn test_bindings_network_policy() {
+    let (bindings, transport) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder().with_network_policy(
+            midoku_http::policy::NetworkPolicy {
+                allowed_hosts: Some(vec!["example.com".to_string()]),
+                allow_private_addresses: true,
+            },
+        ),
+        vec![(
+            "http://example.com/page.png",
+            midoku_http::types::IncomingResponse::new(200, vec![], vec![0x89, 0x50, 0x4e]),
+        )],
+    )
+    .await;
+
+    let res = bindings
+        .download("http://example.com/page.png", &mut Vec::new())
+        .await;
+    assert_eq!(res.unwrap(), 3);
+
+    // Blocked requests are not sent.
+    let res = bindings
+        .download("http://192.168.1.1/page.png", &mut Vec::new())
+        .await;
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "HTTP error: request to http://192.168.1.1/page.png failed: \
+        request blocked: host `192.168.1.1` is not allowed"
+    );
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_bindings_network_policy_allowed_hosts() {
+    let (bindings, transport) =
+        instantiate_with_mock(midoku_bindings::Bindings::builder(), vec![]).await;
+
+    // The source only declares `example.com`.
+    let res = bindings
+        .download("http://example.org/page.png", &mut Vec::new())
+        .await;
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "HTTP error: request to http://example.org/page.png failed: \
+        request blocked: host `example.org` is not allowed"
+    );
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_bindings_retry() {
+    let (bindings, transport) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder()
+            .with_rate_limiter(midoku_limiter::rate_limiter::RateLimiter::default())
+            .with_retry_policy(midoku_http::retry::RetryPolicy {
+                max_attempts: 3,
+                initial_backoff: std::time::Duration::from_millis(1),
+                ..Default::default()
+            }),
+        vec![(
+            "http://example.com/page.png",
+            midoku_http::types::IncomingResponse::new(
+                503,
+                vec![("retry-after".to_string(), "0".to_string())],
+                vec![],
+            ),
+        )],
+    )
+    .await;
+
+    let res = bindings
+        .download("http://example.com/page.png", &mut Vec::new())
+        .await;
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "HTTP error: request to http://example.com/page.png returned status 503"
+    );
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_bindings_http_cache() {
+    let cache = std::sync::Arc::new(midoku_http::cache::HttpCache::new(
+        std::env::temp_dir().join("midoku-bindings-test-http-cache"),
+    ));
+    cache.clear().unwrap();
+
+    let (bindings, transport) = instantiate_with_mock(
+        midoku_bindings::Bindings::builder().with_http_cache(cache.clone()),
+        vec![(
+            "http://example.com/page.png",
+            midoku_http::types::IncomingResponse::new(
+                200,
+                vec![("cache-control".to_string(), "max-age=60".to_string())],
+                vec![0x89, 0x50, 0x4e],
+            ),
+        )],
+    )
+    .await;
+
+    for _ in 0..2 {
+        let mut bytes = Vec::new();
+        bindings
+            .download("http://example.com/page.png", &mut bytes)
+            .await
+            .unwrap();
+        assert_eq!(bytes, vec![0x89, 0x50, 0x4e]);
+    }
+    assert_eq!(transport.requests().len(), 1);
+
+    cache.force_refresh();
+    bindings
+        .download("http://example.com/page.png", &mut Vec::new())
+        .await
+        .unwrap();
+    assert_eq!(transport.requests().len(), 2);
+
+    cache.clear().unwrap();
+}
@@ -0,0 +1,972 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::transport::{HttpTransport, Request, SendFuture};
+use crate::types::{decode_header_value, HttpError, IncomingResponse, Method};
+
+/// File extension of the cache entries.
+const EXTENSION: &str = "entry";
+
+/// Default maximum size in bytes of the body of an entry, see
+/// [`HttpCache::with_max_entry_size`].
+pub const DEFAULT_MAX_ENTRY_SIZE: u64 = 8 << 20;
+
+/// Request headers making a request conditional or partial, which the cache
+/// does not answer itself.
+const CONDITIONAL_HEADERS: &[&str] = &[
+    "if-none-match",
+    "if-modified-since",
+    "if-match",
+    "if-unmodified-since",
+    "if-range",
+    "range",
+];
+
+/// Request headers holding the credentials of the source. Responses are
+/// cached per value of these headers.
+pub(crate) const CREDENTIAL_HEADERS: &[&str] = &["authorization", "cookie"];
+
+/// Response headers not stored in the cache, as replaying them would e.g.
+/// restore a session cleared from the cookie jar.
+pub(crate) const UNSTORED_HEADERS: &[&str] = &["set-cookie", "set-cookie2"];
+
+/// Number of entries written so far, to give each write its own temporary
+/// file.
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// An on-disk cache of HTTP responses, following the rules of a private cache
+/// of RFC 9111.
+///
+/// Only successful responses to `GET` requests are stored, when their
+/// `Cache-Control` or `Expires` header makes them fresh for a while, or when
+/// they have an `ETag` or `Last-Modified` header to revalidate them with.
+/// Stale entries are revalidated with a conditional request, which the server
+/// answers with `304 Not Modified` if the entry is still valid.
+///
+/// Responses are stored per URL and per credentials of the request, so that
+/// a response received before a login is not served after it. `Set-Cookie`
+/// headers are not stored.
+///
+/// The cache is used through a [`CachingTransport`].
+///
+/// # Example
+/// ```ignore
+/// let cache = Arc::new(HttpCache::new(data_dir.join("http-cache")).with_max_size(64 << 20));
+///
+/// let bindings = Bindings::builder()
+///     .with_http_cache(cache.clone())
+///     .instantiate(&runtime, &component)
+///     .await?;
+///
+/// // On a pull-to-refresh, revalidate every response with the server.
+/// cache.force_refresh();
+/// ```
+#[derive(Debug)]
+pub struct HttpCache {
+    dir: PathBuf,
+    max_size: Option<u64>,
+    max_entry_size: u64,
+    /// Entries stored before this time, in milliseconds since the Unix epoch,
+    /// are stale.
+    refreshed_at: AtomicU64,
+}
+
+impl HttpCache {
+    /// Create a new cache storing its entries in `dir`.
+    ///
+    /// The directory is created when the first entry is written.
+    pub fn new<T: Into<PathBuf>>(dir: T) -> Self {
+        Self {
+            dir: dir.into(),
+            max_size: None,
+            max_entry_size: DEFAULT_MAX_ENTRY_SIZE,
+            refreshed_at: AtomicU64::new(0),
+        }
+    }
+
+    /// Limit the total size in bytes of the entries.
+    ///
+    /// The least recently stored entries are removed when the cache grows
+    /// past this size, and larger responses are not stored.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Limit the size in bytes of the body of an entry, defaulting to
+    /// [`DEFAULT_MAX_ENTRY_SIZE`].
+    ///
+    /// Bodies are read into memory to be stored, larger ones are streamed to
+    /// the source without storing them.
+    pub fn with_max_entry_size(mut self, max_entry_size: u64) -> Self {
+        self.max_entry_size = max_entry_size;
+        self
+    }
+
+    /// Returns the directory where the cache entries are stored.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the maximum total size in bytes of the entries, if any.
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Returns the maximum size in bytes of the body of an entry.
+    pub fn max_entry_size(&self) -> u64 {
+        self.max_entry_size
+    }
+
+    /// Consider every entry stored so far stale, so that the next request
+    /// for each of them is revalidated with the server.
+    pub fn force_refresh(&self) {
+        self.refreshed_at.store(unix_time_ms(), Ordering::Relaxed);
+    }
+
+    /// Returns the total size in bytes of the entries.
+    pub fn size(&self) -> io::Result<u64> {
+        Ok(self.entries()?.iter().map(|(_, len, _)| len).sum())
+    }
+
+    /// Remove every entry from the cache.
+    pub fn clear(&self) -> io::Result<()> {
+        for (path, _, _) in self.entries()? {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path of the entry for `request`, keyed by its URL and its
+    /// credentials.
+    fn entry_path(&self, request: &Request) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(request.url.as_bytes());
+        let headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| (name, value.as_bytes()))
+            .chain(
+                request
+                    .raw_headers
+                    .iter()
+                    .map(|(name, value)| (name, value.as_slice())),
+            );
+        for (name, value) in headers {
+            if is_one_of(name, CREDENTIAL_HEADERS) {
+                hasher.update(format!("\n{}: ", name.to_ascii_lowercase()));
+                hasher.update(value);
+            }
+        }
+
+        let key: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.dir.join(key).with_extension(EXTENSION)
+    }
+
+    /// Returns the path, size and modification time of every entry.
+    fn entries(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let dir = match fs::read_dir(&self.dir) {
+            Ok(dir) => dir,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut entries = Vec::new();
+        for entry in dir {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION)
+            {
+                let metadata = fs::metadata(&path)?;
+                entries.push((path, metadata.len(), metadata.modified()?));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Read the entry at `path`, stored for a request to `url`.
+    ///
+    /// Entries that cannot be read, e.g. truncated files, are ignored.
+    fn load(&self, path: &Path, url: &str) -> Option<CacheEntry> {
+        let mut bytes = Vec::new();
+        File::open(path).ok()?.read_to_end(&mut bytes).ok()?;
+
+        // The metadata is a line of JSON followed by the body.
+        let split = bytes.iter().position(|&byte| byte == b'\n')?;
+        let mut entry: CacheEntry = serde_json::from_slice(&bytes[..split]).ok()?;
+        if entry.url != url {
+            return None;
+        }
+
+        entry.body = bytes.split_off(split + 1);
+        if entry.body.len() as u64 != entry.body_len {
+            return None;
+        }
+
+        Some(entry)
+    }
+
+    /// Write the entry at `path`, then remove the oldest entries if the
+    /// cache is too large.
+    ///
+    /// The entry is written to a temporary file first, so that it is never
+    /// read while it is being written.
+    fn store(&self, path: &Path, entry: &CacheEntry) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let write = WRITES.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = path.with_extension(format!("{}.{write}.tmp", std::process::id()));
+        let result = File::create(&tmp_path).and_then(|mut file| {
+            serde_json::to_writer(&mut file, entry)?;
+            file.write_all(b"\n")?;
+            file.write_all(&entry.body)?;
+            file.flush()
+        });
+        if let Err(err) = result.and_then(|()| fs::rename(&tmp_path, path)) {
+            _ = fs::remove_file(&tmp_path);
+            return Err(err);
+        }
+
+        self.evict()
+    }
+
+    /// Remove the entry at `path`, if any.
+    fn remove(&self, path: &Path) {
+        _ = fs::remove_file(path);
+    }
+
+    /// Remove the least recently stored entries until the cache fits in its
+    /// maximum size.
+    fn evict(&self) -> io::Result<()> {
+        let Some(max_size) = self.max_size else {
+            return Ok(());
+        };
+
+        let mut entries = self.entries()?;
+        let mut size: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        entries.sort_by_key(|(_, _, modified)| *modified);
+
+        for (path, len, _) in entries {
+            if size <= max_size {
+                break;
+            }
+
+            fs::remove_file(path)?;
+            size -= len;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the entry can be served without revalidating it.
+    fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        if entry.stored_at <= self.refreshed_at.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        let age = Duration::from_millis(unix_time_ms().saturating_sub(entry.stored_at))
+            + header(&entry.headers, "age")
+                .and_then(|age| age.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or_default();
+
+        freshness_lifetime(&entry.headers).is_some_and(|lifetime| age < lifetime)
+    }
+}
+
+/// A response stored in the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// URL of the request.
+    url: String,
+    /// URL the response was received from, after following redirects.
+    final_url: Option<String>,
+    status_code: u16,
+    /// Headers of the response, without [`UNSTORED_HEADERS`].
+    headers: Vec<(String, String)>,
+    /// Headers of the response as received, only stored if a value is not
+    /// valid UTF-8. They take precedence over `headers` when served.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_headers: Option<Vec<(String, Vec<u8>)>>,
+    /// Time the response was received or last revalidated, in milliseconds
+    /// since the Unix epoch.
+    stored_at: u64,
+    /// Length of the body, to detect an entry that was not fully written.
+    body_len: u64,
+    #[serde(skip)]
+    body: Vec<u8>,
+}
+
+impl CacheEntry {
+    fn new(url: String, response: &IncomingResponse, body: Vec<u8>) -> Self {
+        let mut entry = Self {
+            url,
+            final_url: response.url().map(str::to_string),
+            status_code: response.status_code(),
+            headers: Vec::new(),
+            raw_headers: None,
+            stored_at: unix_time_ms(),
+            body_len: body.len() as u64,
+            body,
+        };
+        entry.set_headers(without_unstored_headers(response.raw_headers().clone()));
+        entry
+    }
+
+    /// Returns the headers of the response as received.
+    fn raw_headers(&self) -> Vec<(String, Vec<u8>)> {
+        match &self.raw_headers {
+            Some(raw_headers) => raw_headers.clone(),
+            None => self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone().into_bytes()))
+                .collect(),
+        }
+    }
+
+    /// Set the headers of the response as received, keeping their bytes if a
+    /// value is not valid UTF-8.
+    fn set_headers(&mut self, raw_headers: Vec<(String, Vec<u8>)>) {
+        self.headers = raw_headers
+            .iter()
+            .map(|(name, value)| (name.clone(), decode_header_value(value)))
+            .collect();
+        let is_utf8 = raw_headers
+            .iter()
+            .all(|(_, value)| std::str::from_utf8(value).is_ok());
+        self.raw_headers = (!is_utf8).then_some(raw_headers);
+    }
+
+    fn to_response(&self) -> IncomingResponse {
+        IncomingResponse::new(self.status_code, vec![], self.body.clone())
+            .with_raw_headers(self.raw_headers())
+            .with_url(self.final_url.clone())
+    }
+}
+
+/// Transport answering requests from an [`HttpCache`] when possible, and
+/// sending them through another transport otherwise.
+///
+/// Responses stored in the cache are read in full before being returned, up
+/// to [`HttpCache::max_entry_size`]. The entries are read and written on the
+/// blocking threads of the runtime. Requests other than `GET`, e.g. `POST`, are never answered from the cache
+/// and remove the entry of their URL, as they may change the resource.
+pub struct CachingTransport {
+    inner: Arc<dyn HttpTransport>,
+    cache: Arc<HttpCache>,
+}
+
+impl CachingTransport {
+    pub fn new(inner: Arc<dyn HttpTransport>, cache: Arc<HttpCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    pub fn cache(&self) -> &Arc<HttpCache> {
+        &self.cache
+    }
+
+    /// Run `f` on a blocking thread, as the cache reads and writes its
+    /// entries with [`std::fs`].
+    ///
+    /// Returns `None` if `f` panicked.
+    async fn with_cache<T, F>(&self, f: F) -> Option<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&HttpCache) -> T + Send + 'static,
+    {
+        let cache = self.cache.clone();
+        tokio::task::spawn_blocking(move || f(&cache)).await.ok()
+    }
+
+    async fn send_cached(&self, mut request: Request) -> Result<IncomingResponse, HttpError> {
+        let path = self.cache.entry_path(&request);
+        let request_directives = cache_control(&request.headers);
+        let entry = match request_directives.no_cache {
+            true => None,
+            false => {
+                let (path, url) = (path.clone(), request.url.clone());
+                self.with_cache(move |cache| cache.load(&path, &url))
+                    .await
+                    .flatten()
+            }
+        };
+
+        if let Some(entry) = &entry {
+            if self.cache.is_fresh(entry) {
+                return Ok(entry.to_response());
+            }
+
+            // Ask the server to only send the response if it changed.
+            if let Some(etag) = header(&entry.headers, "etag") {
+                request
+                    .headers
+                    .push(("if-none-match".to_string(), etag.to_string()));
+            }
+            if let Some(last_modified) = header(&entry.headers, "last-modified") {
+                request
+                    .headers
+                    .push(("if-modified-since".to_string(), last_modified.to_string()));
+            }
+        }
+
+        let url = request.url.clone();
+        let response = self.inner.send(request).await?;
+
+        if let (304, Some(mut entry)) = (response.status_code(), entry) {
+            // The entry is still valid, with the updated headers of the
+            // server.
+            let mut headers = entry.raw_headers();
+            for (name, value) in without_unstored_headers(response.raw_headers().clone()) {
+                headers.retain(|(entry_name, _)| !entry_name.eq_ignore_ascii_case(&name));
+                headers.push((name, value));
+            }
+            entry.set_headers(headers);
+            entry.stored_at = unix_time_ms();
+
+            let cached = entry.to_response();
+            self.with_cache(move |cache| cache.store(&path, &entry))
+                .await;
+            return Ok(with_unstored_headers(cached, &response));
+        }
+
+        if request_directives.no_store || !is_cacheable(&response) {
+            return Ok(response);
+        }
+
+        // Responses larger than an entry are streamed without storing them.
+        let max_entry_size = match self.cache.max_size {
+            Some(max_size) => max_size.min(self.cache.max_entry_size),
+            None => self.cache.max_entry_size,
+        };
+        let content_length = header(response.headers(), "content-length")
+            .and_then(|content_length| content_length.parse::<u64>().ok());
+        if content_length.is_some_and(|content_length| content_length > max_entry_size) {
+            return Ok(response);
+        }
+
+        // A body found to be too large while reading it keeps the bytes read
+        // so far, the source streams it from there.
+        let max_body_size = usize::try_from(max_entry_size).unwrap_or(usize::MAX);
+        let mut response = response.with_max_body_size(Some(max_body_size));
+        let body = match response.bytes().await {
+            Ok(body) => body.to_vec(),
+            Err(HttpError::BodyTooLarge) => return Ok(response.with_max_body_size(None)),
+            Err(err) => return Err(err),
+        };
+
+        let entry = CacheEntry::new(url, &response, body);
+        let cached = entry.to_response();
+        self.with_cache(move |cache| cache.store(&path, &entry))
+            .await;
+
+        Ok(with_unstored_headers(cached, &response))
+    }
+}
+
+impl HttpTransport for CachingTransport {
+    fn send(&self, request: Request) -> SendFuture<'_> {
+        let is_conditional = request
+            .headers
+            .iter()
+            .any(|(name, _)| is_one_of(name, CONDITIONAL_HEADERS));
+
+        match request.method {
+            Method::Get if !is_conditional => Box::pin(self.send_cached(request)),
+            Method::Get | Method::Head | Method::Options | Method::Trace => {
+                self.inner.send(request)
+            }
+            _ => Box::pin(async move {
+                let path = self.cache.entry_path(&request);
+                self.with_cache(move |cache| cache.remove(&path)).await;
+                self.inner.send(request).await
+            }),
+        }
+    }
+}
+
+/// Directives of a `Cache-Control` header used by the cache.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+/// Parse the `Cache-Control` headers.
+fn cache_control(headers: &[(String, String)]) -> CacheControl {
+    let mut cache_control = CacheControl::default();
+
+    let directives = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("cache-control"))
+        .flat_map(|(_, value)| value.split(','));
+    for directive in directives {
+        let (name, value) = match directive.split_once('=') {
+            Some((name, value)) => (name, Some(value.trim().trim_matches('"'))),
+            None => (directive, None),
+        };
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "no-store" => cache_control.no_store = true,
+            "no-cache" => cache_control.no_cache = true,
+            "max-age" => cache_control.max_age = value.and_then(|value| value.parse().ok()),
+            _ => {}
+        }
+    }
+
+    cache_control
+}
+
+/// Returns how long a response stays fresh after it was received, or `None`
+/// if it must be revalidated before each use.
+fn freshness_lifetime(headers: &[(String, String)]) -> Option<Duration> {
+    let cache_control = cache_control(headers);
+    if cache_control.no_cache {
+        return None;
+    }
+    if let Some(max_age) = cache_control.max_age {
+        return Some(Duration::from_secs(max_age));
+    }
+
+    let expires = httpdate::parse_http_date(header(headers, "expires")?).ok()?;
+    let date = header(headers, "date")
+        .and_then(|date| httpdate::parse_http_date(date).ok())
+        .unwrap_or_else(SystemTime::now);
+    expires.duration_since(date).ok()
+}
+
+/// Returns `true` if the response can be stored in the cache.
+fn is_cacheable(response: &IncomingResponse) -> bool {
+    let headers = response.headers();
+
+    // Responses varying with other request headers than the encoding would
+    // need to be stored once per variant.
+    let varies = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("vary"))
+        .flat_map(|(_, value)| value.split(','))
+        .any(|field| !field.trim().eq_ignore_ascii_case("accept-encoding"));
+
+    let has_validator =
+        header(headers, "etag").is_some() || header(headers, "last-modified").is_some();
+
+    response.status_code() == 200
+        && !varies
+        && !cache_control(headers).no_store
+        && (has_validator
+            || freshness_lifetime(headers).is_some_and(|lifetime| !lifetime.is_zero()))
+}
+
+/// Returns `headers` without [`UNSTORED_HEADERS`].
+fn without_unstored_headers(mut headers: Vec<(String, Vec<u8>)>) -> Vec<(String, Vec<u8>)> {
+    headers.retain(|(name, _)| !is_one_of(name, UNSTORED_HEADERS));
+    headers
+}
+
+/// Add the [`UNSTORED_HEADERS`] of `received`, the response received from
+/// the server, to a response served from the cache.
+fn with_unstored_headers(
+    response: IncomingResponse,
+    received: &IncomingResponse,
+) -> IncomingResponse {
+    let mut raw_headers = response.raw_headers().clone();
+    raw_headers.extend(
+        received
+            .raw_headers()
+            .iter()
+            .filter(|(name, _)| is_one_of(name, UNSTORED_HEADERS))
+            .cloned(),
+    );
+    response.with_raw_headers(raw_headers)
+}
+
+pub(crate) fn is_one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|other| name.eq_ignore_ascii_case(other))
+}
+
+/// Returns the value of the first header named `name`.
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn unix_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    const URL: &str = "https://example.com/manga/1";
+
+    /// Transport answering requests with the given responses, in order.
+    #[derive(Default)]
+    struct QueueTransport {
+        responses: Mutex<VecDeque<IncomingResponse>>,
+        requests: Mutex<Vec<Request>>,
+    }
+
+    impl QueueTransport {
+        fn push(&self, status_code: u16, headers: &[(&str, &str)], body: &[u8]) {
+            let headers = headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            self.responses
+                .lock()
+                .unwrap()
+                .push_back(IncomingResponse::new(status_code, headers, body.to_vec()));
+        }
+
+        fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl HttpTransport for QueueTransport {
+        fn send(&self, request: Request) -> SendFuture<'_> {
+            self.requests.lock().unwrap().push(request);
+            let response = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| HttpError::Connect("no response".to_string()));
+            Box::pin(std::future::ready(response))
+        }
+    }
+
+    fn request(method: Method) -> Request {
+        Request {
+            method,
+            url: URL.to_string(),
+            headers: vec![],
+            raw_headers: vec![],
+            body: None,
+        }
+    }
+
+    fn load(cache: &HttpCache, url: &str) -> Option<CacheEntry> {
+        let mut request = request(Method::Get);
+        request.url = url.to_string();
+        cache.load(&cache.entry_path(&request), url)
+    }
+
+    fn setup(
+        name: &str,
+        max_size: Option<u64>,
+    ) -> (Arc<QueueTransport>, Arc<HttpCache>, CachingTransport) {
+        let dir = std::env::temp_dir().join(format!("midoku-http-test-cache-{name}"));
+        _ = fs::remove_dir_all(&dir);
+
+        let inner = Arc::new(QueueTransport::default());
+        let mut cache = HttpCache::new(dir);
+        cache.max_size = max_size;
+        let cache = Arc::new(cache);
+        let transport = CachingTransport::new(inner.clone(), cache.clone());
+        (inner, cache, transport)
+    }
+
+    #[test]
+    fn test_cache_control() {
+        let headers = vec![(
+            "Cache-Control".to_string(),
+            "public, max-age=\"60\", No-Cache".to_string(),
+        )];
+        assert_eq!(
+            cache_control(&headers),
+            CacheControl {
+                no_store: false,
+                no_cache: true,
+                max_age: Some(60),
+            }
+        );
+        assert_eq!(cache_control(&[]), CacheControl::default());
+    }
+
+    #[test]
+    fn test_freshness_lifetime() {
+        let headers = vec![("cache-control".to_string(), "max-age=60".to_string())];
+        assert_eq!(freshness_lifetime(&headers), Some(Duration::from_secs(60)));
+
+        let headers = vec![
+            (
+                "date".to_string(),
+                "Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+            ),
+            (
+                "expires".to_string(),
+                "Wed, 21 Oct 2015 08:28:00 GMT".to_string(),
+            ),
+        ];
+        assert_eq!(
+            freshness_lifetime(&headers),
+            Some(Duration::from_secs(3600))
+        );
+
+        let headers = vec![(
+            "cache-control".to_string(),
+            "no-cache, max-age=60".to_string(),
+        )];
+        assert_eq!(freshness_lifetime(&headers), None);
+        assert_eq!(freshness_lifetime(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_fresh() {
+        let (inner, cache, transport) = setup("fresh", None);
+        inner.push(200, &[("cache-control", "max-age=60")], b"Hello");
+
+        for _ in 0..2 {
+            let mut response = transport.send(request(Method::Get)).await.unwrap();
+            assert_eq!(response.bytes().await.unwrap(), b"Hello");
+        }
+        assert_eq!(inner.requests().len(), 1);
+
+        // Forcing a refresh sends the request again.
+        cache.force_refresh();
+        inner.push(200, &[("cache-control", "max-age=60")], b"World");
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"World");
+        assert_eq!(inner.requests().len(), 2);
+
+        cache.clear().unwrap();
+        assert_eq!(cache.size().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_revalidate() {
+        let (inner, cache, transport) = setup("revalidate", None);
+        inner.push(
+            200,
+            &[
+                ("etag", "\"v1\""),
+                ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            ],
+            b"Hello",
+        );
+        inner.push(304, &[("cache-control", "max-age=60")], b"");
+
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"Hello");
+
+        // The stale entry is revalidated, and served with the new headers.
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.bytes().await.unwrap(), b"Hello");
+        assert_eq!(
+            header(response.headers(), "cache-control"),
+            Some("max-age=60")
+        );
+
+        let requests = inner.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            header(&requests[1].headers, "if-none-match"),
+            Some("\"v1\"")
+        );
+        assert_eq!(
+            header(&requests[1].headers, "if-modified-since"),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+
+        // The revalidated entry is fresh.
+        assert!(transport.send(request(Method::Get)).await.is_ok());
+        assert_eq!(inner.requests().len(), 2);
+
+        cache.clear().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_not_cacheable() {
+        let (inner, cache, transport) = setup("not-cacheable", None);
+        inner.push(200, &[("cache-control", "no-store, max-age=60")], b"");
+        inner.push(200, &[], b"");
+        inner.push(404, &[("cache-control", "max-age=60")], b"");
+        inner.push(
+            200,
+            &[("cache-control", "max-age=60"), ("vary", "cookie")],
+            b"",
+        );
+
+        for _ in 0..4 {
+            transport.send(request(Method::Get)).await.unwrap();
+        }
+        assert_eq!(cache.size().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_invalidate() {
+        let (inner, cache, transport) = setup("invalidate", None);
+        inner.push(200, &[("cache-control", "max-age=60")], b"Hello");
+        inner.push(200, &[], b"");
+        inner.push(200, &[], b"World");
+
+        transport.send(request(Method::Get)).await.unwrap();
+        assert!(cache.size().unwrap() > 0);
+
+        // A POST may change the resource.
+        transport.send(request(Method::Post)).await.unwrap();
+        assert_eq!(cache.size().unwrap(), 0);
+
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"World");
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_max_size() {
+        let (inner, cache, transport) = setup("max-size", Some(1024));
+
+        // Too large to be stored.
+        inner.push(200, &[("cache-control", "max-age=60")], &[0; 2048]);
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap().len(), 2048);
+        assert_eq!(cache.size().unwrap(), 0);
+
+        // The oldest entries are evicted.
+        for i in 0..4 {
+            inner.push(200, &[("cache-control", "max-age=60")], &[0; 400]);
+            let mut request = request(Method::Get);
+            request.url = format!("{URL}/{i}");
+            transport.send(request).await.unwrap();
+        }
+        assert!(cache.size().unwrap() <= 1024);
+        assert!(load(&cache, &format!("{URL}/3")).is_some());
+        assert!(load(&cache, &format!("{URL}/0")).is_none());
+
+        cache.clear().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_max_entry_size() {
+        let (inner, cache, transport) = setup("max-entry-size", None);
+        let transport = CachingTransport::new(
+            inner.clone(),
+            Arc::new(HttpCache::new(cache.dir()).with_max_entry_size(1024)),
+        );
+
+        // The body read so far is kept when it is found too large.
+        inner.push(200, &[("cache-control", "max-age=60")], &[1; 2048]);
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), [1; 2048]);
+        assert_eq!(cache.size().unwrap(), 0);
+
+        inner.push(200, &[("cache-control", "max-age=60")], &[1; 1024]);
+        transport.send(request(Method::Get)).await.unwrap();
+        assert!(load(&cache, URL).is_some());
+
+        cache.clear().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_raw_headers() {
+        let (inner, cache, transport) = setup("raw-headers", None);
+        inner.push(200, &[("cache-control", "max-age=60")], b"Hello");
+        let response = inner.responses.lock().unwrap().pop_front().unwrap();
+        let mut raw_headers = response.raw_headers().clone();
+        raw_headers.push(("x-title".to_string(), b"caf\xe9".to_vec()));
+        inner
+            .responses
+            .lock()
+            .unwrap()
+            .push_back(response.with_raw_headers(raw_headers));
+
+        transport.send(request(Method::Get)).await.unwrap();
+        let response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(inner.requests().len(), 1);
+        assert_eq!(response.raw_headers()[1].1, b"caf\xe9");
+        assert_eq!(header(response.headers(), "x-title"), Some("café"));
+
+        cache.clear().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_truncated_entry() {
+        let (inner, cache, transport) = setup("truncated", None);
+        inner.push(200, &[("cache-control", "max-age=60")], b"Hello");
+        inner.push(200, &[("cache-control", "max-age=60")], b"Hello");
+
+        transport.send(request(Method::Get)).await.unwrap();
+        assert!(load(&cache, URL).is_some());
+
+        // An entry whose body was not fully written is not served.
+        let path = cache.entry_path(&request(Method::Get));
+        let len = fs::metadata(&path).unwrap().len();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 2)
+            .unwrap();
+        assert!(load(&cache, URL).is_none());
+
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"Hello");
+        assert_eq!(inner.requests().len(), 2);
+
+        cache.clear().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_caching_transport_cookies() {
+        let (inner, cache, transport) = setup("cookies", None);
+        inner.push(
+            200,
+            &[("cache-control", "max-age=60"), ("set-cookie", "session=1")],
+            b"Guest",
+        );
+        inner.push(200, &[("cache-control", "max-age=60")], b"User");
+
+        // The cookies set by the server reach the source, but are not stored.
+        let response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(header(response.headers(), "set-cookie"), Some("session=1"));
+        let response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(header(response.headers(), "set-cookie"), None);
+        assert_eq!(inner.requests().len(), 1);
+
+        // Responses are cached per cookie.
+        let mut logged_in = request(Method::Get);
+        logged_in
+            .headers
+            .push(("Cookie".to_string(), "session=1".to_string()));
+        let mut response = transport.send(logged_in.clone()).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"User");
+        let mut response = transport.send(logged_in).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"User");
+        let mut logged_in = request(Method::Get);
+        logged_in
+            .raw_headers
+            .push(("cookie".to_string(), b"session=1".to_vec()));
+        let mut response = transport.send(logged_in).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"User");
+        let mut response = transport.send(request(Method::Get)).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"Guest");
+        assert_eq!(inner.requests().len(), 2);
+
+        cache.clear().unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{redirect, Client, ClientBuilder, Proxy};
+
+use crate::dns::Resolver;
+use crate::policy::NetworkPolicy;
+
+/// Maximum number of redirects followed by the host, whatever the redirect
+/// policy of the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Never follow redirects, the redirect response is returned as is.
+    None,
+    /// Follow up to the given number of redirects.
+    Limited(usize),
+}
+
+impl RedirectPolicy {
+    /// Returns the number of redirects to follow for a request with the
+    /// redirect policy `requested`, or `None` if redirects are not followed.
+    pub fn max_redirects(&self, requested: crate::types::RedirectPolicy) -> Option<usize> {
+        match self {
+            Self::None => None,
+            Self::Limited(max) => Some((*max).min(requested.max_redirects()? as usize)),
+        }
+    }
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        // Same as reqwest.
+        Self::Limited(10)
+    }
+}
+
+/// Configuration of the HTTP client used to send the requests of sources.
+///
+/// The client does not follow redirects, the host follows them as requested
+/// by each request, see [`RedirectPolicy`](crate::types::RedirectPolicy).
+///
+/// # Example
+/// ```ignore
+/// let client = HttpConfig {
+///     connect_timeout: Some(Duration::from_secs(10)),
+///     default_headers: vec![("user-agent".to_string(), "Midoku".to_string())],
+///     ..Default::default()
+/// }
+/// .build()?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpConfig {
+    /// Timeout of the connection to the server.
+    pub connect_timeout: Option<Duration>,
+
+    /// Timeout of each read of the response.
+    pub read_timeout: Option<Duration>,
+
+    /// Timeout of the whole request, from connecting to reading the body.
+    pub timeout: Option<Duration>,
+
+    /// URL of the proxy every request goes through, e.g.
+    /// `http://127.0.0.1:8080`.
+    pub proxy: Option<String>,
+
+    /// Maximum number of redirects followed by the host. A request asking to
+    /// follow more fails with
+    /// [`HttpError::TooManyRedirects`](crate::types::HttpError::TooManyRedirects).
+    pub redirect: RedirectPolicy,
+
+    /// Headers sent with every request, unless the request sets them.
+    pub default_headers: Vec<(String, String)>,
+}
+
+impl HttpConfig {
+    /// Build a client with this configuration.
+    ///
+    /// The client holds a connection pool, it should be built once and reused
+    /// for every request.
+    pub fn build(&self) -> Result<Client, ConfigError> {
+        self.builder()?
+            .dns_resolver(Arc::new(Resolver {
+                allow_private_addresses: true,
+            }))
+            .build()
+            .map_err(ConfigError::Client)
+    }
+
+    /// Build a client with this configuration, enforcing `network_policy`
+    /// when connecting.
+    ///
+    /// If the policy denies private addresses, the client fails to connect to
+    /// a host resolving to private addresses only, with
+    /// [`HttpError::Blocked`](crate::types::HttpError::Blocked). Requests
+    /// through a proxy cannot be checked, as the proxy resolves the hosts, so
+    /// this returns [`ConfigError::UnenforceablePolicy`] if a proxy is set.
+    pub fn build_with_policy(&self, network_policy: &NetworkPolicy) -> Result<Client, ConfigError> {
+        if network_policy.allow_private_addresses {
+            return self.build();
+        }
+        if self.proxy.is_some() {
+            return Err(ConfigError::UnenforceablePolicy);
+        }
+
+        self.builder()?
+            // Ignore the proxy of the system as well.
+            .no_proxy()
+            .dns_resolver(Arc::new(Resolver {
+                allow_private_addresses: false,
+            }))
+            .build()
+            .map_err(ConfigError::Client)
+    }
+
+    fn builder(&self) -> Result<ClientBuilder, ConfigError> {
+        let mut builder = Client::builder().redirect(redirect::Policy::none());
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(read_timeout) = self.read_timeout {
+            builder = builder.read_timeout(read_timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy).map_err(|_| ConfigError::InvalidProxy(proxy.clone()))?;
+            builder = builder.proxy(proxy);
+        }
+
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in &self.default_headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| ConfigError::InvalidHeader(name.clone()))?;
+            let header_value = HeaderValue::from_str(value)
+                .map_err(|_| ConfigError::InvalidHeader(name.clone()))?;
+            default_headers.append(header_name, header_value);
+        }
+
+        Ok(builder.default_headers(default_headers))
+    }
+}
+
+/// Errors returned when building a client from an [`HttpConfig`].
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The proxy URL is invalid.
+    InvalidProxy(String),
+    /// The name or the value of a default header is invalid.
+    InvalidHeader(String),
+    /// The network policy cannot be enforced by the client, e.g. because the
+    /// requests go through a proxy, see [`HttpConfig::build_with_policy`].
+    UnenforceablePolicy,
+    /// The client could not be built, e.g. the TLS backend failed to
+    /// initialize.
+    Client(reqwest::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProxy(proxy) => write!(f, "invalid proxy `{proxy}`"),
+            Self::InvalidHeader(name) => write!(f, "invalid header `{name}`"),
+            Self::UnenforceablePolicy => {
+                write!(f, "the network policy cannot be enforced through a proxy")
+            }
+            Self::Client(err) => write!(f, "failed to build the HTTP client: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Client(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_config_build() {
+        let config = HttpConfig {
+            connect_timeout: Some(Duration::from_secs(10)),
+            read_timeout: Some(Duration::from_secs(30)),
+            timeout: Some(Duration::from_secs(60)),
+            proxy: Some("http://127.0.0.1:8080".to_string()),
+            redirect: RedirectPolicy::None,
+            default_headers: vec![("User-Agent".to_string(), "Midoku".to_string())],
+        };
+
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn test_http_config_invalid_header() {
+        let config = HttpConfig {
+            default_headers: vec![("invalid header".to_string(), "value".to_string())],
+            ..Default::default()
+        };
+
+        let err = config.build().unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidHeader(name) if name == "invalid header"));
+    }
+
+    #[test]
+    fn test_http_config_invalid_proxy() {
+        let config = HttpConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+
+        let err = config.build().unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidProxy(_)));
+    }
+
+    #[tokio::test]
+    async fn test_http_config_build_with_policy() {
+        let config = HttpConfig::default();
+        let network_policy = NetworkPolicy {
+            allowed_hosts: None,
+            allow_private_addresses: false,
+        };
+
+        // The resolved addresses are checked when connecting.
+        let client = config.build_with_policy(&network_policy).unwrap();
+        let err = client.get("http://localhost:1").send().await.unwrap_err();
+        let err = crate::types::HttpError::from(err);
+        assert!(
+            matches!(&err, crate::types::HttpError::Blocked(reason) if reason.starts_with("host `localhost` resolves to the private address")),
+            "{err:?}"
+        );
+
+        let config = HttpConfig {
+            proxy: Some("http://127.0.0.1:8080".to_string()),
+            ..Default::default()
+        };
+        let err = config.build_with_policy(&network_policy).unwrap_err();
+        assert!(matches!(err, ConfigError::UnenforceablePolicy));
+        assert!(config.build_with_policy(&NetworkPolicy::default()).is_ok());
+    }
+
+    #[test]
+    fn test_redirect_policy_max_redirects() {
+        use crate::types::RedirectPolicy as RequestedPolicy;
+
+        let policy = RedirectPolicy::Limited(3);
+        assert_eq!(policy.max_redirects(RequestedPolicy::Follow), Some(3));
+        assert_eq!(policy.max_redirects(RequestedPolicy::Limited(1)), Some(1));
+        assert_eq!(policy.max_redirects(RequestedPolicy::None), None);
+
+        let policy = RedirectPolicy::None;
+        assert_eq!(policy.max_redirects(RequestedPolicy::Follow), None);
+    }
+}
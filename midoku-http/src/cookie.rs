@@ -0,0 +1,507 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Public suffixes of more than one label, under which anyone can register a
+/// domain, e.g. `co.uk`. Domains of a single label, e.g. `com`, are public
+/// suffixes too.
+///
+/// This is a subset of the [Public Suffix List](https://publicsuffix.org/).
+const PUBLIC_SUFFIXES: &[&str] = &[
+    "ac.jp",
+    "ac.uk",
+    "co.id",
+    "co.in",
+    "co.jp",
+    "co.kr",
+    "co.nz",
+    "co.th",
+    "co.uk",
+    "co.za",
+    "com.ar",
+    "com.au",
+    "com.br",
+    "com.cn",
+    "com.hk",
+    "com.mx",
+    "com.my",
+    "com.sg",
+    "com.tr",
+    "com.tw",
+    "com.ua",
+    "com.vn",
+    "gov.uk",
+    "ne.jp",
+    "net.au",
+    "or.jp",
+    "org.au",
+    "org.uk",
+    "github.io",
+    "blogspot.com",
+    "herokuapp.com",
+    "netlify.app",
+    "pages.dev",
+    "vercel.app",
+    "web.app",
+];
+
+/// Number of jars saved so far, to give each save its own temporary file.
+static SAVES: AtomicUsize = AtomicUsize::new(0);
+
+/// A cookie stored in a [`CookieJar`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    /// Domain the cookie is sent to, without a leading dot.
+    pub domain: String,
+    /// Whether the cookie is only sent to [`Cookie::domain`], and not to its
+    /// subdomains.
+    #[serde(default)]
+    pub host_only: bool,
+    pub path: String,
+    /// Expiration date in seconds since the Unix epoch, or `None` for a
+    /// session cookie.
+    #[serde(default)]
+    pub expires: Option<u64>,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub http_only: bool,
+}
+
+impl Cookie {
+    /// Parse a `Set-Cookie` header received in response to a request to
+    /// `url`.
+    ///
+    /// Returns `None` if the header is malformed, or if it sets a cookie for
+    /// another domain than the one of `url`, e.g. for a public suffix like
+    /// `com`. The value is kept as is, including its quotes if any.
+    pub fn parse(url: &Url, header: &str) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            expires: None,
+            secure: false,
+            http_only: false,
+        };
+
+        let mut max_age = None;
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if !domain_match(&host, &domain) {
+                        return None;
+                    }
+
+                    // A cookie for a public suffix or an IP address is only
+                    // sent to the host that set it.
+                    if is_public_suffix(&domain) || host.parse::<IpAddr>().is_ok() {
+                        if domain != host {
+                            return None;
+                        }
+                        cookie.host_only = true;
+                    } else {
+                        cookie.host_only = false;
+                    }
+                    cookie.domain = domain;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "expires" => {
+                    if let Ok(expires) = httpdate::parse_http_date(value) {
+                        cookie.expires = Some(unix_time(expires));
+                    }
+                }
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                _ => {}
+            }
+        }
+
+        // Max-Age takes precedence over Expires.
+        if let Some(max_age) = max_age {
+            cookie.expires = Some(match u64::try_from(max_age) {
+                Ok(max_age) if max_age > 0 => unix_time(SystemTime::now()).saturating_add(max_age),
+                _ => 0,
+            });
+        }
+
+        Some(cookie)
+    }
+
+    /// Returns `true` if the cookie has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires
+            .is_some_and(|expires| expires <= unix_time(SystemTime::now()))
+    }
+
+    /// Returns `true` if the cookie must be sent with a request to `url`.
+    pub fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+
+        let domain_matches = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(&host, &self.domain)
+        };
+
+        domain_matches
+            && path_match(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+            && !self.is_expired()
+    }
+}
+
+/// Store of the cookies of a source, applied to every request it sends.
+///
+/// The jar can be seeded, e.g. with the cookies of a login done in a web
+/// view, and persisted between runs with [`CookieJar::save`] and
+/// [`CookieJar::load`].
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the cookies saved by [`CookieJar::save`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let cookies: Vec<Cookie> = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        let jar = Self::new();
+        cookies.into_iter().for_each(|cookie| jar.insert(cookie));
+        Ok(jar)
+    }
+
+    /// Write the cookies to a file, creating its parent directories.
+    ///
+    /// Session cookies are saved too, so that a session survives restarting
+    /// the host. The cookies are written to a temporary file first, so that
+    /// a save interrupted midway does not lose the previous one.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let save = SAVES.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = path.with_extension(format!("{}.{save}.tmp", std::process::id()));
+        let result = File::create(&tmp_path).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, &self.cookies())?;
+            writer.flush()
+        });
+        if let Err(err) = result.and_then(|()| fs::rename(&tmp_path, path)) {
+            _ = fs::remove_file(&tmp_path);
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Returns every cookie that has not expired.
+    pub fn cookies(&self) -> Vec<Cookie> {
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|cookie| !cookie.is_expired());
+        cookies.clone()
+    }
+
+    /// Returns the cookies sent with a request to `url`.
+    pub fn cookies_for(&self, url: &Url) -> Vec<Cookie> {
+        let mut cookies: Vec<Cookie> = self
+            .cookies
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|cookie| cookie.matches(url))
+            .cloned()
+            .collect();
+
+        // Cookies with longer paths are listed first.
+        cookies.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        cookies
+    }
+
+    /// Insert a cookie, replacing the cookie with the same name, domain and
+    /// path.
+    ///
+    /// Inserting an expired cookie removes it.
+    pub fn insert(&self, cookie: Cookie) {
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|other| {
+            other.name != cookie.name || other.domain != cookie.domain || other.path != cookie.path
+        });
+
+        if !cookie.is_expired() {
+            cookies.push(cookie);
+        }
+    }
+
+    /// Store the cookies set by the `Set-Cookie` headers of a response to a
+    /// request to `url`. Other headers are ignored.
+    pub fn set_cookies(&self, url: &Url, headers: &[(String, String)]) {
+        headers
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+            .filter_map(|(_, value)| Cookie::parse(url, value))
+            .for_each(|cookie| self.insert(cookie));
+    }
+
+    /// Returns the value of the `Cookie` header of a request to `url`, if
+    /// any cookie must be sent.
+    pub fn header(&self, url: &Url) -> Option<String> {
+        let cookies = self.cookies_for(url);
+        if cookies.is_empty() {
+            return None;
+        }
+
+        let pairs: Vec<String> = cookies
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        Some(pairs.join("; "))
+    }
+
+    /// Remove every cookie.
+    pub fn clear(&self) {
+        self.cookies.lock().unwrap().clear();
+    }
+}
+
+/// Returns `true` if `host` is `domain` or one of its subdomains.
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Returns `true` if `domain` is a public suffix, see [`PUBLIC_SUFFIXES`].
+fn is_public_suffix(domain: &str) -> bool {
+    !domain.contains('.') || PUBLIC_SUFFIXES.contains(&domain)
+}
+
+/// Returns `true` if a cookie with the path `cookie_path` is sent with a
+/// request to `path`.
+fn path_match(path: &str, cookie_path: &str) -> bool {
+    path == cookie_path
+        || path
+            .strip_prefix(cookie_path)
+            .is_some_and(|rest| cookie_path.ends_with('/') || rest.starts_with('/'))
+}
+
+/// Returns the path of a cookie set without a `Path` attribute, i.e. the
+/// directory of the path of `url`.
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_cookie_parse() {
+        let cookie = Cookie::parse(
+            &url("https://www.example.com/manga/1"),
+            "session=abc; Domain=.example.com; Path=/; Secure; HttpOnly; Max-Age=3600",
+        )
+        .unwrap();
+
+        assert_eq!(cookie.name, "session");
+        assert_eq!(cookie.value, "abc");
+        assert_eq!(cookie.domain, "example.com");
+        assert!(!cookie.host_only);
+        assert_eq!(cookie.path, "/");
+        assert!(cookie.secure);
+        assert!(cookie.http_only);
+        assert!(!cookie.is_expired());
+    }
+
+    #[test]
+    fn test_cookie_parse_defaults() {
+        let cookie = Cookie::parse(&url("http://example.com/manga/1"), "id=1").unwrap();
+
+        assert_eq!(cookie.domain, "example.com");
+        assert!(cookie.host_only);
+        assert_eq!(cookie.path, "/manga");
+        assert_eq!(cookie.expires, None);
+    }
+
+    #[test]
+    fn test_cookie_parse_invalid() {
+        let url = url("https://example.com");
+
+        assert!(Cookie::parse(&url, "no value").is_none());
+        assert!(Cookie::parse(&url, "=value").is_none());
+        // Cookies cannot be set for another domain.
+        assert!(Cookie::parse(&url, "id=1; Domain=example.org").is_none());
+        // Nor for a public suffix.
+        assert!(Cookie::parse(&url, "id=1; Domain=com").is_none());
+        let co_uk = self::url("https://example.co.uk");
+        assert!(Cookie::parse(&co_uk, "id=1; Domain=co.uk").is_none());
+        let ip = self::url("http://10.0.0.1");
+        assert!(Cookie::parse(&ip, "id=1; Domain=0.1").is_none());
+    }
+
+    #[test]
+    fn test_cookie_parse_host_only_domain() {
+        // A public suffix or an IP address can set a cookie for itself.
+        let cookie = Cookie::parse(&url("https://localhost"), "id=1; Domain=localhost").unwrap();
+        assert_eq!(cookie.domain, "localhost");
+        assert!(cookie.host_only);
+
+        let cookie = Cookie::parse(&url("http://10.0.0.1"), "id=1; Domain=10.0.0.1").unwrap();
+        assert!(cookie.host_only);
+    }
+
+    #[test]
+    fn test_cookie_parse_quoted_value() {
+        let url = url("https://example.com");
+
+        let cookie = Cookie::parse(&url, "id=\"a b\"; Path=/").unwrap();
+        assert_eq!(cookie.value, "\"a b\"");
+
+        let jar = CookieJar::new();
+        jar.insert(cookie);
+        assert_eq!(jar.header(&url).unwrap(), "id=\"a b\"");
+    }
+
+    #[test]
+    fn test_cookie_parse_expires() {
+        let url = url("https://example.com");
+
+        let cookie = Cookie::parse(&url, "id=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(cookie.expires, Some(1445412480));
+        assert!(cookie.is_expired());
+
+        let cookie = Cookie::parse(&url, "id=1; Max-Age=0").unwrap();
+        assert!(cookie.is_expired());
+    }
+
+    #[test]
+    fn test_cookie_jar_header() {
+        let jar = CookieJar::new();
+        jar.set_cookies(
+            &url("https://example.com/manga/1"),
+            &[
+                ("Set-Cookie".to_string(), "a=1; Path=/".to_string()),
+                ("set-cookie".to_string(), "b=2; Path=/manga".to_string()),
+                ("set-cookie".to_string(), "c=3; Secure".to_string()),
+                ("content-type".to_string(), "text/html".to_string()),
+            ],
+        );
+
+        assert_eq!(jar.cookies().len(), 3);
+        assert_eq!(
+            jar.header(&url("https://example.com/manga/2")).unwrap(),
+            "b=2; c=3; a=1"
+        );
+        assert_eq!(
+            jar.header(&url("http://example.com/manga")).unwrap(),
+            "b=2; a=1"
+        );
+        assert_eq!(jar.header(&url("https://example.com/")).unwrap(), "a=1");
+        assert_eq!(jar.header(&url("https://www.example.com/")), None);
+        assert_eq!(jar.header(&url("https://example.org/")), None);
+    }
+
+    #[test]
+    fn test_cookie_jar_replace_and_remove() {
+        let jar = CookieJar::new();
+        let url = url("https://example.com");
+
+        jar.set_cookies(&url, &[("set-cookie".to_string(), "id=1".to_string())]);
+        jar.set_cookies(&url, &[("set-cookie".to_string(), "id=2".to_string())]);
+        assert_eq!(jar.header(&url).unwrap(), "id=2");
+
+        jar.set_cookies(
+            &url,
+            &[("set-cookie".to_string(), "id=; Max-Age=0".to_string())],
+        );
+        assert!(jar.cookies().is_empty());
+
+        jar.insert(Cookie::parse(&url, "id=3").unwrap());
+        jar.clear();
+        assert!(jar.cookies().is_empty());
+    }
+
+    #[test]
+    fn test_cookie_jar_save_load() {
+        let path = std::env::temp_dir().join("midoku-http-test-cookie-jar.json");
+
+        let jar = CookieJar::new();
+        jar.set_cookies(
+            &url("https://example.com"),
+            &[
+                ("set-cookie".to_string(), "session=abc".to_string()),
+                ("set-cookie".to_string(), "id=1; Max-Age=3600".to_string()),
+            ],
+        );
+        jar.save(&path).unwrap();
+
+        let loaded = CookieJar::load(&path).unwrap();
+        assert_eq!(loaded.cookies(), jar.cookies());
+
+        // Saving again replaces the file, without leaving temporary files.
+        jar.clear();
+        jar.save(&path).unwrap();
+        assert!(CookieJar::load(&path).unwrap().cookies().is_empty());
+        let tmp_files = std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with("midoku-http-test-cookie-jar.") && name.ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(tmp_files, 0);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::policy::is_private_address;
+
+/// DNS resolver of the clients built from an
+/// [`HttpConfig`](crate::client::HttpConfig).
+///
+/// Failures are reported as [`DnsError`], so that they can be told apart from
+/// the other connection errors. If private addresses are denied, they are
+/// checked where the connection is made, so that a host cannot resolve to a
+/// public address when checked and to a private one when connecting.
+pub(crate) struct Resolver {
+    pub allow_private_addresses: bool,
+}
+
+impl Resolve for Resolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        let allow_private_addresses = self.allow_private_addresses;
+
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await
+                .map_err(|err| DnsError(err.to_string()))?
+                .collect();
+            if allow_private_addresses {
+                return Ok(Box::new(addresses.into_iter()) as Addrs);
+            }
+
+            let (public, private): (Vec<SocketAddr>, Vec<SocketAddr>) = addresses
+                .into_iter()
+                .partition(|address| !is_private_address(&address.ip()));
+
+            match private.first() {
+                Some(address) if public.is_empty() => Err(Box::new(BlockedAddress {
+                    host,
+                    address: address.ip(),
+                })
+                    as Box<dyn Error + Send + Sync>),
+                _ => Ok(Box::new(public.into_iter()) as Addrs),
+            }
+        })
+    }
+}
+
+/// Error of [`Resolver`] when a host name could not be resolved.
+#[derive(Debug)]
+pub(crate) struct DnsError(String);
+
+impl fmt::Display for DnsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for DnsError {}
+
+/// Error of [`Resolver`] when a host only resolves to private addresses.
+#[derive(Debug)]
+pub(crate) struct BlockedAddress {
+    host: String,
+    address: IpAddr,
+}
+
+impl fmt::Display for BlockedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "host `{}` resolves to the private address {}",
+            self.host, self.address
+        )
+    }
+}
+
+impl Error for BlockedAddress {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolver_private_addresses() {
+        let resolver = Resolver {
+            allow_private_addresses: false,
+        };
+        let err = resolver
+            .resolve("localhost".parse().unwrap())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<BlockedAddress>().unwrap().host,
+            "localhost"
+        );
+
+        let resolver = Resolver {
+            allow_private_addresses: true,
+        };
+        let addresses = resolver.resolve("localhost".parse().unwrap()).await;
+        assert!(addresses.unwrap().all(|address| address.ip().is_loopback()));
+    }
+
+    #[tokio::test]
+    async fn test_resolver_dns_error() {
+        let resolver = Resolver {
+            allow_private_addresses: true,
+        };
+        let err = resolver
+            .resolve("midoku.invalid".parse().unwrap())
+            .await
+            .err()
+            .unwrap();
+        assert!(err.is::<DnsError>());
+    }
+}
@@ -0,0 +1,588 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{is_one_of, CREDENTIAL_HEADERS, UNSTORED_HEADERS};
+use crate::transport::{HttpTransport, Request, SendFuture};
+use crate::types::{HttpError, IncomingResponse, Redirect};
+
+/// A request sent by a source and the response it received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub request: Request,
+    /// The response, or `None` if the request failed.
+    pub response: Option<RecordedResponse>,
+    /// The error the request failed with, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<HttpError>,
+}
+
+impl Exchange {
+    /// Returns the recorded outcome of the request.
+    ///
+    /// A failed exchange recorded without its error fails with
+    /// [`HttpError::Other`].
+    fn into_result(self) -> Result<IncomingResponse, HttpError> {
+        match (self.response, self.error) {
+            (Some(response), _) => Ok(response.into()),
+            (None, Some(error)) => Err(error),
+            (None, None) => Err(HttpError::Other("recorded request failed".to_string())),
+        }
+    }
+
+    /// Replace the credentials of the request and the cookies set by the
+    /// response with [`REDACTED`].
+    fn redact(&mut self) {
+        redact(&mut self.request.headers, CREDENTIAL_HEADERS);
+        redact(&mut self.request.raw_headers, CREDENTIAL_HEADERS);
+
+        if let Some(response) = &mut self.response {
+            redact(&mut response.headers, UNSTORED_HEADERS);
+            if let Some(raw_headers) = &mut response.raw_headers {
+                redact(raw_headers, UNSTORED_HEADERS);
+            }
+        }
+    }
+}
+
+/// Value recorded in place of the credentials of a source, e.g. its
+/// `Cookie` and `Authorization` headers, as fixtures are meant to be
+/// committed. A redacted `Set-Cookie` header is ignored when replayed.
+pub const REDACTED: &str = "[redacted]";
+
+/// Replace the value of every header named one of `names` with [`REDACTED`].
+fn redact<V: From<&'static str>>(headers: &mut [(String, V)], names: &[&str]) {
+    for (name, value) in headers {
+        if is_one_of(name, names) {
+            *value = V::from(REDACTED);
+        }
+    }
+}
+
+/// A response recorded in a fixture, with its whole body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The URL the response was received from, if it is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The redirects followed by the request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<Redirect>,
+    pub status_code: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// The headers with their values as received, only recorded if a value
+    /// is not valid UTF-8. They take precedence over `headers` when replayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_headers: Option<Vec<(String, Vec<u8>)>>,
+    #[serde(default, with = "body")]
+    pub body: Vec<u8>,
+}
+
+impl RecordedResponse {
+    /// Read the whole body of a response to record it.
+    pub async fn record(mut response: IncomingResponse) -> Result<Self, HttpError> {
+        let body = response.bytes().await?.to_vec();
+
+        let headers = response.headers().clone();
+        let raw_headers = response.raw_headers();
+        let is_utf8 = raw_headers
+            .iter()
+            .all(|(_, value)| std::str::from_utf8(value).is_ok());
+
+        Ok(Self {
+            url: response.url().map(str::to_string),
+            redirects: response.redirects().to_vec(),
+            status_code: response.status_code(),
+            raw_headers: (!is_utf8).then(|| raw_headers.clone()),
+            headers,
+            body,
+        })
+    }
+}
+
+impl From<RecordedResponse> for IncomingResponse {
+    fn from(value: RecordedResponse) -> Self {
+        let mut response = IncomingResponse::new(value.status_code, value.headers, value.body);
+        if let Some(raw_headers) = value.raw_headers {
+            response = response.with_raw_headers(raw_headers);
+        }
+
+        response.with_url(value.url).with_redirects(value.redirects)
+    }
+}
+
+/// A list of exchanges, stored as JSON in a fixture file.
+///
+/// Bodies are written as strings when they are valid UTF-8, and as arrays of
+/// bytes otherwise, so that fixtures can be reviewed and edited by hand.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub exchanges: Vec<Exchange>,
+}
+
+impl Fixture {
+    /// Load a fixture file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
+
+    /// Write the fixture to a file, creating its parent directories.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+}
+
+/// Transport recording every exchange sent through another transport.
+///
+/// The body of every response is read in full to be recorded. The
+/// credentials of the requests and the cookies set by the responses are
+/// recorded as [`REDACTED`], the source still receives them.
+///
+/// # Example
+/// ```ignore
+/// let transport = Arc::new(RecordingTransport::new(Arc::new(ReqwestTransport::default())));
+///
+/// let bindings = Bindings::builder()
+///     .with_transport(transport.clone())
+///     .instantiate(&runtime, &component)
+///     .await?;
+/// bindings.get_manga_list(vec![], 0).await?;
+///
+/// transport.save("tests/fixtures/manga-list.json")?;
+/// ```
+pub struct RecordingTransport {
+    inner: Arc<dyn HttpTransport>,
+    exchanges: Mutex<Vec<Exchange>>,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn HttpTransport>) -> Self {
+        Self {
+            inner,
+            exchanges: Mutex::default(),
+        }
+    }
+
+    /// Returns the exchanges recorded so far, in the order of the responses.
+    pub fn fixture(&self) -> Fixture {
+        Fixture {
+            exchanges: self.exchanges.lock().unwrap().clone(),
+        }
+    }
+
+    /// Write the exchanges recorded so far to a fixture file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.fixture().save(path)
+    }
+}
+
+impl HttpTransport for RecordingTransport {
+    fn send(&self, request: Request) -> SendFuture<'_> {
+        Box::pin(async move {
+            let response = match self.inner.send(request.clone()).await {
+                Ok(response) => RecordedResponse::record(response).await,
+                Err(err) => Err(err),
+            };
+
+            let mut exchange = Exchange {
+                request,
+                response: response.as_ref().ok().cloned(),
+                error: response.err(),
+            };
+            let result = exchange.clone().into_result();
+
+            exchange.redact();
+            self.exchanges.lock().unwrap().push(exchange);
+
+            result
+        })
+    }
+}
+
+/// Transport serving the exchanges of a fixture back.
+///
+/// A request is answered with the first exchange not served yet with the same
+/// method, URL and body. The headers are not compared, as they may vary
+/// between runs. A request without a matching exchange fails with
+/// [`HttpError::Other`], which is not retried, and is kept in
+/// [`ReplayTransport::unexpected`] so that tests can assert on it.
+#[derive(Debug, Default)]
+pub struct ReplayTransport {
+    exchanges: Mutex<Vec<Option<Exchange>>>,
+    unexpected: Mutex<Vec<Request>>,
+}
+
+impl ReplayTransport {
+    pub fn new(fixture: Fixture) -> Self {
+        Self {
+            exchanges: Mutex::new(fixture.exchanges.into_iter().map(Some).collect()),
+            unexpected: Mutex::default(),
+        }
+    }
+
+    /// Load the fixture to replay from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Fixture::load(path).map(Self::new)
+    }
+
+    /// Returns the requests without a matching exchange.
+    pub fn unexpected(&self) -> Vec<Request> {
+        self.unexpected.lock().unwrap().clone()
+    }
+
+    /// Returns the exchanges not served yet.
+    pub fn remaining(&self) -> Vec<Exchange> {
+        self.exchanges
+            .lock()
+            .unwrap()
+            .iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+impl HttpTransport for ReplayTransport {
+    fn send(&self, request: Request) -> SendFuture<'_> {
+        let exchange = self
+            .exchanges
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|exchange| {
+                exchange.as_ref().is_some_and(|exchange| {
+                    exchange.request.method == request.method
+                        && exchange.request.url == request.url
+                        && exchange.request.body == request.body
+                })
+            })
+            .and_then(Option::take);
+
+        let response = match exchange {
+            Some(exchange) => exchange.into_result(),
+            None => {
+                let error = HttpError::Other(format!(
+                    "no recorded response for {} {}",
+                    request.method.as_str(),
+                    request.url
+                ));
+                self.unexpected.lock().unwrap().push(request);
+                Err(error)
+            }
+        };
+
+        Box::pin(std::future::ready(response))
+    }
+}
+
+/// (De)serialize a body as a string if it is valid UTF-8, and as an array of
+/// bytes otherwise.
+pub(crate) mod body {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    #[serde(untagged)]
+    enum Body<'a> {
+        Text(&'a str),
+        Bytes(&'a [u8]),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OwnedBody {
+        Text(String),
+        Bytes(Vec<u8>),
+    }
+
+    impl From<OwnedBody> for Vec<u8> {
+        fn from(value: OwnedBody) -> Self {
+            match value {
+                OwnedBody::Text(text) => text.into_bytes(),
+                OwnedBody::Bytes(bytes) => bytes,
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Body::Text(text).serialize(serializer),
+            Err(_) => Body::Bytes(bytes).serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        OwnedBody::deserialize(deserializer).map(Vec::from)
+    }
+
+    /// Same as the parent module, for an optional body.
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            bytes: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => super::serialize(bytes, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            Option::<OwnedBody>::deserialize(deserializer).map(|body| body.map(Vec::from))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry::RetryPolicy;
+    use crate::transport::MockTransport;
+    use crate::types::Method;
+
+    const URL: &str = "https://example.com";
+
+    fn request(method: Method, url: &str, body: Option<Vec<u8>>) -> Request {
+        Request {
+            method,
+            url: url.to_string(),
+            headers: vec![("accept".to_string(), "*/*".to_string())],
+            raw_headers: vec![],
+            body,
+        }
+    }
+
+    fn response(
+        status_code: u16,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> RecordedResponse {
+        RecordedResponse {
+            url: None,
+            redirects: vec![],
+            status_code,
+            headers,
+            raw_headers: None,
+            body,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recording_transport() {
+        let mock = MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(200, vec![], b"Hello".to_vec()),
+        );
+        let transport = RecordingTransport::new(Arc::new(mock));
+
+        assert!(transport
+            .send(request(Method::Get, URL, None))
+            .await
+            .is_ok());
+        assert!(transport
+            .send(request(Method::Post, URL, Some(vec![1, 2])))
+            .await
+            .is_err());
+
+        let fixture = transport.fixture();
+        assert_eq!(fixture.exchanges.len(), 2);
+        assert_eq!(
+            fixture.exchanges[0].request,
+            request(Method::Get, URL, None)
+        );
+        assert_eq!(
+            fixture.exchanges[0].response.as_ref().unwrap().body,
+            b"Hello"
+        );
+        assert!(fixture.exchanges[1].response.is_none());
+        assert_eq!(
+            fixture.exchanges[1].error,
+            Some(HttpError::Other(
+                "no mock response for POST https://example.com".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recording_transport_redacts_credentials() {
+        let set_cookie = ("Set-Cookie".to_string(), "session=abc".to_string());
+        let mock = MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(200, vec![set_cookie.clone()], vec![]),
+        );
+        let transport = RecordingTransport::new(Arc::new(mock));
+
+        let mut request = request(Method::Get, URL, None);
+        request.headers = vec![
+            ("authorization".to_string(), "Bearer token".to_string()),
+            ("accept".to_string(), "*/*".to_string()),
+        ];
+        request.raw_headers = vec![("Cookie".to_string(), b"login=1".to_vec())];
+        let response = transport.send(request).await.unwrap();
+
+        // The source receives the cookies.
+        assert_eq!(response.headers(), &vec![set_cookie]);
+
+        let exchange = &transport.fixture().exchanges[0];
+        assert_eq!(
+            exchange.request.headers,
+            vec![
+                ("authorization".to_string(), REDACTED.to_string()),
+                ("accept".to_string(), "*/*".to_string()),
+            ]
+        );
+        assert_eq!(
+            exchange.request.raw_headers,
+            vec![("Cookie".to_string(), REDACTED.as_bytes().to_vec())]
+        );
+        assert_eq!(
+            exchange.response.as_ref().unwrap().headers,
+            vec![("Set-Cookie".to_string(), REDACTED.to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recording_transport_response() {
+        let redirects = vec![Redirect {
+            url: "http://example.com".to_string(),
+            status_code: 301,
+        }];
+        let mock = MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(200, vec![], vec![])
+                .with_raw_headers(vec![("x-title".to_string(), b"caf\xe9".to_vec())])
+                .with_url(Some(URL.to_string()))
+                .with_redirects(redirects.clone()),
+        );
+        let transport = RecordingTransport::new(Arc::new(mock));
+        transport
+            .send(request(Method::Get, URL, None))
+            .await
+            .unwrap();
+
+        let fixture = transport.fixture();
+        let recorded = fixture.exchanges[0].response.clone().unwrap();
+        assert_eq!(recorded.url.as_deref(), Some(URL));
+        assert_eq!(recorded.redirects, redirects);
+        assert_eq!(
+            recorded.raw_headers,
+            Some(vec![("x-title".to_string(), b"caf\xe9".to_vec())])
+        );
+
+        let replayed = ReplayTransport::new(fixture)
+            .send(request(Method::Get, URL, None))
+            .await
+            .unwrap();
+        assert_eq!(replayed.url(), Some(URL));
+        assert_eq!(replayed.redirects(), redirects);
+        assert_eq!(replayed.raw_headers()[0].1, b"caf\xe9");
+        assert_eq!(replayed.headers()[0].1, "café");
+    }
+
+    #[tokio::test]
+    async fn test_replay_transport() {
+        let fixture = Fixture {
+            exchanges: vec![
+                Exchange {
+                    request: request(Method::Get, URL, None),
+                    response: Some(response(200, vec![], b"first".to_vec())),
+                    error: None,
+                },
+                Exchange {
+                    request: request(Method::Get, URL, None),
+                    response: Some(response(200, vec![], b"second".to_vec())),
+                    error: None,
+                },
+                Exchange {
+                    request: request(Method::Post, URL, Some(b"body".to_vec())),
+                    response: None,
+                    error: Some(HttpError::Timeout),
+                },
+            ],
+        };
+        let transport = ReplayTransport::new(fixture.clone());
+
+        // Identical requests are served in order.
+        let response = transport.send(request(Method::Get, URL, None)).await;
+        assert_eq!(response.unwrap().bytes().await.unwrap(), b"first");
+        let response = transport.send(request(Method::Get, URL, None)).await;
+        assert_eq!(response.unwrap().bytes().await.unwrap(), b"second");
+
+        // Every exchange is served once. An unexpected request is not
+        // retried.
+        let response = transport.send(request(Method::Get, URL, None)).await;
+        assert_eq!(
+            response.as_ref().unwrap_err(),
+            &HttpError::Other("no recorded response for GET https://example.com".to_string())
+        );
+        let retry_delay = RetryPolicy::default().retry_delay(&Method::Get, 1, &response);
+        assert_eq!(retry_delay, None);
+        assert_eq!(
+            transport.unexpected(),
+            vec![request(Method::Get, URL, None)]
+        );
+
+        // The body must match.
+        assert!(transport
+            .send(request(Method::Post, URL, Some(b"other".to_vec())))
+            .await
+            .is_err());
+        assert_eq!(transport.unexpected().len(), 2);
+        assert_eq!(transport.remaining(), vec![fixture.exchanges[2].clone()]);
+
+        // Failed requests fail again with the same error.
+        let response = transport
+            .send(request(Method::Post, URL, Some(b"body".to_vec())))
+            .await;
+        assert_eq!(response.unwrap_err(), HttpError::Timeout);
+    }
+
+    #[test]
+    fn test_fixture_save_load() {
+        let path = std::env::temp_dir().join("midoku-http-test-fixture.json");
+        let fixture = Fixture {
+            exchanges: vec![
+                Exchange {
+                    request: request(Method::Post, URL, Some(b"{}".to_vec())),
+                    response: Some(response(
+                        200,
+                        vec![("content-type".to_string(), "text/html".to_string())],
+                        b"<html></html>".to_vec(),
+                    )),
+                    error: None,
+                },
+                Exchange {
+                    request: request(Method::Get, URL, None),
+                    response: Some(response(200, vec![], vec![0xff, 0xfe])),
+                    error: None,
+                },
+            ],
+        };
+
+        fixture.save(&path).unwrap();
+
+        // Text bodies are readable.
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(json.contains(r#""body": "<html></html>""#));
+
+        assert_eq!(Fixture::load(&path).unwrap(), fixture);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,2 +1,11 @@
+pub mod cache;
+pub mod client;
+pub mod cookie;
+mod dns;
+pub mod fixture;
 pub mod outgoing_handler;
+pub mod policy;
+pub mod redirect;
+pub mod retry;
+pub mod transport;
 pub mod types;
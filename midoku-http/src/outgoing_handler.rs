@@ -1,63 +1,196 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::LazyLock;
+
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-use reqwest::Client;
+pub use reqwest::{Client, Url};
+
+use crate::client::HttpConfig;
+use crate::dns::{BlockedAddress, DnsError};
+use crate::transport::Request;
+use crate::types::{HttpError, IncomingResponse, Method};
 
-use crate::types::{IncomingResponse, Method};
+impl TryFrom<Method> for reqwest::Method {
+    type Error = InvalidMethod;
 
-impl From<Method> for reqwest::Method {
-    fn from(value: Method) -> Self {
-        match value {
+    fn try_from(value: Method) -> Result<Self, Self::Error> {
+        Ok(match value {
             Method::Get => reqwest::Method::GET,
             Method::Post => reqwest::Method::POST,
             Method::Put => reqwest::Method::PUT,
             Method::Head => reqwest::Method::HEAD,
             Method::Delete => reqwest::Method::DELETE,
+            Method::Patch => reqwest::Method::PATCH,
+            Method::Options => reqwest::Method::OPTIONS,
+            Method::Connect => reqwest::Method::CONNECT,
+            Method::Trace => reqwest::Method::TRACE,
+            Method::Other(method) => {
+                reqwest::Method::from_bytes(method.as_bytes()).map_err(|_| InvalidMethod(method))?
+            }
+        })
+    }
+}
+
+/// Error returned when converting a [`Method::Other`] that is not a valid
+/// HTTP token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMethod(pub String);
+
+impl fmt::Display for InvalidMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid HTTP method `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidMethod {}
+
+impl From<reqwest::Error> for HttpError {
+    fn from(err: reqwest::Error) -> Self {
+        let message = root_cause(&err).to_string();
+
+        if err.is_timeout() {
+            HttpError::Timeout
+        } else if err.is_redirect() {
+            HttpError::TooManyRedirects
+        } else if err.is_builder() {
+            // The URL is parsed before building the request, the remaining
+            // builder errors are unsupported schemes.
+            HttpError::InvalidUrl(message)
+        } else if let Some(err) = find_source::<rustls::Error>(&err) {
+            HttpError::Tls(err.to_string())
+        } else if let Some(err) = find_source::<BlockedAddress>(&err) {
+            HttpError::Blocked(err.to_string())
+        } else if find_source::<DnsError>(&err).is_some() {
+            HttpError::Dns(message)
+        } else if err.is_connect() {
+            // Including the resolution failures of clients not built from an
+            // `HttpConfig`, which are not recognizable.
+            HttpError::Connect(message)
+        } else {
+            HttpError::Other(message)
+        }
+    }
+}
+
+/// Returns the error at the end of the chain of sources of `err`.
+fn root_cause<'a>(err: &'a (dyn Error + 'static)) -> &'a (dyn Error + 'static) {
+    find_source_by(err, |source| source.source().is_none()).unwrap_or(err)
+}
+
+/// Returns the first error of type `E` in the chain of sources of `err`.
+fn find_source<'a, E: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a E> {
+    find_source_by(err, |source| source.is::<E>()).and_then(|source| source.downcast_ref())
+}
+
+/// Returns the first error matching `predicate` in the chain of sources of
+/// `err`, including `err` itself.
+fn find_source_by<'a>(
+    err: &'a (dyn Error + 'static),
+    predicate: impl Fn(&(dyn Error + 'static)) -> bool,
+) -> Option<&'a (dyn Error + 'static)> {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if predicate(err) {
+            return Some(err);
         }
+
+        // `io::Error::source` skips the error it wraps, e.g. the TLS error of
+        // a failed handshake.
+        source = match err.downcast_ref::<io::Error>().and_then(io::Error::get_ref) {
+            Some(inner) => Some(inner as &(dyn Error + 'static)),
+            None => err.source(),
+        };
     }
+
+    None
 }
 
+/// Client used by [`handle`], shared by every request of the process.
+///
+/// Like every client of the host, it does not follow redirects.
+pub(crate) static DEFAULT_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    HttpConfig::default()
+        .build()
+        .expect("failed to build the default HTTP client")
+});
+
+/// Send the request with a default client.
+///
+/// The client is shared by every call, so that connections are reused.
 pub async fn handle(
     method: Method,
     url: String,
     headers: Option<Vec<(String, String)>>,
     body: Option<Vec<u8>>,
-) -> Result<IncomingResponse, ()> {
-    let client = Client::new();
+) -> Result<IncomingResponse, HttpError> {
+    handle_with_client(&DEFAULT_CLIENT, method, url, headers, body).await
+}
+
+/// Send the request with the given client.
+///
+/// The response is returned as soon as its headers are received, its body is
+/// streamed as it is read.
+///
+/// Reusing a client shares its connection pool and configuration between
+/// requests.
+pub async fn handle_with_client(
+    client: &Client,
+    method: Method,
+    url: String,
+    headers: Option<Vec<(String, String)>>,
+    body: Option<Vec<u8>>,
+) -> Result<IncomingResponse, HttpError> {
+    let request = Request {
+        method,
+        url,
+        headers: headers.unwrap_or_default(),
+        raw_headers: Vec::new(),
+        body,
+    };
 
-    let mut header_map = HeaderMap::new();
-    if let Some(headers) = headers {
-        for (header_name, header_value) in headers {
-            let header_name = header_name.to_lowercase();
-            let header_name = HeaderName::from_lowercase(header_name.as_bytes());
-            let header_name = header_name.map_err(|_| ())?;
+    send_with_client(client, request).await
+}
 
-            let header_value = HeaderValue::from_str(&header_value);
-            let header_value = header_value.map_err(|_| ())?;
+/// Send the request with the given client, see [`handle_with_client`].
+pub(crate) async fn send_with_client(
+    client: &Client,
+    request: Request,
+) -> Result<IncomingResponse, HttpError> {
+    let Request {
+        method,
+        url,
+        headers,
+        raw_headers,
+        body,
+    } = request;
+    let url = Url::parse(&url).map_err(|err| HttpError::InvalidUrl(err.to_string()))?;
 
-            header_map.insert(header_name, header_value);
-        }
+    // Headers are appended, so that a header sent several times keeps every
+    // value.
+    let headers = headers
+        .into_iter()
+        .map(|(name, value)| (name, value.into_bytes()))
+        .chain(raw_headers);
+    let mut header_map = HeaderMap::new();
+    for (header_name, header_value) in headers {
+        let invalid_header = || HttpError::InvalidHeader(header_name.clone());
+        let name = HeaderName::from_bytes(header_name.as_bytes()).map_err(|_| invalid_header())?;
+        let value = HeaderValue::from_bytes(&header_value).map_err(|_| invalid_header())?;
+
+        header_map.append(name, value);
     }
 
-    let mut request_builder = client.request(method.into(), url).headers(header_map);
+    let method =
+        reqwest::Method::try_from(method).map_err(|err| HttpError::Other(err.to_string()))?;
+    let mut request_builder = client.request(method, url).headers(header_map);
     if let Some(body) = body {
         request_builder = request_builder.body(body);
     }
 
-    let response = request_builder.send().await.map_err(|_| ())?;
-
-    let status_code = response.status().as_u16();
-    let headers = response
-        .headers()
-        .iter()
-        .map(|(name, value)| {
-            (
-                name.as_str().to_string(),
-                value.to_str().unwrap().to_string(),
-            )
-        })
-        .collect();
-    let bytes = response.bytes().await.map_err(|_| ())?.to_vec();
+    let response = request_builder.send().await?;
 
-    Ok(IncomingResponse::new(status_code, headers, bytes))
+    Ok(IncomingResponse::from_reqwest(response))
 }
 
 #[cfg(test)]
@@ -66,20 +199,52 @@ mod tests {
 
     #[test]
     fn test_from_method() {
-        let reqwest_method: reqwest::Method = Method::Get.into();
+        let reqwest_method: reqwest::Method = Method::Get.try_into().unwrap();
         assert_eq!(reqwest_method, reqwest::Method::GET);
 
-        let reqwest_method: reqwest::Method = Method::Post.into();
+        let reqwest_method: reqwest::Method = Method::Post.try_into().unwrap();
         assert_eq!(reqwest_method, reqwest::Method::POST);
 
-        let reqwest_method: reqwest::Method = Method::Put.into();
+        let reqwest_method: reqwest::Method = Method::Put.try_into().unwrap();
         assert_eq!(reqwest_method, reqwest::Method::PUT);
 
-        let reqwest_method: reqwest::Method = Method::Head.into();
+        let reqwest_method: reqwest::Method = Method::Head.try_into().unwrap();
         assert_eq!(reqwest_method, reqwest::Method::HEAD);
 
-        let reqwest_method: reqwest::Method = Method::Delete.into();
+        let reqwest_method: reqwest::Method = Method::Delete.try_into().unwrap();
         assert_eq!(reqwest_method, reqwest::Method::DELETE);
+
+        let reqwest_method: reqwest::Method = Method::Patch.try_into().unwrap();
+        assert_eq!(reqwest_method, reqwest::Method::PATCH);
+
+        let reqwest_method: reqwest::Method = Method::Options.try_into().unwrap();
+        assert_eq!(reqwest_method, reqwest::Method::OPTIONS);
+
+        let reqwest_method: reqwest::Method = Method::Connect.try_into().unwrap();
+        assert_eq!(reqwest_method, reqwest::Method::CONNECT);
+
+        let reqwest_method: reqwest::Method = Method::Trace.try_into().unwrap();
+        assert_eq!(reqwest_method, reqwest::Method::TRACE);
+
+        let reqwest_method: reqwest::Method =
+            Method::Other("PURGE".to_string()).try_into().unwrap();
+        assert_eq!(reqwest_method.as_str(), "PURGE");
+    }
+
+    #[test]
+    fn test_from_method_invalid() {
+        let res = reqwest::Method::try_from(Method::Other("NOT VALID".to_string()));
+        assert_eq!(res, Err(InvalidMethod("NOT VALID".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_handle_other_method() {
+        let url = serve_chunked(&["Purged"]);
+
+        let mut response = handle(Method::Other("PURGE".to_string()), url, None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"Purged");
     }
 
     const URL: &str = "https://example.com";
@@ -90,6 +255,13 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_handle_with_client() {
+        let client = Client::new();
+        let response = handle_with_client(&client, Method::Get, URL.to_string(), None, None).await;
+        assert!(response.is_ok());
+    }
+
     #[tokio::test]
     async fn test_handle_with_headers() {
         let headers = vec![("Content-Type".to_string(), "application/json".to_string())];
@@ -111,4 +283,255 @@ mod tests {
         let response = handle(Method::Get, URL.to_string(), Some(headers), Some(body)).await;
         assert!(response.is_ok());
     }
+
+    /// Serve a single raw response on a local port, and return its URL and
+    /// a receiver of the raw request.
+    fn serve(response: Vec<u8>) -> (String, std::sync::mpsc::Receiver<Vec<u8>>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let len = stream.read(&mut request).unwrap();
+            // The receiver may have been dropped.
+            let _ = sender.send(request[..len].to_vec());
+
+            stream.write_all(&response).unwrap();
+        });
+
+        (url, receiver)
+    }
+
+    /// Serve a single response with a chunked body on a local port, and
+    /// return its URL.
+    fn serve_chunked(chunks: &'static [&'static str]) -> String {
+        let mut response = "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n".to_string();
+        for chunk in chunks {
+            response += &format!("{:x}\r\n{chunk}\r\n", chunk.len());
+        }
+        response += "0\r\n\r\n";
+
+        serve(response.into_bytes()).0
+    }
+
+    #[tokio::test]
+    async fn test_handle_repeated_headers() {
+        let (url, request) = serve(
+            b"HTTP/1.1 200 OK\r\n\
+            set-cookie: a=1\r\n\
+            set-cookie: b=2\r\n\
+            content-length: 0\r\n\r\n"
+                .to_vec(),
+        );
+
+        let headers = vec![
+            ("Accept".to_string(), "text/html".to_string()),
+            ("accept".to_string(), "image/webp".to_string()),
+        ];
+        let response = handle(Method::Get, url, Some(headers), None).await.unwrap();
+
+        let request = String::from_utf8(request.recv().unwrap()).unwrap();
+        assert!(request.contains("accept: text/html\r\n"));
+        assert!(request.contains("accept: image/webp\r\n"));
+
+        let cookies: Vec<&str> = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| name == "set-cookie")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(cookies, vec!["a=1", "b=2"]);
+    }
+
+    #[tokio::test]
+    async fn test_handle_non_utf8_header() {
+        let (url, _) = serve(
+            b"HTTP/1.1 200 OK\r\n\
+            content-disposition: filename=\"caf\xe9.png\"\r\n\
+            content-length: 0\r\n\r\n"
+                .to_vec(),
+        );
+
+        let response = handle(Method::Get, url, None, None).await.unwrap();
+
+        let (_, value) = &response.headers()[0];
+        assert_eq!(value, "filename=\"café.png\"");
+        let (_, value) = &response.raw_headers()[0];
+        assert_eq!(value, b"filename=\"caf\xe9.png\"");
+    }
+
+    #[tokio::test]
+    async fn test_handle_streaming_body() {
+        let url = serve_chunked(&["Hello", ", ", "World!"]);
+
+        let mut response = handle(Method::Get, url.clone(), None, None).await.unwrap();
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.url(), Some(format!("{url}/").as_str()));
+
+        let mut body = response.take_body().unwrap();
+        let mut bytes = Vec::new();
+        while !body.is_end_of_stream() {
+            bytes.extend(body.read(3).await.unwrap());
+        }
+        assert_eq!(bytes, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_handle_body_bytes() {
+        let url = serve_chunked(&["Hello", ", ", "World!"]);
+
+        let mut response = handle(Method::Get, url, None, None).await.unwrap();
+        let mut body = response.take_body().unwrap();
+
+        assert_eq!(body.skip(7).await.unwrap(), 7);
+        assert_eq!(body.buffer().await.unwrap(), b"World!");
+        // Buffering does not consume the bytes.
+        assert_eq!(body.read(10).await.unwrap(), b"World!");
+        assert!(body.is_end_of_stream());
+    }
+
+    #[tokio::test]
+    async fn test_handle_body_error() {
+        // The connection is closed before the end of the body.
+        let (url, _) = serve(b"HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\nabc".to_vec());
+
+        let mut response = handle(Method::Get, url, None, None).await.unwrap();
+        let error = response.bytes().await.unwrap_err();
+        assert!(matches!(error, HttpError::Other(_)));
+
+        // The truncated body is never returned.
+        assert_eq!(response.bytes().await, Err(error.clone()));
+        assert_eq!(response.text(None).await, Err(error));
+    }
+
+    #[tokio::test]
+    async fn test_handle_invalid_url() {
+        let response = handle(Method::Get, "example.com".to_string(), None, None).await;
+        assert!(matches!(response, Err(HttpError::InvalidUrl(_))));
+
+        let response = handle(Method::Get, "ftp://example.com".to_string(), None, None).await;
+        assert!(matches!(response, Err(HttpError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_handle_invalid_header() {
+        let headers = vec![("Bad Header".to_string(), "value".to_string())];
+        let response = handle(Method::Get, URL.to_string(), Some(headers), None).await;
+        assert_eq!(
+            response.unwrap_err(),
+            HttpError::InvalidHeader("Bad Header".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_dns_error() {
+        let url = "http://midoku.invalid".to_string();
+        let response = handle(Method::Get, url, None, None).await;
+        assert!(matches!(response, Err(HttpError::Dns(_))));
+    }
+
+    #[tokio::test]
+    async fn test_handle_connect_error() {
+        // Bind then drop a listener to get a port nothing listens on.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let response = handle(Method::Get, url, None, None).await;
+        assert!(matches!(response, Err(HttpError::Connect(_))));
+    }
+
+    #[tokio::test]
+    async fn test_handle_tls_error() {
+        // The server answers the TLS handshake in plain text.
+        let (url, _) = serve(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n".to_vec());
+        let url = url.replace("http://", "https://");
+
+        let response = handle(Method::Get, url, None, None).await;
+        assert!(matches!(response, Err(HttpError::Tls(_))));
+    }
+
+    #[tokio::test]
+    async fn test_handle_timeout() {
+        // The server accepts the connection but never answers.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let client = crate::client::HttpConfig {
+            timeout: Some(std::time::Duration::from_millis(100)),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+        let response = handle_with_client(&client, Method::Get, url, None, None).await;
+        assert_eq!(response.unwrap_err(), HttpError::Timeout);
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_handle_too_many_redirects() {
+        let (url, _) = serve(
+            b"HTTP/1.1 302 Found\r\n\
+            location: /\r\n\
+            content-length: 0\r\n\r\n"
+                .to_vec(),
+        );
+
+        let client = Client::builder()
+            .redirect(reqwest::redirect::Policy::limited(0))
+            .build()
+            .unwrap();
+        let response = handle_with_client(&client, Method::Get, url, None, None).await;
+        assert_eq!(response.unwrap_err(), HttpError::TooManyRedirects);
+    }
+
+    #[tokio::test]
+    async fn test_handle_redirect_not_followed() {
+        let (url, _) = serve(
+            b"HTTP/1.1 302 Found\r\n\
+            location: /manga\r\n\
+            content-length: 0\r\n\r\n"
+                .to_vec(),
+        );
+
+        // The host follows the redirects itself.
+        let client = crate::client::HttpConfig::default().build().unwrap();
+        let response = handle_with_client(&client, Method::Get, url.clone(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), 302);
+        assert_eq!(response.url(), Some(format!("{url}/").as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_handle_gzip() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"Hello, World!").unwrap();
+        let body = encoder.finish().unwrap();
+
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-encoding: gzip\r\ncontent-length: {}\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend(body);
+        let (url, request) = serve(response);
+
+        let mut response = handle(Method::Get, url, None, None).await.unwrap();
+        assert_eq!(response.bytes().await.unwrap(), b"Hello, World!");
+        // The headers describe the decoded body.
+        assert!(response
+            .headers()
+            .iter()
+            .all(|(name, _)| name != "content-encoding" && name != "content-length"));
+
+        let request = String::from_utf8(request.recv().unwrap()).unwrap();
+        assert!(request.contains("accept-encoding: zstd,gzip,br\r\n"));
+    }
 }
@@ -0,0 +1,377 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use reqwest::Url;
+use url::Host;
+
+use crate::types::HttpError;
+
+/// Network access granted to a source.
+///
+/// The default policy allows any request, which is only safe for trusted
+/// sources.
+///
+/// # Example
+/// ```ignore
+/// let policy = NetworkPolicy::allow_hosts(["example.com", "*.example-cdn.com"]);
+///
+/// assert!(policy.check("https://example.com/manga").is_ok());
+/// assert!(policy.check("https://img.example-cdn.com/1.png").is_ok());
+/// assert!(policy.check("http://192.168.1.1").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkPolicy {
+    /// Hosts the source may send requests to, `None` allowing any host.
+    ///
+    /// A host is either a domain, e.g. `example.com`, which only matches
+    /// itself, or a wildcard, e.g. `*.example.com`, which matches any of its
+    /// subdomains. Domains are compared case-insensitively.
+    pub allowed_hosts: Option<Vec<String>>,
+
+    /// Whether the source may send requests to loopback, private and
+    /// link-local addresses, e.g. `127.0.0.1` or `192.168.1.1`.
+    ///
+    /// Addresses in URLs are checked by [`NetworkPolicy::check`]. Host names
+    /// are only resolved when connecting, so they are checked by a client
+    /// built with [`HttpConfig::build_with_policy`](crate::client::HttpConfig::build_with_policy).
+    pub allow_private_addresses: bool,
+}
+
+impl NetworkPolicy {
+    /// Policy allowing requests to the given hosts only, and to no private
+    /// address.
+    pub fn allow_hosts<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed_hosts: Some(hosts.into_iter().map(Into::into).collect()),
+            allow_private_addresses: false,
+        }
+    }
+
+    /// Returns `true` if the policy allows any request.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed_hosts.is_none() && self.allow_private_addresses
+    }
+
+    /// Restrict the allowed hosts to those also matching one of `hosts`, e.g.
+    /// the hosts a source declares it needs.
+    ///
+    /// A host is then allowed only if it is allowed both by the policy and by
+    /// `hosts`.
+    pub fn restrict_to_hosts(&mut self, hosts: &[String]) {
+        let allowed_hosts = match &self.allowed_hosts {
+            None => hosts.to_vec(),
+            Some(allowed_hosts) => allowed_hosts
+                .iter()
+                .flat_map(|allowed_host| {
+                    hosts.iter().filter_map(move |host| {
+                        if pattern_covers(allowed_host, host) {
+                            Some(host.clone())
+                        } else if pattern_covers(host, allowed_host) {
+                            Some(allowed_host.clone())
+                        } else {
+                            None
+                        }
+                    })
+                })
+                .collect(),
+        };
+
+        self.allowed_hosts = Some(allowed_hosts);
+    }
+
+    /// Check that the policy allows a request to `url`.
+    ///
+    /// Returns [`HttpError::Blocked`] if it does not. Host names are not
+    /// resolved, their addresses are checked when connecting, see
+    /// [`NetworkPolicy::allow_private_addresses`].
+    pub fn check(&self, url: &str) -> Result<(), HttpError> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+
+        let url = Url::parse(url).map_err(|err| HttpError::InvalidUrl(err.to_string()))?;
+        let Some(host) = url.host() else {
+            return Err(HttpError::InvalidUrl("empty host".to_string()));
+        };
+
+        if !self.is_allowed_host(&host) {
+            return Err(HttpError::Blocked(format!("host `{host}` is not allowed")));
+        }
+
+        if self.allow_private_addresses {
+            return Ok(());
+        }
+
+        let is_private = match host {
+            // Always resolved to a loopback address, see RFC 6761.
+            Host::Domain(domain) => {
+                let domain = domain.to_ascii_lowercase();
+                domain == "localhost" || domain.ends_with(".localhost")
+            }
+            Host::Ipv4(address) => is_private_address(&IpAddr::V4(address)),
+            Host::Ipv6(address) => is_private_address(&IpAddr::V6(address)),
+        };
+
+        match is_private {
+            true => Err(HttpError::Blocked(format!(
+                "host `{host}` is a private address"
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    fn is_allowed_host(&self, host: &Host<&str>) -> bool {
+        let Some(allowed_hosts) = &self.allowed_hosts else {
+            return true;
+        };
+
+        let host = host.to_string();
+        allowed_hosts
+            .iter()
+            .any(|allowed_host| pattern_covers(allowed_host, &host))
+    }
+}
+
+impl Default for NetworkPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: None,
+            allow_private_addresses: true,
+        }
+    }
+}
+
+/// Returns `true` if every host matched by `other`, a host or a wildcard, is
+/// matched by the allowed host `pattern`.
+fn pattern_covers(pattern: &str, other: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let other = other.to_ascii_lowercase();
+    if pattern == other {
+        return true;
+    }
+
+    // A wildcard is covered by another wildcard matching its domain.
+    let host = other.strip_prefix("*.").unwrap_or(&other);
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|subdomain| subdomain.len() > 1 && subdomain.ends_with('.')),
+        None => false,
+    }
+}
+
+/// Returns `true` if `address` is not reachable on the public internet, e.g.
+/// a loopback or private network address.
+///
+/// IPv6 addresses embedding an IPv4 address are checked against the IPv4
+/// address, as they may be routed to it.
+pub(crate) fn is_private_address(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_private_ipv4(address),
+        IpAddr::V6(address) => match embedded_ipv4(address) {
+            Some(address) => is_private_ipv4(&address),
+            None => is_private_ipv6(address),
+        },
+    }
+}
+
+fn is_private_ipv4(address: &Ipv4Addr) -> bool {
+    let [a, b, c, _] = address.octets();
+
+    address.is_unspecified()
+        || address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_broadcast()
+        || address.is_multicast()
+        || address.is_documentation()
+        // 0.0.0.0/8, "this network"
+        || a == 0
+        // 100.64.0.0/10, shared address space
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // 192.0.0.0/24, IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // 198.18.0.0/15, benchmarking
+        || (a == 198 && (b & 0b1111_1110) == 18)
+        // 240.0.0.0/4, reserved
+        || a >= 240
+}
+
+fn is_private_ipv6(address: &Ipv6Addr) -> bool {
+    let [a, b, ..] = address.segments();
+
+    address.is_unspecified()
+        || address.is_loopback()
+        || address.is_multicast()
+        // fc00::/7, unique local addresses
+        || (a & 0xfe00) == 0xfc00
+        // fe80::/10, link-local addresses
+        || (a & 0xffc0) == 0xfe80
+        // 64:ff9b:1::/48, local-use IPv4/IPv6 translation
+        || (a == 0x64 && b == 0xff9b)
+        // 2001:db8::/32, documentation
+        || (a == 0x2001 && b == 0xdb8)
+}
+
+/// Returns the IPv4 address embedded in `address`, if any.
+///
+/// This covers IPv4-mapped (`::ffff:0:0/96`), IPv4-compatible (`::/96`),
+/// NAT64 (`64:ff9b::/96`) and 6to4 (`2002::/16`) addresses.
+fn embedded_ipv4(address: &Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = address.segments();
+    let octets = address.octets();
+
+    match segments {
+        // `::` and `::1` are checked as IPv6 addresses.
+        _ if address.is_unspecified() || address.is_loopback() => None,
+        [0, 0, 0, 0, 0, 0 | 0xffff, ..] => address.to_ipv4(),
+        [0x64, 0xff9b, 0, 0, 0, 0, ..] => Some(Ipv4Addr::new(
+            octets[12], octets[13], octets[14], octets[15],
+        )),
+        [0x2002, ..] => Some(Ipv4Addr::new(octets[2], octets[3], octets[4], octets[5])),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_policy_default() {
+        let policy = NetworkPolicy::default();
+
+        assert!(policy.is_unrestricted());
+        assert!(policy.check("http://127.0.0.1").is_ok());
+        assert!(policy.check("https://example.com").is_ok());
+    }
+
+    #[test]
+    fn test_network_policy_allowed_hosts() {
+        let policy = NetworkPolicy {
+            allowed_hosts: Some(vec!["Example.com".to_string(), "*.cdn.com".to_string()]),
+            allow_private_addresses: true,
+        };
+
+        assert!(policy.check("https://example.com/manga").is_ok());
+        assert!(policy.check("https://EXAMPLE.com").is_ok());
+        assert!(policy.check("https://img.cdn.com/1.png").is_ok());
+        assert!(policy.check("https://a.b.cdn.com/1.png").is_ok());
+
+        let res = policy.check("https://www.example.com");
+        assert_eq!(
+            res,
+            Err(HttpError::Blocked(
+                "host `www.example.com` is not allowed".to_string()
+            ))
+        );
+        assert!(policy.check("https://cdn.com").is_err());
+        assert!(policy.check("https://evilcdn.com").is_err());
+        assert!(policy.check("https://example.com.evil.com").is_err());
+    }
+
+    #[test]
+    fn test_network_policy_private_addresses() {
+        let policy = NetworkPolicy {
+            allowed_hosts: None,
+            allow_private_addresses: false,
+        };
+
+        for url in [
+            "http://127.0.0.1:8080",
+            "http://10.0.0.1",
+            "http://172.16.0.1",
+            "http://192.168.1.1",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1",
+            "http://0.0.0.0",
+            "http://[::1]",
+            "http://[fd00::1]",
+            "http://[fe80::1]",
+            "http://[::ffff:127.0.0.1]",
+            "http://localhost",
+            "http://0.1.2.3",
+            "http://100.127.255.254",
+            "http://192.0.0.8",
+            "http://198.19.0.1",
+            "http://240.0.0.1",
+            "http://255.255.255.255",
+            "http://224.0.0.1",
+            "http://192.0.2.1",
+            "http://[64:ff9b::10.0.0.1]",
+            "http://[64:ff9b:1::1]",
+            "http://[2002:a00:1::]",
+            "http://[::127.0.0.1]",
+            "http://[ff02::1]",
+            "http://[2001:db8::1]",
+        ] {
+            let res = policy.check(url);
+            assert!(matches!(res, Err(HttpError::Blocked(_))), "{url}");
+        }
+
+        assert!(policy.check("http://93.184.215.14").is_ok());
+        assert!(policy.check("http://[2606:2800:21f:cb07::1]").is_ok());
+        assert!(policy.check("http://100.128.0.1").is_ok());
+        assert!(policy.check("http://198.20.0.1").is_ok());
+        assert!(policy.check("http://[64:ff9b::5db8:d70e]").is_ok());
+        assert!(policy.check("http://[2002:5db8:d70e::]").is_ok());
+        // Resolved when connecting.
+        assert!(policy.check("http://example.com").is_ok());
+    }
+
+    #[test]
+    fn test_network_policy_restrict_to_hosts() {
+        let hosts = |hosts: &[&str]| {
+            hosts
+                .iter()
+                .map(|host| host.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut policy = NetworkPolicy::default();
+        policy.restrict_to_hosts(&hosts(&["example.com"]));
+        assert_eq!(policy.allowed_hosts, Some(hosts(&["example.com"])));
+        assert!(policy.allow_private_addresses);
+
+        let mut policy = NetworkPolicy::allow_hosts(["example.com", "*.cdn.com", "*.img.com"]);
+        policy.restrict_to_hosts(&hosts(&[
+            "Example.com",
+            "a.cdn.com",
+            "*.img.com",
+            "*.b.cdn.com",
+            "evil.com",
+        ]));
+        assert_eq!(
+            policy.allowed_hosts,
+            Some(hosts(&[
+                "Example.com",
+                "a.cdn.com",
+                "*.b.cdn.com",
+                "*.img.com"
+            ]))
+        );
+
+        let mut policy = NetworkPolicy::allow_hosts(["a.cdn.com", "*.b.cdn.com"]);
+        policy.restrict_to_hosts(&hosts(&["*.cdn.com"]));
+        assert_eq!(
+            policy.allowed_hosts,
+            Some(hosts(&["a.cdn.com", "*.b.cdn.com"]))
+        );
+
+        let mut policy = NetworkPolicy::allow_hosts(["example.com"]);
+        policy.restrict_to_hosts(&[]);
+        assert!(policy.check("https://example.com").is_err());
+    }
+
+    #[test]
+    fn test_network_policy_allow_hosts() {
+        let policy = NetworkPolicy::allow_hosts(["example.com"]);
+
+        assert_eq!(policy.allowed_hosts, Some(vec!["example.com".to_string()]));
+        assert!(!policy.allow_private_addresses);
+        assert!(!policy.is_unrestricted());
+    }
+}
@@ -0,0 +1,176 @@
+use reqwest::Url;
+
+use crate::transport::Request;
+use crate::types::{HttpError, IncomingResponse, Method};
+
+/// Headers removed from a request redirected to another origin, as they may
+/// hold the credentials of the source.
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "cookie", "proxy-authorization"];
+
+/// Headers describing the body of a request, removed when the body is.
+const BODY_HEADERS: &[&str] = &[
+    "content-type",
+    "content-length",
+    "content-encoding",
+    "transfer-encoding",
+];
+
+/// Returns the request following the redirect `response` to `request`, or
+/// `None` if the response is not a redirect.
+///
+/// Like browsers, a `303 See Other`, or a `301` or `302` to a `POST` request,
+/// is followed with a `GET` request without body. The credentials of the
+/// request are not sent to another origin.
+///
+/// Returns [`HttpError::InvalidUrl`] if the `Location` header of the response
+/// is not a valid URL.
+pub fn redirect_request(
+    request: &Request,
+    response: &IncomingResponse,
+) -> Result<Option<Request>, HttpError> {
+    let status_code = response.status_code();
+    if !matches!(status_code, 301 | 302 | 303 | 307 | 308) {
+        return Ok(None);
+    }
+    let Some((_, location)) = response
+        .headers()
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("location"))
+    else {
+        return Ok(None);
+    };
+
+    let invalid_url = |err: url::ParseError| HttpError::InvalidUrl(err.to_string());
+    let previous_url = Url::parse(&request.url).map_err(invalid_url)?;
+    let url = previous_url.join(location).map_err(invalid_url)?;
+
+    let mut headers = request.headers.clone();
+    let mut raw_headers = request.raw_headers.clone();
+    if url.origin() != previous_url.origin() {
+        headers.retain(|(name, _)| !is_one_of(name, SENSITIVE_HEADERS));
+        raw_headers.retain(|(name, _)| !is_one_of(name, SENSITIVE_HEADERS));
+    }
+
+    let (method, body) = match (status_code, &request.method) {
+        (303, Method::Head) => (Method::Head, None),
+        (303, _) | (301 | 302, Method::Post) => {
+            headers.retain(|(name, _)| !is_one_of(name, BODY_HEADERS));
+            raw_headers.retain(|(name, _)| !is_one_of(name, BODY_HEADERS));
+            (Method::Get, None)
+        }
+        _ => (request.method.clone(), request.body.clone()),
+    };
+
+    Ok(Some(Request {
+        method,
+        url: url.to_string(),
+        headers,
+        raw_headers,
+        body,
+    }))
+}
+
+fn is_one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|other| name.eq_ignore_ascii_case(other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, url: &str) -> Request {
+        Request {
+            method,
+            url: url.to_string(),
+            headers: vec![
+                ("Authorization".to_string(), "Bearer token".to_string()),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ],
+            raw_headers: vec![],
+            body: Some(b"{}".to_vec()),
+        }
+    }
+
+    fn redirect(status_code: u16, location: &str) -> IncomingResponse {
+        let headers = vec![("Location".to_string(), location.to_string())];
+        IncomingResponse::new(status_code, headers, vec![])
+    }
+
+    #[test]
+    fn test_redirect_request() {
+        let request = request(Method::Put, "https://example.com/manga/one-piece");
+
+        let next = redirect_request(&request, &redirect(301, "/manga/13"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.method, Method::Put);
+        assert_eq!(next.url, "https://example.com/manga/13");
+        assert_eq!(next.headers, request.headers);
+        assert_eq!(next.body, request.body);
+
+        let response = IncomingResponse::new(200, vec![], vec![]);
+        assert_eq!(redirect_request(&request, &response), Ok(None));
+        // A redirect without location cannot be followed.
+        let response = IncomingResponse::new(302, vec![], vec![]);
+        assert_eq!(redirect_request(&request, &response), Ok(None));
+
+        let res = redirect_request(&request, &redirect(302, "http://[::1"));
+        assert!(matches!(res, Err(HttpError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_redirect_request_method() {
+        let post = request(Method::Post, "https://example.com/login");
+        for status_code in [301, 302, 303] {
+            let next = redirect_request(&post, &redirect(status_code, "/home"))
+                .unwrap()
+                .unwrap();
+            assert_eq!(next.method, Method::Get, "{status_code}");
+            assert_eq!(next.body, None, "{status_code}");
+            assert_eq!(
+                next.headers,
+                vec![("Authorization".to_string(), "Bearer token".to_string())]
+            );
+        }
+
+        for status_code in [307, 308] {
+            let next = redirect_request(&post, &redirect(status_code, "/home"))
+                .unwrap()
+                .unwrap();
+            assert_eq!(next.method, Method::Post, "{status_code}");
+            assert_eq!(next.body, post.body, "{status_code}");
+        }
+
+        let head = request(Method::Head, "https://example.com/manga");
+        let next = redirect_request(&head, &redirect(303, "/manga/13"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.method, Method::Head);
+    }
+
+    #[test]
+    fn test_redirect_request_other_origin() {
+        let mut request = request(Method::Get, "https://example.com/manga");
+        request
+            .headers
+            .push(("cookie".to_string(), "session=1".to_string()));
+        request
+            .raw_headers
+            .push(("Cookie".to_string(), b"name=caf\xe9".to_vec()));
+
+        let next = redirect_request(&request, &redirect(302, "https://cdn.example.com/manga"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            next.headers,
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+        assert!(next.raw_headers.is_empty());
+
+        // Same host, but another scheme.
+        let next = redirect_request(&request, &redirect(302, "http://example.com/manga"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.headers.len(), 1);
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+use crate::types::{HttpError, IncomingResponse, Method};
+
+/// Policy to retry requests that failed transiently, e.g. with a
+/// `503 Service Unavailable` response.
+///
+/// Only idempotent requests are retried, see [`Method::is_idempotent`]. The
+/// delay before a retry doubles after each attempt, with a random jitter, or
+/// is given by the `Retry-After` header of the response.
+///
+/// # Example
+/// ```ignore
+/// let policy = RetryPolicy {
+///     max_attempts: 5,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts of a request, including the first one.
+    ///
+    /// A request is never retried if this is 1 or less.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled before each following retry.
+    pub initial_backoff: Duration,
+
+    /// Maximum delay before a retry.
+    ///
+    /// A response asking to retry later than this with `Retry-After` is
+    /// returned as is.
+    pub max_backoff: Duration,
+
+    /// Status codes of the responses to retry.
+    pub retry_statuses: Vec<u16>,
+}
+
+impl RetryPolicy {
+    /// Policy never retrying a request.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the delay to wait before retrying a request, or `None` if it
+    /// should not be retried.
+    ///
+    /// `attempt` is the number of attempts made so far, and `result` the
+    /// result of the last one. Requests are retried when they time out, fail
+    /// to connect, or receive a response with one of the
+    /// [`RetryPolicy::retry_statuses`].
+    pub fn retry_delay(
+        &self,
+        method: &Method,
+        attempt: u32,
+        result: &Result<IncomingResponse, HttpError>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !method.is_idempotent() {
+            return None;
+        }
+
+        match result {
+            Ok(response) if self.retry_statuses.contains(&response.status_code()) => {
+                match retry_after(response.headers()) {
+                    Some(delay) if delay > self.max_backoff => None,
+                    Some(delay) => Some(delay),
+                    None => Some(self.backoff(attempt)),
+                }
+            }
+            Err(HttpError::Timeout | HttpError::Connect(_)) => Some(self.backoff(attempt)),
+            _ => None,
+        }
+    }
+
+    /// Returns the delay before the retry following the given attempt.
+    ///
+    /// The delay is picked at random between half and all of the exponential
+    /// backoff, so that clients failing together do not retry together.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+
+        let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        backoff.mul_f64(0.5 + jitter / 2.0)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            retry_statuses: vec![429, 502, 503, 504],
+        }
+    }
+}
+
+/// Returns the delay given by the `Retry-After` header, either as a number of
+/// seconds or as a date.
+pub fn retry_after(headers: &[(String, String)]) -> Option<Duration> {
+    let (_, value) = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))?;
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    // A date in the past means the request can be retried right away.
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status_code: u16, headers: Vec<(&str, &str)>) -> IncomingResponse {
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        IncomingResponse::new(status_code, headers, vec![])
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let headers = vec![("Retry-After".to_string(), "120".to_string())];
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+
+        let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(60));
+        let headers = vec![("retry-after".to_string(), date)];
+        let delay = retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(58) && delay <= Duration::from_secs(60));
+
+        let headers = vec![(
+            "retry-after".to_string(),
+            "Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+        )];
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        let headers = vec![("retry-after".to_string(), "soon".to_string())];
+        assert_eq!(retry_after(&headers), None);
+        assert_eq!(retry_after(&[]), None);
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        for (attempt, backoff) in [(1, 100), (2, 200), (3, 300), (10, 300), (100, 300)] {
+            let delay = policy.backoff(attempt);
+            assert!(delay >= Duration::from_millis(backoff / 2), "{attempt}");
+            assert!(delay <= Duration::from_millis(backoff), "{attempt}");
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_retry_delay() {
+        let policy = RetryPolicy::default();
+
+        let result = Ok(response(503, vec![]));
+        assert!(policy.retry_delay(&Method::Get, 1, &result).is_some());
+        assert!(policy.retry_delay(&Method::Get, 2, &result).is_some());
+        // Every attempt was made.
+        assert!(policy.retry_delay(&Method::Get, 3, &result).is_none());
+        // POST is not idempotent.
+        assert!(policy.retry_delay(&Method::Post, 1, &result).is_none());
+
+        let result = Ok(response(429, vec![("retry-after", "2")]));
+        assert_eq!(
+            policy.retry_delay(&Method::Get, 1, &result),
+            Some(Duration::from_secs(2))
+        );
+        let result = Ok(response(429, vec![("retry-after", "3600")]));
+        assert!(policy.retry_delay(&Method::Get, 1, &result).is_none());
+
+        let result = Ok(response(200, vec![]));
+        assert!(policy.retry_delay(&Method::Get, 1, &result).is_none());
+        let result = Ok(response(404, vec![]));
+        assert!(policy.retry_delay(&Method::Get, 1, &result).is_none());
+
+        let result = Err(HttpError::Timeout);
+        assert!(policy.retry_delay(&Method::Put, 1, &result).is_some());
+        let result = Err(HttpError::InvalidHeader("a b".to_string()));
+        assert!(policy.retry_delay(&Method::Get, 1, &result).is_none());
+
+        let result = Ok(response(503, vec![]));
+        let policy = RetryPolicy::disabled();
+        assert!(policy.retry_delay(&Method::Get, 1, &result).is_none());
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::outgoing_handler::{send_with_client, Client, DEFAULT_CLIENT};
+use crate::types::{HttpError, IncomingResponse, Method, RequestBody};
+
+/// A future returned by [`HttpTransport::send`].
+pub type SendFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<IncomingResponse, HttpError>> + Send + 'a>>;
+
+/// An outgoing request, as sent by a source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Headers sent after `headers`, with their values as given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_headers: Vec<(String, Vec<u8>)>,
+    #[serde(default, with = "crate::fixture::body::option")]
+    pub body: Option<Vec<u8>>,
+}
+
+impl Request {
+    /// Encode `body` as the body of the request, and add its `Content-Type`
+    /// header, see [`RequestBody::encode`].
+    ///
+    /// A `Content-Type` header already in the headers or in the raw headers
+    /// is kept, except for a multipart body.
+    pub fn set_body(&mut self, body: RequestBody) {
+        let bytes = body.encode_with_raw_headers(&mut self.headers, &mut self.raw_headers);
+        self.body = Some(bytes);
+    }
+}
+
+/// Transport sending the HTTP requests of sources.
+///
+/// The host injects the transport into the bindings, which allows replacing
+/// the network with e.g. a [`MockTransport`] in tests. A transport should
+/// return redirect responses as is, the host follows them itself, see
+/// [`redirect_request`](crate::redirect::redirect_request).
+pub trait HttpTransport: Send + Sync {
+    /// Send the request and wait for the response.
+    fn send(&self, request: Request) -> SendFuture<'_>;
+}
+
+/// Transport sending the requests over the network with a reqwest client.
+///
+/// This is the default transport.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl Default for ReqwestTransport {
+    /// Send the requests with the client of
+    /// [`handle`](crate::outgoing_handler::handle).
+    fn default() -> Self {
+        Self::new(DEFAULT_CLIENT.clone())
+    }
+}
+
+impl From<Client> for ReqwestTransport {
+    fn from(client: Client) -> Self {
+        Self::new(client)
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: Request) -> SendFuture<'_> {
+        Box::pin(send_with_client(&self.client, request))
+    }
+}
+
+/// In-memory transport serving canned responses, for tests.
+///
+/// Responses are matched by method and URL. Requests without a matching
+/// response fail with [`HttpError::Other`], which is not retried, so that a
+/// missing response is reported right away.
+///
+/// # Example
+/// ```ignore
+/// let transport = MockTransport::new().with_response(
+///     Method::Get,
+///     "https://example.com/manga",
+///     IncomingResponse::new(200, vec![], b"[]".to_vec()),
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: HashMap<(Method, String), IncomingResponse>,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `response` to every request with the given method and URL.
+    ///
+    /// The response must be created with [`IncomingResponse::new`], a
+    /// response streamed from the network cannot be served again.
+    pub fn with_response(
+        mut self,
+        method: Method,
+        url: impl Into<String>,
+        response: IncomingResponse,
+    ) -> Self {
+        self.responses.insert((method, url.into()), response);
+        self
+    }
+
+    /// Returns the requests received so far, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: Request) -> SendFuture<'_> {
+        let response = self
+            .responses
+            .get(&(request.method.clone(), request.url.clone()))
+            .and_then(IncomingResponse::try_clone)
+            .ok_or_else(|| {
+                HttpError::Other(format!(
+                    "no mock response for {} {}",
+                    request.method.as_str(),
+                    request.url
+                ))
+            });
+        self.requests.lock().unwrap().push(request);
+
+        Box::pin(std::future::ready(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://example.com";
+
+    fn request(method: Method, url: &str) -> Request {
+        Request {
+            method,
+            url: url.to_string(),
+            headers: vec![],
+            raw_headers: vec![],
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_request_set_body() {
+        let mut request = request(Method::Post, URL);
+        request.set_body(RequestBody::Json("{}".to_string()));
+        assert_eq!(request.body, Some(b"{}".to_vec()));
+        assert_eq!(
+            request.headers,
+            vec![("content-type".to_string(), "application/json".to_string())]
+        );
+
+        // The content type set by the source is kept, whatever its form.
+        let mut request = self::request(Method::Post, URL);
+        request.raw_headers = vec![("Content-Type".to_string(), b"text/plain".to_vec())];
+        request.set_body(RequestBody::Json("{}".to_string()));
+        assert!(request.headers.is_empty());
+
+        request.set_body(RequestBody::Multipart(vec![]));
+        assert!(request.raw_headers.is_empty());
+        assert_eq!(request.headers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_transport_invalid_raw_header() {
+        let mut request = request(Method::Get, URL);
+        request.raw_headers = vec![("x-name".to_string(), b"a\nb".to_vec())];
+
+        let response = ReqwestTransport::default().send(request).await;
+        assert_eq!(
+            response.unwrap_err(),
+            HttpError::InvalidHeader("x-name".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport() {
+        let transport = MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(200, vec![], b"Hello".to_vec()),
+        );
+
+        let mut response = transport.send(request(Method::Get, URL)).await.unwrap();
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.bytes().await.unwrap(), b"Hello");
+
+        // The response is served again.
+        assert!(transport.send(request(Method::Get, URL)).await.is_ok());
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport_unmatched() {
+        let transport = MockTransport::new().with_response(
+            Method::Get,
+            URL,
+            IncomingResponse::new(200, vec![], vec![]),
+        );
+
+        let response = transport.send(request(Method::Post, URL)).await;
+        assert_eq!(
+            response.unwrap_err(),
+            HttpError::Other("no mock response for POST https://example.com".to_string())
+        );
+
+        let response = transport
+            .send(request(Method::Get, "https://example.org"))
+            .await;
+        assert!(response.is_err());
+
+        assert_eq!(
+            transport.requests(),
+            vec![
+                request(Method::Post, URL),
+                request(Method::Get, "https://example.org")
+            ]
+        );
+    }
+}
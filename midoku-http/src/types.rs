@@ -1,9 +1,75 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Write};
+
+use encoding_rs::Encoding;
+use serde::{Deserialize, Serialize};
 use wasmtime::component::{ComponentType, Lift, Lower};
 
-#[derive(ComponentType, Lift, Lower, Debug, Clone, Copy, PartialEq)]
+/// An HTTP method.
+#[derive(ComponentType, Lift, Lower, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[component(variant)]
+#[serde(rename_all = "lowercase")]
+pub enum Method {
+    #[component(name = "get")]
+    Get,
+    #[component(name = "post")]
+    Post,
+    #[component(name = "put")]
+    Put,
+    #[component(name = "head")]
+    Head,
+    #[component(name = "delete")]
+    Delete,
+    #[component(name = "patch")]
+    Patch,
+    #[component(name = "options")]
+    Options,
+    #[component(name = "connect")]
+    Connect,
+    #[component(name = "trace")]
+    Trace,
+    /// Any other method, e.g. `PURGE`.
+    #[component(name = "other")]
+    Other(String),
+}
+
+impl Method {
+    /// Returns the name of the method, e.g. `GET`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Head => "HEAD",
+            Self::Delete => "DELETE",
+            Self::Patch => "PATCH",
+            Self::Options => "OPTIONS",
+            Self::Connect => "CONNECT",
+            Self::Trace => "TRACE",
+            Self::Other(method) => method,
+        }
+    }
+
+    /// Returns `true` if sending the request several times has the same
+    /// effect as sending it once, so that it can be retried safely.
+    pub fn is_idempotent(&self) -> bool {
+        matches!(
+            self,
+            Self::Get | Self::Put | Self::Head | Self::Delete | Self::Options | Self::Trace
+        )
+    }
+}
+
+/// An HTTP method of `midoku:http@0.1.0`, which only has the methods below.
+///
+/// Sources built against 0.1.0 send requests with this type, the host converts
+/// it to a [`Method`].
+#[derive(ComponentType, Lift, Lower, Debug, Clone, Copy, PartialEq, Eq)]
 #[component(enum)]
 #[repr(u8)]
-pub enum Method {
+pub enum MethodV0_1 {
     #[component(name = "get")]
     Get,
     #[component(name = "post")]
@@ -16,32 +82,671 @@ pub enum Method {
     Delete,
 }
 
+impl From<MethodV0_1> for Method {
+    fn from(value: MethodV0_1) -> Self {
+        match value {
+            MethodV0_1::Get => Self::Get,
+            MethodV0_1::Post => Self::Post,
+            MethodV0_1::Put => Self::Put,
+            MethodV0_1::Head => Self::Head,
+            MethodV0_1::Delete => Self::Delete,
+        }
+    }
+}
+
+/// An error sending an HTTP request or reading its response.
+#[derive(ComponentType, Lift, Lower, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[component(variant)]
+#[serde(rename_all = "kebab-case")]
+pub enum HttpError {
+    /// The URL is not valid, or its scheme is not supported.
+    #[component(name = "invalid-url")]
+    InvalidUrl(String),
+    /// A header name or value is not valid, contains the name of the header.
+    #[component(name = "invalid-header")]
+    InvalidHeader(String),
+    /// The host name could not be resolved.
+    #[component(name = "dns")]
+    Dns(String),
+    /// The connection to the server could not be established.
+    #[component(name = "connect")]
+    Connect(String),
+    /// The TLS handshake failed, e.g. because of an invalid certificate.
+    #[component(name = "tls")]
+    Tls(String),
+    /// The request, or reading its response, timed out.
+    #[component(name = "timeout")]
+    Timeout,
+    /// The server redirected the request too many times.
+    #[component(name = "too-many-redirects")]
+    TooManyRedirects,
+    /// The response body is larger than the host allows.
+    #[component(name = "body-too-large")]
+    BodyTooLarge,
+    /// The host does not allow the source to send requests to the URL, see
+    /// [`NetworkPolicy`](crate::policy::NetworkPolicy).
+    #[component(name = "blocked")]
+    Blocked(String),
+    /// Any other error, e.g. the connection was closed while reading the
+    /// response.
+    #[component(name = "other")]
+    Other(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl(message) => write!(f, "invalid URL: {message}"),
+            Self::InvalidHeader(name) => write!(f, "invalid header `{name}`"),
+            Self::Dns(message) => write!(f, "DNS error: {message}"),
+            Self::Connect(message) => write!(f, "connection error: {message}"),
+            Self::Tls(message) => write!(f, "TLS error: {message}"),
+            Self::Timeout => write!(f, "request timed out"),
+            Self::TooManyRedirects => write!(f, "too many redirects"),
+            Self::BodyTooLarge => write!(f, "response body too large"),
+            Self::Blocked(message) => write!(f, "request blocked: {message}"),
+            Self::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// The body of a request, encoded by the host, see [`RequestBody::encode`].
+#[derive(ComponentType, Lift, Lower, Debug, Clone, PartialEq, Eq)]
+#[component(variant)]
+pub enum RequestBody {
+    /// Bytes sent as is.
+    #[component(name = "bytes")]
+    Bytes(Vec<u8>),
+    /// Fields of a form, sent as `application/x-www-form-urlencoded`.
+    #[component(name = "form")]
+    Form(Vec<(String, String)>),
+    /// A JSON document, sent as `application/json`.
+    #[component(name = "json")]
+    Json(String),
+    /// Parts of a form, sent as `multipart/form-data`.
+    #[component(name = "multipart")]
+    Multipart(Vec<MultipartPart>),
+}
+
+/// A part of a `multipart/form-data` body.
+#[derive(ComponentType, Lift, Lower, Debug, Clone, PartialEq, Eq)]
+#[component(record)]
+pub struct MultipartPart {
+    /// The name of the form field.
+    #[component(name = "name")]
+    pub name: String,
+    /// The name of the file, if the part is a file.
+    #[component(name = "filename")]
+    pub filename: Option<String>,
+    /// The content type of the part, `application/octet-stream` by default
+    /// for files.
+    #[component(name = "content-type")]
+    pub content_type: Option<String>,
+    /// The content of the part.
+    #[component(name = "data")]
+    pub data: Vec<u8>,
+}
+
+impl RequestBody {
+    /// Encode the body, and add its `Content-Type` header to `headers`.
+    ///
+    /// A `Content-Type` header already in `headers` is kept, except for a
+    /// multipart body, whose header must give the boundary between its parts.
+    pub fn encode(self, headers: &mut Vec<(String, String)>) -> Vec<u8> {
+        self.encode_with_raw_headers(headers, &mut Vec::new())
+    }
+
+    /// Encode the body like [`RequestBody::encode`], for a request whose
+    /// headers are also given with their values as bytes, see
+    /// [`Request::raw_headers`](crate::transport::Request::raw_headers).
+    pub(crate) fn encode_with_raw_headers(
+        self,
+        headers: &mut Vec<(String, String)>,
+        raw_headers: &mut Vec<(String, Vec<u8>)>,
+    ) -> Vec<u8> {
+        let is_content_type = |name: &str| name.eq_ignore_ascii_case("content-type");
+        if matches!(self, Self::Multipart(_)) {
+            headers.retain(|(name, _)| !is_content_type(name));
+            raw_headers.retain(|(name, _)| !is_content_type(name));
+        }
+
+        let (content_type, bytes) = self.into_parts();
+        let has_content_type = headers.iter().any(|(name, _)| is_content_type(name))
+            || raw_headers.iter().any(|(name, _)| is_content_type(name));
+        if let (Some(content_type), false) = (content_type, has_content_type) {
+            headers.push(("content-type".to_string(), content_type));
+        }
+
+        bytes
+    }
+
+    /// Encode the body, and return its `Content-Type` header, if any.
+    fn into_parts(self) -> (Option<String>, Vec<u8>) {
+        let (content_type, bytes) = match self {
+            Self::Bytes(bytes) => return (None, bytes),
+            Self::Form(fields) => {
+                let form = url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(fields)
+                    .finish();
+                (
+                    "application/x-www-form-urlencoded".to_string(),
+                    form.into_bytes(),
+                )
+            }
+            Self::Json(json) => ("application/json".to_string(), json.into_bytes()),
+            Self::Multipart(parts) => {
+                let boundary = multipart_boundary(&parts);
+                (
+                    format!("multipart/form-data; boundary={boundary}"),
+                    encode_multipart(&parts, &boundary),
+                )
+            }
+        };
+
+        (Some(content_type), bytes)
+    }
+}
+
+/// Returns a random boundary found in none of the parts.
+fn multipart_boundary(parts: &[MultipartPart]) -> String {
+    loop {
+        let random = RandomState::new().build_hasher().finish();
+        let boundary = format!("midoku-boundary-{random:016x}");
+        let found = parts.iter().any(|part| {
+            part.data
+                .windows(boundary.len())
+                .any(|window| window == boundary.as_bytes())
+        });
+        if !found {
+            return boundary;
+        }
+    }
+}
+
+fn encode_multipart(parts: &[MultipartPart], boundary: &str) -> Vec<u8> {
+    // Quotes and line breaks are percent-encoded in names, like browsers do.
+    fn escape(value: &str) -> String {
+        value
+            .replace('"', "%22")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    let mut bytes = Vec::new();
+    for part in parts {
+        bytes.extend(format!("--{boundary}\r\n").as_bytes());
+        bytes.extend(
+            format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                escape(&part.name)
+            )
+            .as_bytes(),
+        );
+        if let Some(filename) = &part.filename {
+            bytes.extend(format!("; filename=\"{}\"", escape(filename)).as_bytes());
+        }
+        bytes.extend(b"\r\n");
+
+        let content_type = match (&part.content_type, &part.filename) {
+            (Some(content_type), _) => Some(content_type.as_str()),
+            (None, Some(_)) => Some("application/octet-stream"),
+            (None, None) => None,
+        };
+        if let Some(content_type) = content_type {
+            bytes.extend(format!("Content-Type: {content_type}\r\n").as_bytes());
+        }
+
+        bytes.extend(b"\r\n");
+        bytes.extend(&part.data);
+        bytes.extend(b"\r\n");
+    }
+    bytes.extend(format!("--{boundary}--\r\n").as_bytes());
+
+    bytes
+}
+
+/// Policy to follow the redirects of a request.
+#[derive(
+    ComponentType, Lift, Lower, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq,
+)]
+#[component(variant)]
+#[serde(rename_all = "kebab-case")]
+pub enum RedirectPolicy {
+    /// Follow up to [`RedirectPolicy::MAX_REDIRECTS`] redirects.
+    #[default]
+    #[component(name = "follow")]
+    Follow,
+    /// Never follow redirects, the redirect response is returned as is.
+    #[component(name = "none")]
+    None,
+    /// Follow up to the given number of redirects.
+    #[component(name = "limited")]
+    Limited(u32),
+}
+
+impl RedirectPolicy {
+    /// Number of redirects followed by [`RedirectPolicy::Follow`], same as
+    /// reqwest.
+    pub const MAX_REDIRECTS: u32 = 10;
+
+    /// Returns the number of redirects to follow before failing with
+    /// [`HttpError::TooManyRedirects`], or `None` if redirects are not
+    /// followed.
+    pub fn max_redirects(&self) -> Option<u32> {
+        match self {
+            Self::Follow => Some(Self::MAX_REDIRECTS),
+            Self::None => None,
+            Self::Limited(max) => Some(*max),
+        }
+    }
+}
+
+/// A redirect followed by a request.
+#[derive(ComponentType, Lift, Lower, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[component(record)]
+pub struct Redirect {
+    /// The URL that redirected the request.
+    #[component(name = "url")]
+    pub url: String,
+    /// The status code of the redirect response, e.g. 301.
+    #[component(name = "status-code")]
+    pub status_code: u16,
+}
+
+/// A response to an HTTP request.
+///
+/// The body is read lazily: a response received from the network only holds
+/// the bytes that were read so far, see [`IncomingBody`].
 #[derive(Debug)]
 pub struct IncomingResponse {
+    url: Option<String>,
+    redirects: Vec<Redirect>,
     status_code: u16,
     headers: Vec<(String, String)>,
-    bytes: Vec<u8>,
+    raw_headers: Vec<(String, Vec<u8>)>,
+    body: Option<IncomingBody>,
 }
 
 impl IncomingResponse {
-    pub(crate) fn new(status_code: u16, headers: Vec<(String, String)>, bytes: Vec<u8>) -> Self {
+    /// Create a response with a body held in memory.
+    pub fn new(status_code: u16, headers: Vec<(String, String)>, bytes: Vec<u8>) -> Self {
+        let raw_headers = headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone().into_bytes()))
+            .collect();
+
         IncomingResponse {
+            url: None,
+            redirects: Vec::new(),
             status_code,
             headers,
-            bytes,
+            raw_headers,
+            body: Some(IncomingBody::new(bytes)),
         }
     }
 
+    /// Create a response streaming its body from the network.
+    pub(crate) fn from_reqwest(response: reqwest::Response) -> Self {
+        let url = response.url().to_string();
+        let status_code = response.status().as_u16();
+        let raw_headers: Vec<(String, Vec<u8>)> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str().to_string(), value.as_bytes().to_vec()))
+            .collect();
+        let headers = raw_headers
+            .iter()
+            .map(|(name, value)| (name.clone(), decode_header_value(value)))
+            .collect();
+
+        IncomingResponse {
+            url: Some(url),
+            redirects: Vec::new(),
+            status_code,
+            headers,
+            raw_headers,
+            body: Some(IncomingBody::from_reqwest(response)),
+        }
+    }
+
+    /// Set the headers of the response with their values as received, see
+    /// [`IncomingResponse::raw_headers`].
+    pub fn with_raw_headers(mut self, raw_headers: Vec<(String, Vec<u8>)>) -> Self {
+        self.headers = raw_headers
+            .iter()
+            .map(|(name, value)| (name.clone(), decode_header_value(value)))
+            .collect();
+        self.raw_headers = raw_headers;
+        self
+    }
+
+    /// Set the URL the response was received from.
+    pub fn with_url(mut self, url: Option<String>) -> Self {
+        self.url = url;
+        self
+    }
+
+    /// Set the redirects followed by the request, see
+    /// [`IncomingResponse::redirects`].
+    pub fn with_redirects(mut self, redirects: Vec<Redirect>) -> Self {
+        self.redirects = redirects;
+        self
+    }
+
+    /// Set the maximum size in bytes of the body read into memory, see
+    /// [`IncomingBody::with_max_size`].
+    pub fn with_max_body_size(mut self, max_size: Option<usize>) -> Self {
+        self.body = self.body.map(|body| body.with_max_size(max_size));
+        self
+    }
+
+    /// Returns the URL the response was received from, after following
+    /// redirects, if it is known.
+    ///
+    /// The URL of a response created with [`IncomingResponse::new`] is not
+    /// known.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Returns the redirects followed by the request, in order.
+    ///
+    /// The redirects are followed by the host, see
+    /// [`redirect_request`](crate::redirect::redirect_request).
+    pub fn redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+
     pub fn status_code(&self) -> u16 {
         self.status_code
     }
 
+    /// Returns the headers of the response, in the order they were received.
+    ///
+    /// A header received several times is listed once per value. Values that
+    /// are not valid UTF-8 are decoded as Latin-1, see
+    /// [`IncomingResponse::raw_headers`] for the bytes as received.
     pub fn headers(&self) -> &Vec<(String, String)> {
         &self.headers
     }
 
-    pub fn bytes(&self) -> &Vec<u8> {
-        &self.bytes
+    /// Returns the headers of the response with their values as received.
+    pub fn raw_headers(&self) -> &Vec<(String, Vec<u8>)> {
+        &self.raw_headers
+    }
+
+    /// Read the rest of the body, and return the bytes not read yet.
+    ///
+    /// The bytes are kept in memory, so that calling this method again
+    /// returns them again. Returns no bytes if the body was taken, and
+    /// [`HttpError::BodyTooLarge`] if the body is larger than allowed, see
+    /// [`IncomingResponse::with_max_body_size`].
+    pub async fn bytes(&mut self) -> Result<&[u8], HttpError> {
+        match &mut self.body {
+            Some(body) => body.buffer().await,
+            None => Ok(&[]),
+        }
+    }
+
+    /// Read the rest of the body, and decode it as text.
+    ///
+    /// The body is decoded with `encoding`, e.g. `shift_jis`, if given, and
+    /// with the charset of the `Content-Type` header otherwise, defaulting to
+    /// UTF-8. A byte order mark takes precedence over both. Invalid sequences
+    /// are replaced with U+FFFD.
+    ///
+    /// Returns [`HttpError::Other`] if the encoding is not supported.
+    pub async fn text(&mut self, encoding: Option<&str>) -> Result<String, HttpError> {
+        let label = encoding.map(str::to_string).or_else(|| self.charset());
+        let encoding = match label {
+            Some(label) => Encoding::for_label(label.trim().as_bytes())
+                .ok_or_else(|| HttpError::Other(format!("unsupported encoding `{label}`")))?,
+            None => encoding_rs::UTF_8,
+        };
+
+        let (text, _, _) = encoding.decode(self.bytes().await?);
+        Ok(text.into_owned())
+    }
+
+    /// Returns the charset of the `Content-Type` header, if any.
+    fn charset(&self) -> Option<String> {
+        let (_, content_type) = self
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))?;
+
+        content_type.split(';').skip(1).find_map(|parameter| {
+            let (name, value) = parameter.split_once('=')?;
+            name.trim()
+                .eq_ignore_ascii_case("charset")
+                .then(|| value.trim().trim_matches('"').to_string())
+        })
+    }
+
+    /// Take the body to stream it, see [`IncomingBody`].
+    ///
+    /// Returns `None` if the body was already taken.
+    pub fn take_body(&mut self) -> Option<IncomingBody> {
+        self.body.take()
+    }
+
+    /// Clone the response if its body is held in memory.
+    ///
+    /// Returns `None` if the body is streamed from the network.
+    pub fn try_clone(&self) -> Option<Self> {
+        let body = match &self.body {
+            Some(body) => Some(body.try_clone()?),
+            None => None,
+        };
+
+        Some(IncomingResponse {
+            url: self.url.clone(),
+            redirects: self.redirects.clone(),
+            status_code: self.status_code,
+            headers: self.headers.clone(),
+            raw_headers: self.raw_headers.clone(),
+            body,
+        })
+    }
+}
+
+/// Decode a header value as UTF-8, or as Latin-1 if it is not valid UTF-8.
+///
+/// Servers commonly send Latin-1 values, decoding them never fails nor loses
+/// information.
+pub(crate) fn decode_header_value(value: &[u8]) -> String {
+    match std::str::from_utf8(value) {
+        Ok(value) => value.to_string(),
+        Err(_) => value.iter().map(|&byte| byte as char).collect(),
+    }
+}
+
+/// The body of a response, read as a stream of bytes.
+///
+/// Only the chunk being read is held in memory, unless the whole body is
+/// buffered with [`IncomingBody::buffer`].
+#[derive(Debug)]
+pub struct IncomingBody {
+    buffer: Vec<u8>,
+    position: usize,
+    stream: Option<reqwest::Response>,
+    max_size: Option<usize>,
+    /// The error reading the stream failed with, returned by every later
+    /// read as the rest of the body is lost.
+    error: Option<HttpError>,
+}
+
+impl IncomingBody {
+    /// Create a body held in memory.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            buffer: bytes,
+            position: 0,
+            stream: None,
+            max_size: None,
+            error: None,
+        }
+    }
+
+    fn from_reqwest(response: reqwest::Response) -> Self {
+        Self {
+            buffer: Vec::new(),
+            position: 0,
+            stream: Some(response),
+            max_size: None,
+            error: None,
+        }
+    }
+
+    /// Set the maximum size in bytes of the body read into memory by
+    /// [`IncomingBody::buffer`], `None` meaning it is unbounded.
+    ///
+    /// Reading the body one chunk at a time is not limited.
+    pub fn with_max_size(mut self, max_size: Option<usize>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns `true` once every byte of the body was read.
+    ///
+    /// The end of a streamed body is only known after a read reaches it.
+    pub fn is_end_of_stream(&self) -> bool {
+        self.position == self.buffer.len() && self.stream.is_none() && self.error.is_none()
+    }
+
+    /// Read up to `max_len` bytes.
+    ///
+    /// Fewer bytes are returned if fewer are available right away. No bytes
+    /// are returned once the end of the body is reached.
+    pub async fn read(&mut self, max_len: usize) -> Result<Vec<u8>, HttpError> {
+        if max_len == 0 || !self.fill().await? {
+            return Ok(Vec::new());
+        }
+
+        let end = self.buffer.len().min(self.position.saturating_add(max_len));
+        let bytes = self.buffer[self.position..end].to_vec();
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    /// Skip up to `len` bytes, and return the number of bytes skipped.
+    ///
+    /// Fewer bytes are skipped if the end of the body is reached.
+    pub async fn skip(&mut self, len: u64) -> Result<u64, HttpError> {
+        let mut skipped = 0;
+        while skipped < len && self.fill().await? {
+            let available = (self.buffer.len() - self.position) as u64;
+            let count = available.min(len - skipped);
+
+            self.position += count as usize;
+            skipped += count;
+        }
+
+        Ok(skipped)
+    }
+
+    /// Read the rest of the body into memory, and return the bytes not read
+    /// yet.
+    ///
+    /// Returns [`HttpError::BodyTooLarge`] as soon as the bytes not read yet
+    /// exceed the maximum size of the body, see
+    /// [`IncomingBody::with_max_size`].
+    pub async fn buffer(&mut self) -> Result<&[u8], HttpError> {
+        let max_size = self.max_size.unwrap_or(usize::MAX);
+        if self.stream.is_some() {
+            self.buffer.drain(..self.position);
+            self.position = 0;
+        }
+
+        // The rest of the body is not read once it is too large.
+        while self.buffer.len() - self.position <= max_size && self.read_chunk().await? {}
+
+        if self.buffer.len() - self.position > max_size {
+            return Err(HttpError::BodyTooLarge);
+        }
+
+        Ok(&self.buffer[self.position..])
+    }
+
+    /// Write the rest of the body to `writer`, one chunk at a time, and return
+    /// the number of bytes written.
+    pub async fn write_to<W: Write>(&mut self, writer: &mut W) -> io::Result<u64> {
+        let mut written = 0;
+        while self.fill().await.map_err(io::Error::other)? {
+            writer.write_all(&self.buffer[self.position..])?;
+            written += (self.buffer.len() - self.position) as u64;
+            self.position = self.buffer.len();
+        }
+        writer.flush()?;
+
+        Ok(written)
+    }
+
+    /// Read the next chunk if every byte of the current one was read.
+    ///
+    /// Returns `false` if the end of the body is reached.
+    async fn fill(&mut self) -> Result<bool, HttpError> {
+        if self.position < self.buffer.len() {
+            return Ok(true);
+        }
+
+        self.buffer.clear();
+        self.position = 0;
+        while self.buffer.is_empty() {
+            if !self.read_chunk().await? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Append the next chunk of the stream to the buffer.
+    ///
+    /// Returns `false` if the end of the body is reached. The stream is kept
+    /// until then, and the error of a failed read is returned by every later
+    /// read.
+    async fn read_chunk(&mut self) -> Result<bool, HttpError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        let Some(stream) = &mut self.stream else {
+            return Ok(false);
+        };
+
+        match stream.chunk().await {
+            Ok(Some(chunk)) => {
+                self.buffer.extend_from_slice(&chunk);
+                Ok(true)
+            }
+            Ok(None) => {
+                self.stream = None;
+                Ok(false)
+            }
+            Err(err) => {
+                let error = HttpError::from(err);
+                self.stream = None;
+                self.error = Some(error.clone());
+                Err(error)
+            }
+        }
+    }
+
+    fn try_clone(&self) -> Option<Self> {
+        if self.stream.is_some() {
+            return None;
+        }
+
+        Some(Self {
+            buffer: self.buffer.clone(),
+            position: self.position,
+            stream: None,
+            max_size: self.max_size,
+            error: self.error.clone(),
+        })
     }
 }
 
@@ -50,15 +755,186 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_incoming_response() {
+    fn test_method_from_v0_1() {
+        assert_eq!(Method::from(MethodV0_1::Get), Method::Get);
+        assert_eq!(Method::from(MethodV0_1::Delete), Method::Delete);
+    }
+
+    #[test]
+    fn test_method_is_idempotent() {
+        assert!(Method::Get.is_idempotent());
+        assert!(Method::Delete.is_idempotent());
+        assert!(!Method::Post.is_idempotent());
+        assert!(!Method::Patch.is_idempotent());
+        assert!(!Method::Other("PURGE".to_string()).is_idempotent());
+    }
+
+    #[test]
+    fn test_request_body_encode() {
+        let mut headers = vec![];
+        let body = RequestBody::Bytes(vec![1, 2, 3]).encode(&mut headers);
+        assert_eq!(body, vec![1, 2, 3]);
+        assert!(headers.is_empty());
+
+        let fields = vec![
+            ("q".to_string(), "one piece".to_string()),
+            ("lang".to_string(), "日本&en".to_string()),
+        ];
+        let body = RequestBody::Form(fields).encode(&mut headers);
+        assert_eq!(body, b"q=one+piece&lang=%E6%97%A5%E6%9C%AC%26en");
+        assert_eq!(
+            headers,
+            vec![(
+                "content-type".to_string(),
+                "application/x-www-form-urlencoded".to_string()
+            )]
+        );
+
+        // The content type set by the source is kept.
+        let mut headers = vec![(
+            "Content-Type".to_string(),
+            "application/vnd.api+json".to_string(),
+        )];
+        let body = RequestBody::Json("{}".to_string()).encode(&mut headers);
+        assert_eq!(body, b"{}");
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].1, "application/vnd.api+json");
+    }
+
+    #[test]
+    fn test_request_body_encode_multipart() {
+        let parts = vec![
+            MultipartPart {
+                name: "title".to_string(),
+                filename: None,
+                content_type: None,
+                data: b"Berserk".to_vec(),
+            },
+            MultipartPart {
+                name: "cover".to_string(),
+                filename: Some("cover \"1\".png".to_string()),
+                content_type: None,
+                data: vec![0x89, 0x50],
+            },
+        ];
+        // The boundary given by the source cannot match the encoded body.
+        let mut headers = vec![(
+            "content-type".to_string(),
+            "multipart/form-data; boundary=x".to_string(),
+        )];
+        let body = RequestBody::Multipart(parts).encode(&mut headers);
+
+        assert_eq!(headers.len(), 1);
+        let boundary = headers[0]
+            .1
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        assert!(boundary.starts_with("midoku-boundary-"));
+
+        let mut expected = format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"title\"\r\n\
+             \r\n\
+             Berserk\r\n\
+             --{boundary}\r\n\
+             Content-Disposition: form-data; name=\"cover\"; filename=\"cover %221%22.png\"\r\n\
+             Content-Type: application/octet-stream\r\n\
+             \r\n"
+        )
+        .into_bytes();
+        expected.extend([0x89, 0x50]);
+        expected.extend(format!("\r\n--{boundary}--\r\n").as_bytes());
+        assert_eq!(body, expected);
+    }
+
+    #[tokio::test]
+    async fn test_incoming_response() {
         let status_code = 200;
         let headers = vec![("Content-Type".to_string(), "application/json".to_string())];
         let bytes = vec![1, 2, 3];
 
-        let response = IncomingResponse::new(status_code, headers.clone(), bytes.clone());
+        let mut response = IncomingResponse::new(status_code, headers.clone(), bytes.clone());
 
         assert_eq!(response.status_code(), status_code);
         assert_eq!(response.headers(), &headers);
-        assert_eq!(response.bytes(), &bytes);
+        assert_eq!(response.bytes().await.unwrap(), &bytes);
+        // The bytes can be read again.
+        assert_eq!(response.bytes().await.unwrap(), &bytes);
+
+        assert!(response.take_body().is_some());
+        assert!(response.take_body().is_none());
+        assert!(response.bytes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_incoming_response_text() {
+        let headers = vec![(
+            "Content-Type".to_string(),
+            "text/html; charset=\"Shift_JIS\"".to_string(),
+        )];
+        // "日本" in Shift_JIS
+        let bytes = vec![0x93, 0xfa, 0x96, 0x7b];
+        let mut response = IncomingResponse::new(200, headers, bytes);
+        assert_eq!(response.text(None).await.unwrap(), "日本");
+
+        let mut response = IncomingResponse::new(200, vec![], "日本".as_bytes().to_vec());
+        assert_eq!(response.text(None).await.unwrap(), "日本");
+
+        // EUC-KR
+        let mut response = IncomingResponse::new(200, vec![], vec![0xc7, 0xd1]);
+        assert_eq!(response.text(Some("euc-kr")).await.unwrap(), "한");
+        assert_eq!(response.text(None).await.unwrap(), "\u{fffd}\u{fffd}");
+        assert_eq!(
+            response.text(Some("klingon")).await,
+            Err(HttpError::Other(
+                "unsupported encoding `klingon`".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_header_value() {
+        assert_eq!(decode_header_value(b"text/html"), "text/html");
+        assert_eq!(decode_header_value("café".as_bytes()), "café");
+        // Latin-1
+        assert_eq!(decode_header_value(b"caf\xe9"), "café");
+    }
+
+    #[tokio::test]
+    async fn test_incoming_body_read() {
+        let mut body = IncomingBody::new(vec![1, 2, 3, 4, 5]);
+
+        assert_eq!(body.read(2).await.unwrap(), vec![1, 2]);
+        assert_eq!(body.skip(2).await.unwrap(), 2);
+        assert!(!body.is_end_of_stream());
+        assert_eq!(body.read(10).await.unwrap(), vec![5]);
+        assert!(body.is_end_of_stream());
+
+        assert!(body.read(10).await.unwrap().is_empty());
+        assert_eq!(body.skip(10).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_incoming_body_max_size() {
+        let mut response =
+            IncomingResponse::new(200, vec![], vec![1, 2, 3, 4, 5]).with_max_body_size(Some(4));
+        assert_eq!(response.bytes().await, Err(HttpError::BodyTooLarge));
+        assert_eq!(response.text(None).await, Err(HttpError::BodyTooLarge));
+
+        // The bytes already read do not count.
+        let mut body = response.take_body().unwrap();
+        assert_eq!(body.read(1).await.unwrap(), vec![1]);
+        assert_eq!(body.buffer().await.unwrap(), &[2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_incoming_body_write_to() {
+        let mut body = IncomingBody::new(vec![1, 2, 3, 4, 5]);
+        assert_eq!(body.read(1).await.unwrap(), vec![1]);
+
+        let mut bytes = Vec::new();
+        assert_eq!(body.write_to(&mut bytes).await.unwrap(), 4);
+        assert_eq!(bytes, vec![2, 3, 4, 5]);
+        assert!(body.is_end_of_stream());
     }
 }
@@ -10,8 +10,8 @@ async fn test_handle_response_body() {
     )
     .await;
     assert!(response.is_ok());
-    let response = response.unwrap();
-    let bytes = response.bytes();
+    let mut response = response.unwrap();
+    let bytes = response.bytes().await.unwrap();
 
     let expected = r#"{
   "userId": 1,
@@ -22,3 +22,30 @@ async fn test_handle_response_body() {
     let actual = std::str::from_utf8(bytes).unwrap();
     assert_eq!(actual, expected);
 }
+
+#[tokio::test]
+async fn test_mock_transport_response_body() {
+    use midoku_http::transport::HttpTransport;
+
+    let transport = midoku_http::transport::MockTransport::new().with_response(
+        midoku_http::types::Method::Get,
+        "https://jsonplaceholder.typicode.com/todos/1",
+        midoku_http::types::IncomingResponse::new(
+            200,
+            vec![("content-type".to_string(), "application/json".to_string())],
+            br#"{"id": 1}"#.to_vec(),
+        ),
+    );
+
+    let request = midoku_http::transport::Request {
+        method: midoku_http::types::Method::Get,
+        url: "https://jsonplaceholder.typicode.com/todos/1".to_string(),
+        headers: vec![],
+        raw_headers: vec![],
+        body: None,
+    };
+    let mut response = transport.send(request).await.unwrap();
+
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.bytes().await.unwrap(), br#"{"id": 1}"#);
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::rate_limiter::RateLimiter;
+
+/// Rate limiters keyed by host, e.g. `cdn.example.com`, or by any name chosen
+/// by the source.
+///
+/// Each limiter has its own burst size and period, so that e.g. the images of
+/// a CDN are not throttled at the rate of an API.
+///
+/// # Example
+/// ```ignore
+/// let limiters = KeyedRateLimiters::default();
+/// limiters.insert("api.example.com", RateLimiter::new(3, 1000).unwrap());
+///
+/// if let Some(limiter) = limiters.get("api.example.com") {
+///     limiter.block().await;
+/// }
+/// ```
+#[derive(Default)]
+pub struct KeyedRateLimiters {
+    limiters: RwLock<HashMap<String, Arc<RateLimiter>>>,
+}
+
+impl KeyedRateLimiters {
+    /// Set the limiter with the given key, replacing the previous one.
+    pub fn insert(&self, key: impl Into<String>, limiter: RateLimiter) {
+        let mut limiters = self.limiters.write().unwrap();
+        limiters.insert(key.into(), Arc::new(limiter));
+    }
+
+    /// Returns the limiter with the given key.
+    ///
+    /// The limiter is shared, waiting on it does not prevent other limiters
+    /// from being set.
+    pub fn get(&self, key: &str) -> Option<Arc<RateLimiter>> {
+        self.limiters.read().unwrap().get(key).cloned()
+    }
+
+    /// Remove the limiter with the given key.
+    ///
+    /// Returns `true` if there was a limiter with this key.
+    pub fn remove(&self, key: &str) -> bool {
+        self.limiters.write().unwrap().remove(key).is_some()
+    }
+
+    /// Returns the keys of the limiters, in no particular order.
+    pub fn keys(&self) -> Vec<String> {
+        self.limiters.read().unwrap().keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyed_rate_limiters() {
+        let limiters = KeyedRateLimiters::default();
+        assert!(limiters.get("api.example.com").is_none());
+
+        limiters.insert("api.example.com", RateLimiter::new(1, 1000).unwrap());
+        limiters.insert("images", RateLimiter::new(5, 1000).unwrap());
+
+        let limiter = limiters.get("api.example.com").unwrap();
+        assert!(limiter.ready());
+        assert!(!limiter.ready());
+        // Each key has its own limiter.
+        assert!(limiters.get("images").unwrap().ready());
+
+        let mut keys = limiters.keys();
+        keys.sort();
+        assert_eq!(keys, vec!["api.example.com", "images"]);
+
+        // Replacing a limiter resets it.
+        limiters.insert("api.example.com", RateLimiter::new(1, 1000).unwrap());
+        assert!(limiters.get("api.example.com").unwrap().ready());
+
+        assert!(limiters.remove("images"));
+        assert!(!limiters.remove("images"));
+        assert!(limiters.get("images").is_none());
+    }
+}
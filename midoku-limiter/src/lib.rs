@@ -1 +1,2 @@
+pub mod keyed;
 pub mod rate_limiter;
@@ -1,15 +1,58 @@
 use std::num::NonZeroU32;
+use std::sync::Mutex;
 use std::time::Duration;
 
-use governor::{DefaultDirectRateLimiter, Quota, RateLimiter as GovernorRateLimiter};
+use governor::clock::Clock;
+use governor::middleware::NoOpMiddleware;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter as GovernorRateLimiter};
+use tokio::time::Instant;
+
+type DirectRateLimiter =
+    GovernorRateLimiter<NotKeyed, InMemoryState, TokioClock, NoOpMiddleware<Duration>>;
+
+/// A clock following the time of the Tokio runtime, so that the limiter waits
+/// with the timers of the runtime and follows its clock when it is paused.
+#[derive(Debug, Clone)]
+struct TokioClock {
+    start: Instant,
+}
+
+impl TokioClock {
+    fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for TokioClock {
+    type Instant = Duration;
+
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
 
 pub struct RateLimiter {
     burst: u32,
     period_ms: u32,
-    limiter: DefaultDirectRateLimiter,
+    limiter: DirectRateLimiter,
+    paused_until: Mutex<Option<Instant>>,
 }
 
 impl RateLimiter {
+    /// Create a limiter allowing `burst` calls every `period_ms` milliseconds.
+    ///
+    /// Returns `None` if the burst size or the period is zero.
+    pub fn new(burst: u32, period_ms: u32) -> Option<Self> {
+        let mut limiter = Self::default();
+        limiter.set_limiter(burst, period_ms).ok()?;
+        limiter.burst = burst;
+        limiter.period_ms = period_ms;
+        Some(limiter)
+    }
+
     pub fn burst(&self) -> u32 {
         self.burst
     }
@@ -46,16 +89,44 @@ impl RateLimiter {
             .ok_or(())?
             .allow_burst(burst);
 
-        self.limiter = GovernorRateLimiter::direct(quota);
+        self.limiter = GovernorRateLimiter::direct_with_clock(quota, TokioClock::new());
         Ok(())
     }
 
+    /// Pause the limiter for `duration`, e.g. when a server asks to retry
+    /// later.
+    ///
+    /// The limiter is not ready until the pause is over. A shorter pause than
+    /// the current one has no effect.
+    pub fn pause(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut paused_until = self.paused_until.lock().unwrap();
+        if paused_until.is_none_or(|paused_until| paused_until < until) {
+            *paused_until = Some(until);
+        }
+    }
+
+    /// Returns the end of the current pause, if the limiter is paused.
+    pub fn paused_until(&self) -> Option<Instant> {
+        self.paused_until
+            .lock()
+            .unwrap()
+            .filter(|&paused_until| paused_until > Instant::now())
+    }
+
     pub fn ready(&self) -> bool {
-        self.limiter.check().is_ok()
+        self.paused_until().is_none() && self.limiter.check().is_ok()
     }
 
     pub async fn block(&self) {
-        self.limiter.until_ready().await;
+        while let Some(paused_until) = self.paused_until() {
+            tokio::time::sleep_until(paused_until).await;
+        }
+
+        while let Err(not_until) = self.limiter.check() {
+            let now = self.limiter.clock().now();
+            tokio::time::sleep(not_until.wait_time_from(now)).await;
+        }
     }
 }
 
@@ -69,7 +140,8 @@ impl Default for RateLimiter {
         Self {
             burst,
             period_ms,
-            limiter: GovernorRateLimiter::direct(quota),
+            limiter: GovernorRateLimiter::direct_with_clock(quota, TokioClock::new()),
+            paused_until: Mutex::new(None),
         }
     }
 }
@@ -86,6 +158,21 @@ mod tests {
         assert_eq!(rate_limiter.period_ms, 1);
     }
 
+    #[test]
+    fn test_rate_limiter_new() {
+        let rate_limiter = RateLimiter::new(3, 1000).unwrap();
+
+        assert_eq!(rate_limiter.burst(), 3);
+        assert_eq!(rate_limiter.period_ms(), 1000);
+        assert!(rate_limiter.ready());
+        assert!(rate_limiter.ready());
+        assert!(rate_limiter.ready());
+        assert!(!rate_limiter.ready());
+
+        assert!(RateLimiter::new(0, 1000).is_none());
+        assert!(RateLimiter::new(3, 0).is_none());
+    }
+
     #[test]
     fn test_rate_limiter_get_burst() {
         let rate_limiter = RateLimiter::default();
@@ -190,4 +277,21 @@ mod tests {
 
         assert!(elapsed >= 1);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_pause() {
+        let rate_limiter = RateLimiter::default();
+
+        rate_limiter.pause(Duration::from_secs(20));
+        // A shorter pause does not shorten the current one.
+        rate_limiter.pause(Duration::from_secs(1));
+        assert!(rate_limiter.paused_until().is_some());
+        assert!(!rate_limiter.ready());
+
+        let start_time = Instant::now();
+        rate_limiter.block().await;
+
+        assert!(start_time.elapsed() >= Duration::from_secs(20));
+        assert!(rate_limiter.paused_until().is_none());
+    }
 }
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use wasmtime::component::{ComponentType, Lift, Lower};
+
+#[derive(Serialize, Deserialize, ComponentType, Lift, Lower, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+#[component(variant)]
+pub enum SourceError {
+    /// The source could not be reached (e.g. the site is down).
+    #[component(name = "network")]
+    Network(String),
+    /// The response from the source could not be parsed.
+    #[component(name = "parse")]
+    Parse(String),
+    /// The requested item does not exist (e.g. the manga was removed).
+    #[component(name = "not-found")]
+    NotFound(String),
+    /// The source is rate limiting the requests.
+    #[component(name = "rate-limited")]
+    RateLimited(String),
+    /// The source requires the user to be logged in.
+    #[component(name = "requires-login")]
+    RequiresLogin(String),
+    /// The operation is not supported by the source.
+    #[component(name = "unsupported")]
+    Unsupported(String),
+    /// Any other error.
+    #[component(name = "other")]
+    Other(String),
+}
+
+impl SourceError {
+    /// Returns the message describing the error.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Network(message)
+            | Self::Parse(message)
+            | Self::NotFound(message)
+            | Self::RateLimited(message)
+            | Self::RequiresLogin(message)
+            | Self::Unsupported(message)
+            | Self::Other(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Network(_) => "network error",
+            Self::Parse(_) => "parse error",
+            Self::NotFound(_) => "not found",
+            Self::RateLimited(_) => "rate limited",
+            Self::RequiresLogin(_) => "requires login",
+            Self::Unsupported(_) => "unsupported",
+            Self::Other(_) => "error",
+        };
+
+        match self.message() {
+            "" => write!(f, "{kind}"),
+            message => write!(f, "{kind}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
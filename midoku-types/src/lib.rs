@@ -1,4 +1,6 @@
 pub mod chapter;
+pub mod error;
 pub mod filter;
 pub mod manga;
 pub mod page;
+pub mod source_info;
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use wasmtime::component::{ComponentType, Lift, Lower};
+
+/// Describes a source, independently of any request to its website.
+#[derive(Serialize, Deserialize, ComponentType, Lift, Lower, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[component(record)]
+pub struct SourceInfo {
+    pub name: String,
+    /// The version of the source, e.g. `1.2.0`.
+    pub version: String,
+    /// The languages of the content, as ISO 639-1 codes (e.g. `en`).
+    pub languages: Vec<String>,
+    /// The URL of the website the source gets its content from.
+    #[component(name = "base-url")]
+    pub base_url: String,
+    /// If true, the source mostly provides content not safe for work.
+    pub nsfw: bool,
+    /// The icon of the source, as a PNG image.
+    pub icon: Option<Vec<u8>>,
+    /// The hosts the source sends requests to, e.g. `example.com` or
+    /// `*.example-cdn.com` for any of its subdomains.
+    ///
+    /// If set, the host denies requests to any other host.
+    #[component(name = "allowed-hosts")]
+    pub allowed_hosts: Option<Vec<String>>,
+}